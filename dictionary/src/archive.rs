@@ -0,0 +1,278 @@
+//! Language packs loaded from a tar or zip archive
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+use flate2::bufread::MultiGzDecoder;
+
+use crate::{Dictionary, LoadOptions, Metadata, WordLength};
+
+/// Archive entry holding the words which can be answers
+pub const ANSWERS_ENTRY: &str = "answers.txt";
+
+/// Archive entry holding the extra words accepted as guesses
+pub const GUESSES_ENTRY: &str = "guesses.txt";
+
+/// Archive entry describing the language pack
+pub const META_ENTRY: &str = "meta.toml";
+
+/// A complete language pack: the answer words and the words accepted as guesses
+pub struct DictionarySet {
+    answers: Dictionary,
+    guesses: Dictionary,
+}
+
+impl DictionarySet {
+    /// Loads a language pack from a tar, gzip compressed tar or zip archive file
+    /// The archive must contain answers.txt and may contain guesses.txt and meta.toml. Entries
+    /// are found by file name, so they can be in a directory in the archive. The word lists may
+    /// be compressed as Dictionary::new_from_bufread allows
+    pub fn new_from_archive(
+        file: &str,
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        if verbose {
+            println!("Loading language pack from archive {file}");
+        }
+
+        Self::new_from_reader(&mut File::open(Path::new(file))?, options, verbose)
+    }
+
+    /// Loads a language pack from a tar, gzip compressed tar or zip archive byte array
+    pub fn new_from_bytes(
+        bytes: &[u8],
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        Self::new_from_reader(&mut Cursor::new(bytes), options, verbose)
+    }
+
+    /// Loads a language pack from a tar, gzip compressed tar or zip archive reader
+    fn new_from_reader<R: Read + Seek>(
+        reader: &mut R,
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        let mut options = options.into();
+        let mut entries = read_entries(reader)?;
+
+        let Some(answers) = entries.remove(ANSWERS_ENTRY) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Language pack has no {ANSWERS_ENTRY}"),
+            ));
+        };
+
+        let meta = match entries.remove(META_ENTRY) {
+            Some(meta) => {
+                let meta = String::from_utf8(meta).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{META_ENTRY} isn't UTF-8 text"),
+                    )
+                })?;
+
+                Some(Metadata::parse_toml(&meta))
+            }
+            None => None,
+        };
+
+        // The word length in meta.toml sets the board size for both lists
+        if let (WordLength::Detect, Some(length)) =
+            (options.length, meta.as_ref().and_then(|meta| meta.length))
+        {
+            options.length = WordLength::Exactly(length);
+        }
+
+        if verbose {
+            println!("Loading answers from {ANSWERS_ENTRY}");
+        }
+
+        let mut answers = Dictionary::new_from_bytes(&answers, options, verbose)?;
+
+        // Guesses are the answers plus any extra words of the same length
+        let answer_words: Vec<String> = answers
+            .word_elems()
+            .map(|elem| answers.get_word(elem))
+            .collect();
+
+        let mut guesses = match entries.remove(GUESSES_ENTRY) {
+            Some(guesses) => {
+                if verbose {
+                    println!("Loading guesses from {GUESSES_ENTRY}");
+                }
+
+                if options.length != WordLength::Any {
+                    options.length = WordLength::Exactly(answers.word_len());
+                }
+
+                Dictionary::new_from_bytes(&guesses, options, verbose)?.with_words(&answer_words)?
+            }
+            None => answers.with_words(&[])?,
+        };
+
+        // meta.toml describes both lists in place of their own metadata lines
+        if let Some(meta) = meta {
+            answers.metadata = meta.clone();
+            guesses.metadata = meta;
+        } else {
+            guesses.metadata = answers.metadata.clone();
+        }
+
+        Ok(Self { answers, guesses })
+    }
+
+    /// Returns the words which can be answers
+    pub fn answers(&self) -> &Dictionary {
+        &self.answers
+    }
+
+    /// Returns the words accepted as guesses, including the answers
+    pub fn guesses(&self) -> &Dictionary {
+        &self.guesses
+    }
+
+    /// Returns the language pack description from meta.toml, or from the answer word list if
+    /// the archive has no meta.toml
+    pub fn metadata(&self) -> &Metadata {
+        self.answers.metadata()
+    }
+
+    /// Splits the language pack into the answer and guess dictionaries
+    pub fn into_parts(self) -> (Dictionary, Dictionary) {
+        (self.answers, self.guesses)
+    }
+}
+
+impl Metadata {
+    /// Parses the top level `key = value` lines of a meta.toml file. Quotes around strings are
+    /// removed, and comments, tables and unknown keys are ignored
+    fn parse_toml(toml: &str) -> Self {
+        let mut metadata = Self::default();
+
+        for line in toml.lines() {
+            let line = line.trim();
+
+            if line.starts_with('[') {
+                // Keys after a table header aren't at the top level
+                break;
+            }
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim();
+                let value = match value.strip_prefix('"') {
+                    Some(quoted) => quoted.split('"').next().unwrap_or_default(),
+                    None => value.split('#').next().unwrap_or_default().trim(),
+                };
+
+                metadata.parse(&format!("{key}={value}"));
+            }
+        }
+
+        metadata
+    }
+}
+
+/// Archive format of a language pack
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Archive {
+    /// zip archive
+    Zip,
+    /// gzip compressed tar archive
+    TarGz,
+    /// Uncompressed tar archive
+    Tar,
+}
+
+impl Archive {
+    /// Detects the archive format from the signature at the start of the data
+    fn detect(buf: &[u8]) -> Self {
+        if buf.starts_with(b"PK\x03\x04") {
+            Self::Zip
+        } else if buf.starts_with(&[0x1f, 0x8b]) {
+            Self::TarGz
+        } else {
+            Self::Tar
+        }
+    }
+}
+
+/// Reads the language pack entries from an archive, keyed by file name. The first entry with
+/// each name is used
+fn read_entries<R: Read + Seek>(reader: &mut R) -> io::Result<BTreeMap<&'static str, Vec<u8>>> {
+    let mut signature = [0; 4];
+    let sig_len = reader.read(&mut signature)?;
+    reader.rewind()?;
+
+    let mut entries = BTreeMap::new();
+
+    let mut add = |name: &str, read: &mut dyn Read| -> io::Result<()> {
+        let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+
+        if let Some(entry) = [ANSWERS_ENTRY, GUESSES_ENTRY, META_ENTRY]
+            .into_iter()
+            .find(|entry| *entry == name)
+        {
+            if !entries.contains_key(entry) {
+                let mut data = Vec::new();
+                read.read_to_end(&mut data)?;
+                entries.insert(entry, data);
+            }
+        }
+
+        Ok(())
+    };
+
+    match Archive::detect(&signature[..sig_len]) {
+        Archive::Zip => {
+            let mut zip = zip::ZipArchive::new(reader).map_err(archive_error)?;
+
+            for i in 0..zip.len() {
+                let mut file = zip.by_index(i).map_err(archive_error)?;
+
+                if file.is_file() {
+                    let name = file.name().to_string();
+                    add(&name, &mut file)?;
+                }
+            }
+        }
+        Archive::TarGz => read_tar(MultiGzDecoder::new(BufReader::new(reader)), &mut add)?,
+        Archive::Tar => read_tar(reader, &mut add)?,
+    }
+
+    Ok(entries)
+}
+
+/// Passes each file in a tar archive to a function with its path
+fn read_tar(
+    reader: impl Read,
+    add: &mut dyn FnMut(&str, &mut dyn Read) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut tar = tar::Archive::new(reader);
+
+    for entry in tar.entries().map_err(archive_error)? {
+        let mut entry = entry.map_err(archive_error)?;
+
+        if entry.header().entry_type().is_file() {
+            let name = entry.path()?.to_string_lossy().into_owned();
+            add(&name, &mut entry)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Describes an error from reading the archive
+fn archive_error(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Language pack archive is corrupt: {err}"),
+    )
+}
@@ -0,0 +1,60 @@
+//! Word list checksum verification
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+use log::info;
+use sha2::{Digest, Sha256};
+
+/// Path of the word list bundled with the solver
+pub const BUNDLED_DICT: &str = "words.txt.gz";
+
+/// SHA-256 hash of the bundled word list
+pub const BUNDLED_SHA256: &str = "f3f6a7711e42d7ec2e59a40aa6b3c737406d3e3ecd0d3ec0c1f9eff3f3fab2ec";
+
+/// Returns the expected SHA-256 hash of a word list file if it is the bundled word list
+pub fn bundled_sha256(file: &str) -> Option<&'static str> {
+    if Path::new(file) == Path::new(BUNDLED_DICT) {
+        Some(BUNDLED_SHA256)
+    } else {
+        None
+    }
+}
+
+/// Calculates the SHA-256 hash of a file as a lower case hex string
+pub fn file_sha256(file: &str) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+
+    io::copy(&mut BufReader::new(File::open(file)?), &mut hasher)?;
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Checks the SHA-256 hash of a file matches the expected hex string, returning an invalid data
+/// error if not
+pub fn verify_sha256(file: &str, expected: &str, verbose: bool) -> io::Result<()> {
+    let actual = file_sha256(file)?;
+
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "SHA-256 of {file} is {actual}, expected {}",
+                expected.trim()
+            ),
+        ));
+    }
+
+    info!("Verified SHA-256 of {file}");
+
+    if verbose {
+        println!("Verified SHA-256 of {file}");
+    }
+
+    Ok(())
+}
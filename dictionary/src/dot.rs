@@ -0,0 +1,44 @@
+//! Graphviz export of the dictionary tree
+
+use std::io::{self, Write};
+
+use crate::{Dictionary, LETTER_BITS, WORD_END};
+
+impl Dictionary {
+    /// Writes the dictionary tree down to a depth in Graphviz DOT format
+    /// Nodes ending a word are drawn with a double circle and nodes with children below the
+    /// depth limit are dashed
+    pub fn export_dot(&self, writer: &mut dyn Write, max_depth: usize) -> io::Result<()> {
+        writeln!(writer, "digraph dictionary {{")?;
+        writeln!(writer, "    node [shape=circle];")?;
+        writeln!(writer, "    n0 [label=\"\"];")?;
+
+        // Nodes are in breadth first order so each node's depth is known before its children
+        let mut depths = vec![0; self.tree.len()];
+
+        for (elem, ent) in self.tree.iter().enumerate().skip(1) {
+            let depth = depths[ent.parent as usize] + 1;
+
+            if depth > max_depth {
+                break;
+            }
+
+            depths[elem] = depth;
+
+            let mut attrs = format!("label=\"{}\"", (b'A' + ent.letter) as char);
+
+            if ent.children & WORD_END != 0 {
+                attrs.push_str(", shape=doublecircle");
+            }
+
+            if depth == max_depth && ent.children & LETTER_BITS != 0 {
+                attrs.push_str(", style=dashed");
+            }
+
+            writeln!(writer, "    n{elem} [{attrs}];")?;
+            writeln!(writer, "    n{} -> n{elem};", ent.parent)?;
+        }
+
+        writeln!(writer, "}}")
+    }
+}
@@ -0,0 +1,60 @@
+//! Word list load errors
+
+use std::io;
+
+/// Reason a compressed word list can't be loaded
+/// The loading functions return these as the inner error of an io::Error, with the InvalidData
+/// kind for truncated or corrupt data and Unsupported for a missing feature. Use
+/// DictionaryError::of to get the reason back from the io::Error
+#[derive(Debug)]
+pub enum DictionaryError {
+    /// The compressed data ends before the decompressor has finished
+    Truncated,
+    /// The compressed data can't be decompressed, with the decompressor's error
+    Corrupt(io::Error),
+    /// The word list is compressed in a format this build can't read
+    Unsupported {
+        /// Compression format name, which is also the name of the feature needed
+        format: &'static str,
+    },
+}
+
+impl DictionaryError {
+    /// Returns the reason for a word list load error, or None if the error has another cause
+    pub fn of(err: &io::Error) -> Option<&Self> {
+        err.get_ref()?.downcast_ref()
+    }
+}
+
+impl std::fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("Compressed word list is truncated"),
+            Self::Corrupt(err) => write!(f, "Compressed word list is corrupt: {err}"),
+            Self::Unsupported { format } => write!(
+                f,
+                "Word list is {format} compressed, which needs the {format} feature"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Corrupt(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<DictionaryError> for io::Error {
+    fn from(err: DictionaryError) -> Self {
+        let kind = match err {
+            DictionaryError::Unsupported { .. } => io::ErrorKind::Unsupported,
+            _ => io::ErrorKind::InvalidData,
+        };
+
+        io::Error::new(kind, err)
+    }
+}
@@ -0,0 +1,100 @@
+//! Past answers by date
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+use chrono::{Datelike, NaiveDate};
+
+/// Past answers loaded from a file with a date and answer on each line, eg. "2024-03-01 CRANE"
+/// The date and answer may also be separated by a comma or tab. Blank lines and lines starting
+/// with '#' are skipped
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    answers: BTreeMap<NaiveDate, String>,
+}
+
+impl History {
+    /// Loads past answers from a file
+    pub fn new_from_file(file: &str) -> io::Result<Self> {
+        Self::new_from_bufread(&mut BufReader::new(File::open(file)?))
+    }
+
+    /// Loads past answers from a string
+    pub fn new_from_string(string: &str) -> io::Result<Self> {
+        Self::new_from_bufread(&mut BufReader::new(string.as_bytes()))
+    }
+
+    /// Loads past answers from an entity implementing BufRead
+    /// Answers are converted to upper case. Lines which can't be read are reported as invalid
+    /// data errors with the line number
+    pub fn new_from_bufread(bufread: &mut dyn BufRead) -> io::Result<Self> {
+        let mut answers = BTreeMap::new();
+
+        for (line_no, line) in bufread.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |msg: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Line {}: {msg}", line_no + 1),
+                )
+            };
+
+            let (date, answer) = line
+                .split_once(|c: char| c == ',' || c.is_whitespace())
+                .ok_or_else(|| invalid("expected a date and an answer"))?;
+
+            let date: NaiveDate = date
+                .parse()
+                .map_err(|_| invalid(&format!("{date} isn't a YYYY-MM-DD date")))?;
+
+            let answer = answer.trim_matches(|c: char| c == ',' || c.is_whitespace());
+
+            if answer.is_empty() || !answer.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(invalid(&format!("{answer} isn't a word")));
+            }
+
+            answers.insert(date, answer.to_ascii_uppercase());
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// Returns the number of answers
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// Returns true if there are no answers
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    /// Returns the answer for a date
+    pub fn answer(&self, date: NaiveDate) -> Option<&str> {
+        self.answers.get(&date).map(String::as_str)
+    }
+
+    /// Returns the dates of the first and last answers
+    pub fn range(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let first = self.answers.keys().next()?;
+        let last = self.answers.keys().next_back()?;
+
+        Some((*first, *last))
+    }
+
+    /// Returns the answers in a month in date order
+    pub fn month(&self, year: i32, month: u32) -> impl Iterator<Item = (NaiveDate, &str)> {
+        self.answers
+            .iter()
+            .skip_while(move |(date, _)| (date.year(), date.month()) < (year, month))
+            .take_while(move |(date, _)| (date.year(), date.month()) == (year, month))
+            .map(|(date, answer)| (*date, answer.as_str()))
+    }
+}
@@ -2,32 +2,199 @@
 
 //! Word list and loader functions
 
+use std::cell::Cell;
+use std::collections::BTreeMap;
 use std::fs::{read_link, symlink_metadata, File};
 use std::io::prelude::*;
 use std::io::{self, BufReader};
+use std::ops::Range;
 use std::path::PathBuf;
+use std::rc::Rc;
 
-use flate2::bufread::GzDecoder;
+use flate2::bufread::MultiGzDecoder;
+use log::info;
+
+#[cfg(feature = "archive")]
+mod archive;
+mod checksum;
+mod dot;
+mod error;
+mod history;
+
+#[cfg(feature = "archive")]
+pub use archive::{DictionarySet, ANSWERS_ENTRY, GUESSES_ENTRY, META_ENTRY};
+pub use checksum::{bundled_sha256, file_sha256, verify_sha256, BUNDLED_DICT, BUNDLED_SHA256};
+pub use error::DictionaryError;
+pub use history::History;
 
 /// Word next tree node
-pub type LetterNext = u16;
+/// Nodes at the same depth in the tree are numbered in alphabetical order, so sorting the
+/// elements for a list of words sorts the words alphabetically
+pub type LetterNext = u32;
 
 /// No next letter
 pub const NEXT_NONE: LetterNext = LetterNext::MAX;
 
-/// Vector of next letters
+/// Word length used when the word list contains no valid words
+pub const DEFAULT_WORD_LEN: usize = 5;
+
+/// Buffer size for reading words with get_word_into, long enough for any word played on a board
+pub const MAX_WORD_LEN: usize = 64;
+
+/// Word list letter case handling
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WordCase {
+    /// Only accept words which are all lower case
+    #[default]
+    Lower,
+    /// Accept upper and mixed case words, converting them to lower case
+    Normalise,
+}
+
+/// Word list word length handling
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WordLength {
+    /// Only load words of the most common length in the word list
+    #[default]
+    Detect,
+    /// Only load words of the given length
+    Exactly(usize),
+    /// Load words of any length
+    Any,
+}
+
+/// Word list load options
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Letter case handling
+    pub case: WordCase,
+    /// Word length handling
+    pub length: WordLength,
+}
+
+impl From<WordCase> for LoadOptions {
+    fn from(case: WordCase) -> Self {
+        Self {
+            case,
+            ..Self::default()
+        }
+    }
+}
+
+/// Prefix of word list lines describing the list, eg. "#!meta name = English"
+const META_PREFIX: &str = "#!meta";
+
+/// Separator between a word and its frequency rank in a word list line, eg. "crane\t1234"
+const RANK_SEPARATOR: char = '\t';
+
+/// Rank stored for words without a frequency rank
+const NO_RANK: u32 = u32::MAX;
+
+/// Word list description given by `#!meta key = value` lines in a text word list
+/// The keys are name, language, source, version and length
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    /// Word list name
+    pub name: Option<String>,
+    /// Language of the words
+    pub language: Option<String>,
+    /// Where the words came from
+    pub source: Option<String>,
+    /// Word list version
+    pub version: Option<String>,
+    /// Length of the words to load when detecting the word length
+    pub length: Option<usize>,
+}
+
+impl Metadata {
+    /// Parses the key and value after the metadata line prefix. Unknown keys and invalid lengths
+    /// are ignored
+    fn parse(&mut self, line: &str) {
+        let Some((key, value)) = line.split_once('=') else {
+            return;
+        };
+
+        let value = value.trim().to_string();
+
+        match key.trim() {
+            "name" => self.name = Some(value),
+            "language" => self.language = Some(value),
+            "source" => self.source = Some(value),
+            "version" => self.version = Some(value),
+            "length" => self.length = value.parse().ok().or(self.length),
+            _ => (),
+        }
+    }
+}
+
+impl std::fmt::Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name.as_deref().unwrap_or("Word list"))?;
+
+        if let Some(language) = &self.language {
+            write!(f, " ({language})")?;
+        }
+
+        if let Some(version) = &self.version {
+            write!(f, " version {version}")?;
+        }
+
+        if let Some(source) = &self.source {
+            write!(f, " from {source}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Counts of the word list lines read and rejected while loading
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadStats {
+    /// Word lines read, not counting metadata lines
+    pub lines: usize,
+    /// Lines rejected because the word was the wrong length
+    pub wrong_length: usize,
+    /// Lines rejected because the word had upper case or non-letter characters
+    pub wrong_case: usize,
+    /// Lines rejected because the frequency rank after the word wasn't a number
+    pub bad_rank: usize,
+}
+
+/// Children bitmap flag set on nodes which end a word
+const WORD_END: u32 = 1 << 31;
+
+/// Children bitmap letter bits
+const LETTER_BITS: u32 = (1 << 26) - 1;
+
+/// Dictionary tree node
+///
+/// Child nodes are stored contiguously in the tree. The children bitmap has a bit set for each
+/// letter present and the index of a child is found by counting the set bits below it. The top
+/// bit is set if the node ends a word
 struct LetterEnt {
+    children: u32,
+    first_child: LetterNext,
+    parent: LetterNext,
+    letter: u8,
+}
+
+/// Dictionary tree node used while loading
+struct BuildEnt {
     letter_vec: [LetterNext; 26],
     parent: LetterNext,
     letter: u8,
+    end: bool,
+    rank: u32,
 }
 
-impl LetterEnt {
+impl BuildEnt {
     fn new(letter: u8, parent: LetterNext) -> Self {
         Self {
             letter_vec: [NEXT_NONE; 26],
             letter,
             parent,
+            end: false,
+            rank: NO_RANK,
         }
     }
 }
@@ -35,12 +202,23 @@ impl LetterEnt {
 /// Dictionary structure
 pub struct Dictionary {
     words: usize,
+    word_len: usize,
+    length: WordLength,
     tree: Vec<LetterEnt>,
+    first_word: usize,
+    metadata: Metadata,
+    stats: LoadStats,
+    /// Frequency rank of each tree element ending a word, empty if the word list has no ranks
+    ranks: Vec<u32>,
 }
 
 impl Dictionary {
     /// Loads a dictionary from a file
-    pub fn new_from_file(file: &str, verbose: bool) -> io::Result<Self> {
+    pub fn new_from_file(
+        file: &str,
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<Self> {
         let path_buf = PathBuf::from(file);
 
         if verbose {
@@ -48,82 +226,193 @@ impl Dictionary {
         }
 
         // Create buf reader for the file
-        Self::new_from_bufread(&mut BufReader::new(File::open(&path_buf)?), verbose)
+        Self::new_from_bufread(
+            &mut BufReader::new(File::open(&path_buf)?),
+            options,
+            verbose,
+        )
     }
 
     /// Loads a dictionary from a string
     #[allow(dead_code)]
-    pub fn new_from_string(string: &str, verbose: bool) -> io::Result<Self> {
+    pub fn new_from_string(
+        string: &str,
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<Self> {
         if verbose {
             println!("Loading words from string '{string}'");
         }
 
-        Self::new_from_bufread(&mut BufReader::new(string.as_bytes()), verbose)
+        Self::new_from_bufread(&mut BufReader::new(string.as_bytes()), options, verbose)
     }
 
     /// Loads a dictionary from a byte array
     #[allow(dead_code)]
-    pub fn new_from_bytes(bytes: &[u8], verbose: bool) -> io::Result<Self> {
+    pub fn new_from_bytes(
+        bytes: &[u8],
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<Self> {
         if verbose {
             println!("Loading words from byte array (length {})", bytes.len());
         }
 
-        Self::new_from_bufread(&mut BufReader::new(bytes), verbose)
+        Self::new_from_bufread(&mut BufReader::new(bytes), options, verbose)
     }
 
     /// Loads a dictionary from an entity implementing BufRead
-    /// Handles gzip compressed buffers
-    pub fn new_from_bufread(bufread: &mut dyn BufRead, verbose: bool) -> io::Result<Self> {
-        // Fill the bufreader buffer
-        let buf = bufread.fill_buf()?;
-
-        // Check for gzip signature
-        if buf.len() >= 2 && buf[0] == 0x1f && buf[1] == 0x8b {
-            // gzip compressed file
-            if verbose {
-                println!("Decompressing word list");
+    /// Handles gzip compressed buffers, including files of several concatenated gzip members,
+    /// and zstd and xz compressed buffers when built with the zstd and xz features.
+    /// Compressed data which fails to decompress gives an InvalidData error carrying a
+    /// DictionaryError, Truncated if the decompressor had run out of input or Corrupt if not
+    pub fn new_from_bufread(
+        bufread: &mut dyn BufRead,
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        let options = options.into();
+
+        // Fill the bufreader buffer and check for a compression signature
+        let compression = Compression::detect(bufread.fill_buf()?);
+
+        if verbose && compression.is_some() {
+            println!("Decompressing word list");
+        }
+
+        // Set when the compressed input runs out, telling truncated data from corrupt data
+        let at_end = Rc::new(Cell::new(false));
+
+        match compression {
+            None => Self::new_from_bufread_internal(bufread, options, verbose),
+            Some(Compression::Gzip) => {
+                let reader = MultiGzDecoder::new(Input::new(bufread, &at_end));
+
+                Self::new_from_bufread_internal(
+                    &mut BufReader::new(Decompress { reader, at_end }),
+                    options,
+                    verbose,
+                )
             }
+            #[cfg(feature = "zstd")]
+            Some(Compression::Zstd) => {
+                let reader = ruzstd::decoding::StreamingDecoder::new(Input::new(bufread, &at_end))
+                    .map_err(|err| decompress_error(io::Error::other(err), at_end.get()))?;
+
+                Self::new_from_bufread_internal(
+                    &mut BufReader::new(Decompress { reader, at_end }),
+                    options,
+                    verbose,
+                )
+            }
+            #[cfg(feature = "xz")]
+            Some(Compression::Xz) => {
+                // The xz decoder writes all of the data rather than being read from
+                let mut data = Vec::new();
 
-            Self::new_from_bufread_internal(&mut BufReader::new(GzDecoder::new(bufread)), verbose)
-        } else {
-            Self::new_from_bufread_internal(bufread, verbose)
+                let mut input = Input::new(bufread, &at_end);
+
+                lzma_rs::xz_decompress(&mut input, &mut data).map_err(|err| match err {
+                    lzma_rs::error::Error::IoError(err) => decompress_error(err, at_end.get()),
+                    err => decompress_error(io::Error::other(err), at_end.get()),
+                })?;
+
+                Self::new_from_bufread_internal(&mut data.as_slice(), options, verbose)
+            }
+            #[allow(unreachable_patterns)]
+            Some(compression) => Err(DictionaryError::Unsupported {
+                format: compression.name(),
+            }
+            .into()),
         }
     }
 
     /// Loads a dictionary from an entity implementing BufRead
-    fn new_from_bufread_internal(bufread: &mut dyn BufRead, verbose: bool) -> io::Result<Self> {
-        let mut tree = Vec::new();
+    fn new_from_bufread_internal(
+        bufread: &mut dyn BufRead,
+        options: LoadOptions,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        let case = options.case;
 
         let mut lines: usize = 0;
-        let mut words: usize = 0;
-        let mut wrong_length: usize = 0;
         let mut wrong_case: usize = 0;
+        let mut bad_rank: usize = 0;
 
-        tree.push(LetterEnt::new(0, NEXT_NONE));
+        let mut candidates = Vec::new();
+        let mut length_counts: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut metadata = Metadata::default();
 
         // Iterate file lines
         for line in bufread.lines() {
-            let line = line?;
+            let mut line = line?;
+
+            // Read word list description lines
+            if let Some(meta) = line.strip_prefix(META_PREFIX) {
+                metadata.parse(meta);
+                continue;
+            }
 
             lines += 1;
 
-            // Check length
-            let length = line.len();
+            // Split off the frequency rank if given
+            let rank = match line.split_once(RANK_SEPARATOR) {
+                Some((word, rank)) => match rank.trim().parse::<u32>() {
+                    Ok(rank) if rank != NO_RANK => {
+                        line.truncate(word.len());
+                        rank
+                    }
+                    _ => {
+                        bad_rank += 1;
+                        continue;
+                    }
+                },
+                None => NO_RANK,
+            };
 
-            if length != 5 {
-                wrong_length += 1;
-                continue;
+            // Convert to lower case if required
+            if case == WordCase::Normalise {
+                line.make_ascii_lowercase();
             }
 
             // Make sure word consists of all lower case ascii characters
-            if !Self::is_ascii_lower(&line) {
+            if line.is_empty() || !Self::is_ascii_lower(&line) {
                 wrong_case += 1;
                 continue;
             }
 
-            // Add this word to the tree
-            words += 1;
+            *length_counts.entry(line.len()).or_default() += 1;
+            candidates.push((line, rank));
+        }
+
+        // Choose the most common word length, preferring the shortest on a tie
+        let common_len = length_counts
+            .iter()
+            .rev()
+            .max_by_key(|(_, &count)| count)
+            .map(|(&len, _)| len)
+            .unwrap_or(DEFAULT_WORD_LEN);
+
+        let word_len = match options.length {
+            WordLength::Exactly(len) => len,
+            WordLength::Detect => metadata.length.unwrap_or(common_len),
+            WordLength::Any => common_len,
+        };
+
+        let mut tree = Vec::new();
 
+        let mut wrong_length: usize = 0;
+
+        tree.push(BuildEnt::new(0, NEXT_NONE));
+
+        for (line, rank) in candidates {
+            // Check length
+            if options.length != WordLength::Any && line.len() != word_len {
+                wrong_length += 1;
+                continue;
+            }
+
+            // Add this word to the tree
             let mut cur_elem = 0;
 
             for c in line.chars() {
@@ -131,7 +420,7 @@ impl Dictionary {
 
                 cur_elem = match tree[cur_elem].letter_vec[letter] {
                     NEXT_NONE => {
-                        tree.push(LetterEnt::new(letter as u8, cur_elem as LetterNext));
+                        tree.push(BuildEnt::new(letter as u8, cur_elem as LetterNext));
                         let e = tree.len() - 1;
                         tree[cur_elem].letter_vec[letter] = e as LetterNext;
                         e
@@ -139,19 +428,80 @@ impl Dictionary {
                     e => e as usize,
                 };
             }
+
+            // Keep the best rank of a repeated word
+            tree[cur_elem].end = true;
+            tree[cur_elem].rank = tree[cur_elem].rank.min(rank);
         }
 
-        let dictionary = Self { words, tree };
+        let (tree, ranks) = Self::compress(&tree);
+
+        // Nodes are in breadth first order so every word is at or after the first word end.
+        // With words of one length every node from there is a word, but with words of any length
+        // nodes starting longer words are mixed in
+        let first_word = tree
+            .iter()
+            .position(|ent| ent.children & WORD_END != 0)
+            .unwrap_or(tree.len());
+
+        // Duplicate words share a node so count the word ends
+        let dictionary = Self {
+            words: tree
+                .iter()
+                .filter(|ent| ent.children & WORD_END != 0)
+                .count(),
+            word_len,
+            length: options.length,
+            tree,
+            first_word,
+            metadata,
+            stats: LoadStats {
+                lines,
+                wrong_length,
+                wrong_case,
+                bad_rank,
+            },
+            ranks,
+        };
+
+        info!("Word list: {}", dictionary.metadata);
+
+        info!(
+            "Loaded {} words ({}) from {} lines ({} wrong length, {} wrong case, {} bad rank), {} \
+            tree nodes{}",
+            dictionary.word_count(),
+            dictionary.length_text(),
+            lines,
+            wrong_length,
+            wrong_case,
+            bad_rank,
+            dictionary.tree_node_count(),
+            if dictionary.has_ranks() {
+                ", ranked"
+            } else {
+                ""
+            }
+        );
 
         if verbose {
+            println!("Word list: {}", dictionary.metadata);
+
             println!(
-                "{} total words, ({} wrong length, {} not all lower case)",
-                lines, wrong_length, wrong_case
+                "{} total words, ({} wrong length, {} {}, {} bad rank)",
+                lines,
+                wrong_length,
+                wrong_case,
+                match case {
+                    WordCase::Lower => "not all lower case",
+                    WordCase::Normalise => "not all letters",
+                },
+                bad_rank
             );
 
             println!(
-                "Dictionary words {}, tree nodes {} ({} bytes of {} allocated)",
+                "Dictionary words {} ({}), tree nodes {} ({} bytes of {} allocated)",
                 dictionary.word_count(),
+                dictionary.length_text(),
                 dictionary.tree_node_count(),
                 dictionary.tree_mem_usage(),
                 dictionary.tree_mem_alloc(),
@@ -161,11 +511,184 @@ impl Dictionary {
         Ok(dictionary)
     }
 
+    /// Creates a new dictionary containing the words of this dictionary and the extra words given
+    /// Extra words are accepted in any case. Words of the wrong length are ignored. Word ranks
+    /// are kept and the extra words have no rank
+    pub fn with_words(&self, extra: &[String]) -> io::Result<Self> {
+        let mut words = self
+            .word_elems()
+            .map(|elem| {
+                let word = self.get_word(elem).to_ascii_lowercase();
+
+                match self.rank(elem) {
+                    Some(rank) => format!("{word}{RANK_SEPARATOR}{rank}"),
+                    None => word,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        words.extend(
+            extra
+                .iter()
+                .filter(|word| self.length == WordLength::Any || word.len() == self.word_len)
+                .map(|word| word.to_ascii_lowercase()),
+        );
+
+        let options = LoadOptions {
+            case: WordCase::Lower,
+            length: match self.length {
+                WordLength::Any => WordLength::Any,
+                _ => WordLength::Exactly(self.word_len),
+            },
+        };
+
+        let mut dictionary = Self::new_from_string(&words.join("\n"), options, false)?;
+        dictionary.metadata = self.metadata.clone();
+
+        info!(
+            "Added {} extra words to the dictionary",
+            dictionary.word_count() - self.word_count()
+        );
+
+        Ok(dictionary)
+    }
+
+    /// Describes the word lengths loaded
+    fn length_text(&self) -> String {
+        match self.length {
+            WordLength::Any => "any length".to_string(),
+            _ => format!("{} letters", self.word_len),
+        }
+    }
+
+    /// Converts the load tree to the compressed tree and the word ranks
+    /// Nodes are renumbered in breadth first order so each node's children are contiguous.
+    /// Children are visited in letter order so nodes at each depth are in alphabetical order.
+    /// The ranks are empty if no word has a rank
+    fn compress(build: &[BuildEnt]) -> (Vec<LetterEnt>, Vec<u32>) {
+        let mut tree = Vec::with_capacity(build.len());
+
+        // Map of build tree index to compressed tree index, in breadth first order
+        let mut order = Vec::with_capacity(build.len());
+        let mut new_index = vec![NEXT_NONE; build.len()];
+
+        order.push(0);
+        new_index[0] = 0;
+
+        let mut next = 0;
+
+        while next < order.len() {
+            let elem = order[next];
+
+            for &child in build[elem].letter_vec.iter().filter(|&&c| c != NEXT_NONE) {
+                new_index[child as usize] = order.len() as LetterNext;
+                order.push(child as usize);
+            }
+
+            next += 1;
+        }
+
+        // Build the compressed nodes
+        for &elem in &order {
+            let ent = &build[elem];
+
+            let mut children = if ent.end { WORD_END } else { 0 };
+            let mut first_child = NEXT_NONE;
+
+            for (letter, &child) in ent.letter_vec.iter().enumerate() {
+                if child != NEXT_NONE {
+                    if first_child == NEXT_NONE {
+                        first_child = new_index[child as usize];
+                    }
+
+                    children |= 1 << letter;
+                }
+            }
+
+            tree.push(LetterEnt {
+                children,
+                first_child,
+                parent: match ent.parent {
+                    NEXT_NONE => NEXT_NONE,
+                    p => new_index[p as usize],
+                },
+                letter: ent.letter,
+            });
+        }
+
+        let ranks = if build.iter().any(|ent| ent.rank != NO_RANK) {
+            order.iter().map(|&elem| build[elem].rank).collect()
+        } else {
+            Vec::new()
+        };
+
+        (tree, ranks)
+    }
+
+    /// Returns the word list description
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the counts of the lines read and rejected when the word list was loaded
+    pub fn load_stats(&self) -> LoadStats {
+        self.stats
+    }
+
+    /// Tests if the word list gave frequency ranks for its words
+    pub fn has_ranks(&self) -> bool {
+        !self.ranks.is_empty()
+    }
+
+    /// Returns the frequency rank given for the word ending at a tree element, lower ranks being
+    /// more common
+    pub fn rank(&self, elem: usize) -> Option<u32> {
+        self.ranks
+            .get(elem)
+            .copied()
+            .filter(|rank| *rank != NO_RANK)
+    }
+
     /// Returns the number of words stored in the dictionary
     pub fn word_count(&self) -> usize {
         self.words
     }
 
+    /// Returns the length of the words stored in the dictionary
+    /// If words of any length were loaded this is the most common length
+    pub fn word_len(&self) -> usize {
+        self.word_len
+    }
+
+    /// Returns the range of tree elements holding the words, eg. to index a set of words
+    /// If words of any length were loaded the range also holds elements which only start longer
+    /// words, so use word_elems to list the words
+    pub fn word_range(&self) -> Range<usize> {
+        self.first_word..self.tree.len()
+    }
+
+    /// Returns the tree elements which are complete words, shortest words first and in
+    /// alphabetical order for each length
+    pub fn word_elems(&self) -> impl Iterator<Item = usize> + '_ {
+        self.word_range().filter(|elem| self.is_word(*elem))
+    }
+
+    /// Returns the letters used by the words in the dictionary, indexed by letter number
+    pub fn letters_used(&self) -> [bool; 26] {
+        let used = self
+            .tree
+            .iter()
+            .fold(0, |used, ent| used | (ent.children & LETTER_BITS));
+
+        std::array::from_fn(|letter| used & (1 << letter) != 0)
+    }
+
+    /// Tests if a tree element ends a word
+    #[inline]
+    pub fn is_word(&self, elem: usize) -> bool {
+        self.tree[elem].children & WORD_END != 0
+    }
+
     /// Returns the size of the dictionary tree
     pub fn tree_node_count(&self) -> usize {
         self.tree.len()
@@ -184,41 +707,193 @@ impl Dictionary {
     /// Looks up the letter number (0-25) in the dictionary tree node
     #[inline]
     pub fn lookup_elem_letter_num(&self, elem: usize, letter: u8) -> LetterNext {
-        self.tree[elem].letter_vec[letter as usize]
+        let ent = &self.tree[elem];
+        let bit = 1u32 << letter;
+
+        if ent.children & bit == 0 {
+            NEXT_NONE
+        } else {
+            ent.first_child + (ent.children & (bit - 1)).count_ones() as LetterNext
+        }
+    }
+
+    /// Looks up a word in the dictionary, returning its element if found
+    /// Upper and lower case letters are accepted
+    pub fn find(&self, word: &str) -> Option<LetterNext> {
+        if !word.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        let mut elem = 0;
+
+        for c in word.chars() {
+            match self.lookup_elem_letter_num(elem, Self::uchar_to_u8(c.to_ascii_uppercase())) {
+                NEXT_NONE => return None,
+                next => elem = next as usize,
+            }
+        }
+
+        if self.is_word(elem) {
+            Some(elem as LetterNext)
+        } else {
+            None
+        }
+    }
+
+    /// Tests if a word is in the dictionary
+    /// Upper and lower case letters are accepted
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some()
+    }
+
+    /// Finds the words matching a pattern, in alphabetical order
+    /// Letters in the pattern must match and '?', '_' or '.' match any letter. If available
+    /// letters are given, indexed by letter number, only those letters match the blanks
+    pub fn find_pattern(&self, pattern: &str, available: Option<&[bool; 26]>) -> Vec<LetterNext> {
+        let mut result = Vec::new();
+
+        // Convert the pattern to letter numbers with None for blanks
+        let pattern = pattern
+            .chars()
+            .map(|c| match c {
+                '?' | '_' | '.' => Ok(None),
+                c if c.is_ascii_alphabetic() => Ok(Some(Self::uchar_to_u8(c.to_ascii_uppercase()))),
+                _ => Err(()),
+            })
+            .collect::<Result<Vec<_>, _>>();
+
+        if let Ok(pattern) = pattern {
+            self.find_pattern_rec(&pattern, available, 0, &mut result);
+        }
+
+        result
+    }
+
+    fn find_pattern_rec(
+        &self,
+        pattern: &[Option<u8>],
+        available: Option<&[bool; 26]>,
+        elem: usize,
+        result: &mut Vec<LetterNext>,
+    ) {
+        let Some((first, rest)) = pattern.split_first() else {
+            if self.is_word(elem) {
+                result.push(elem as LetterNext);
+            }
+            return;
+        };
+
+        let mut walk = |letter: u8| {
+            let next = self.lookup_elem_letter_num(elem, letter);
+
+            if next != NEXT_NONE {
+                self.find_pattern_rec(rest, available, next as usize, result);
+            }
+        };
+
+        match first {
+            Some(letter) => walk(*letter),
+            None => (0..26u8)
+                .filter(|letter| available.is_none_or(|available| available[*letter as usize]))
+                .for_each(walk),
+        }
+    }
+
+    /// Finds the words made only from the available letters, indexed by letter number, with at
+    /// least the minimum length. Letters can be used more than once. The words are returned in
+    /// alphabetical order
+    pub fn find_letters(&self, available: &[bool; 26], min_len: usize) -> Vec<LetterNext> {
+        let mut result = Vec::new();
+
+        self.find_letters_rec(available, min_len, 0, 0, &mut result);
+
+        result
+    }
+
+    fn find_letters_rec(
+        &self,
+        available: &[bool; 26],
+        min_len: usize,
+        depth: usize,
+        elem: usize,
+        result: &mut Vec<LetterNext>,
+    ) {
+        if depth >= min_len && self.is_word(elem) {
+            result.push(elem as LetterNext);
+        }
+
+        for letter in (0..26u8).filter(|letter| available[*letter as usize]) {
+            let next = self.lookup_elem_letter_num(elem, letter);
+
+            if next != NEXT_NONE {
+                self.find_letters_rec(available, min_len, depth + 1, next as usize, result);
+            }
+        }
     }
 
     /// Returns the word for a dictionary element
     #[inline]
     pub fn get_word(&self, elem: usize) -> String {
-        let mut result = String::with_capacity(5);
+        let mut word = vec![0; self.word_depth(elem)];
 
-        self.get_word_rec(elem, &mut result);
+        self.fill_word(elem, &mut word);
 
-        result
+        String::from_utf8(word).expect("Dictionary letters are not ASCII")
     }
 
+    /// Writes the upper case word for a dictionary element to the start of a buffer without
+    /// allocating, returning the word length. Panics if the buffer is shorter than the word
     #[inline]
-    fn get_word_rec(&self, elem: usize, result: &mut String) {
-        let next_elem = self.tree[elem].parent as usize;
+    pub fn get_word_into(&self, elem: usize, buf: &mut [u8]) -> usize {
+        let len = self.word_depth(elem);
 
-        if next_elem != 0 {
-            self.get_word_rec(next_elem, result);
-        }
+        self.fill_word(elem, &mut buf[..len]);
 
-        result.push((self.tree[elem].letter + b'A') as char)
+        len
     }
 
-    /// Tests if a word contains a given letter
-    pub fn word_contains(&self, mut elem: usize, letter: u8, count: u8, exact: bool) -> bool {
-        let mut counted = 0;
+    /// Returns the upper case word for a dictionary element as an array. Panics if the word isn't
+    /// N letters long
+    #[inline]
+    pub fn get_word_bytes<const N: usize>(&self, elem: usize) -> [u8; N] {
+        let mut word = [0; N];
+
+        assert_eq!(
+            self.get_word_into(elem, &mut word),
+            N,
+            "Word is not {N} letters long"
+        );
+
+        word
+    }
+
+    /// Returns the number of letters in the word ending at a tree element
+    #[inline]
+    fn word_depth(&self, mut elem: usize) -> usize {
+        let mut depth = 0;
 
         while elem != 0 {
-            if self.tree[elem].letter == letter {
-                counted += 1;
-            }
+            depth += 1;
+            elem = self.tree[elem].parent as usize;
+        }
 
+        depth
+    }
+
+    /// Fills a buffer as long as the word ending at a tree element with its letters, walking up
+    /// the tree from the last letter
+    #[inline]
+    fn fill_word(&self, mut elem: usize, word: &mut [u8]) {
+        for c in word.iter_mut().rev() {
+            *c = self.tree[elem].letter + b'A';
             elem = self.tree[elem].parent as usize;
         }
+    }
+
+    /// Tests if a word contains a letter number a number of times. If exact is false the word may
+    /// contain the letter more times
+    pub fn word_contains(&self, elem: usize, letter: u8, count: u8, exact: bool) -> bool {
+        let counted = self.letter_count(elem, letter);
 
         if exact {
             counted == count
@@ -227,6 +902,21 @@ impl Dictionary {
         }
     }
 
+    /// Counts the times a word contains a letter number
+    pub fn letter_count(&self, mut elem: usize, letter: u8) -> u8 {
+        let mut counted = 0;
+
+        while elem != 0 {
+            if self.tree[elem].letter == letter {
+                counted += 1;
+            }
+
+            elem = self.tree[elem].parent as usize;
+        }
+
+        counted
+    }
+
     /// Converts a lower case character to usize
     #[inline]
     pub fn lchar_to_usize(c: char) -> usize {
@@ -267,6 +957,109 @@ impl Dictionary {
     }
 }
 
+/// Compression format of a word list
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compression {
+    /// gzip, always supported
+    Gzip,
+    /// Zstandard, supported with the zstd feature
+    Zstd,
+    /// xz, supported with the xz feature
+    Xz,
+}
+
+impl Compression {
+    /// Detects the compression format from the signature at the start of the data
+    fn detect(buf: &[u8]) -> Option<Self> {
+        if buf.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::Gzip)
+        } else if buf.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::Zstd)
+        } else if buf.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::Xz)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the format's name, which is also the name of the feature supporting it
+    fn name(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+            Self::Xz => "xz",
+        }
+    }
+}
+
+/// Compressed input to a decompressor, recording when it runs out
+struct Input<'a> {
+    reader: &'a mut dyn BufRead,
+    at_end: Rc<Cell<bool>>,
+}
+
+impl<'a> Input<'a> {
+    fn new(reader: &'a mut dyn BufRead, at_end: &Rc<Cell<bool>>) -> Self {
+        Self {
+            reader,
+            at_end: at_end.clone(),
+        }
+    }
+}
+
+impl Read for Input<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.reader.read(buf)?;
+
+        if len == 0 && !buf.is_empty() {
+            self.at_end.set(true);
+        }
+
+        Ok(len)
+    }
+}
+
+impl BufRead for Input<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.reader.fill_buf()?;
+
+        if buf.is_empty() {
+            self.at_end.set(true);
+        }
+
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.reader.consume(amt)
+    }
+}
+
+/// Reads from a decompressor, describing errors from truncated or corrupt data
+struct Decompress<R: Read> {
+    reader: R,
+    /// Set when the decompressor's input has run out
+    at_end: Rc<Cell<bool>>,
+}
+
+impl<R: Read> Read for Decompress<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader
+            .read(buf)
+            .map_err(|err| decompress_error(err, self.at_end.get()))
+    }
+}
+
+/// Describes an error from decompressing the word list. The data is truncated if the
+/// decompressor failed after running out of input, which decompressors report in different ways
+fn decompress_error(err: io::Error, at_end: bool) -> io::Error {
+    if at_end || err.kind() == io::ErrorKind::UnexpectedEof {
+        DictionaryError::Truncated.into()
+    } else {
+        DictionaryError::Corrupt(err).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use flate2::write::GzEncoder;
@@ -283,7 +1076,7 @@ mod tests {
     #[test]
     fn dict1() {
         // Create dictionary with one word in it "rusty"
-        let dictionary = Dictionary::new_from_string("rusty", false).unwrap();
+        let dictionary = Dictionary::new_from_string("rusty", WordCase::Lower, false).unwrap();
 
         test_dict1(dictionary)
     }
@@ -291,7 +1084,8 @@ mod tests {
     #[test]
     fn dict1z() {
         // Create dictionary from compressed data with one word in it "rusty"
-        let dictionary = Dictionary::new_from_bytes(&gz_dict("rusty"), false).unwrap();
+        let dictionary =
+            Dictionary::new_from_bytes(&gz_dict("rusty"), WordCase::Lower, false).unwrap();
 
         test_dict1(dictionary)
     }
@@ -299,7 +1093,7 @@ mod tests {
     fn test_dict1(dictionary: Dictionary) {
         assert_eq!(dictionary.word_count(), 1);
         assert_eq!(dictionary.tree_node_count(), 6);
-        assert_eq!(dictionary.tree_mem_usage(), 6 * 56);
+        assert_eq!(dictionary.tree_mem_usage(), 6 * 16);
 
         assert!(matches!(
             dictionary.lookup_elem_letter_num(0, Dictionary::uchar_to_u8('R')),
@@ -326,7 +1120,8 @@ mod tests {
     #[test]
     fn dict2() {
         // Create dictionary with two words, "rusts" and "rusty"
-        let dictionary = Dictionary::new_from_string("rusts\nrusty", false).unwrap();
+        let dictionary =
+            Dictionary::new_from_string("rusts\nrusty", WordCase::Lower, false).unwrap();
 
         test_dict2(dictionary);
     }
@@ -334,15 +1129,304 @@ mod tests {
     #[test]
     fn dict2z() {
         // Create dictionary from compressed data with two words, "rusts" and "rusty"
-        let dictionary = Dictionary::new_from_bytes(&gz_dict("rusts\nrusty"), false).unwrap();
+        let dictionary =
+            Dictionary::new_from_bytes(&gz_dict("rusts\nrusty"), WordCase::Lower, false).unwrap();
 
         test_dict2(dictionary);
     }
 
+    #[test]
+    fn dict2z_members() {
+        // Create dictionary from two concatenated gzip members, one word in each
+        let mut bytes = gz_dict("rusts\n");
+        bytes.extend(gz_dict("rusty"));
+
+        let dictionary = Dictionary::new_from_bytes(&bytes, WordCase::Lower, false).unwrap();
+
+        test_dict2(dictionary);
+    }
+
+    #[test]
+    fn dict_gz_errors() {
+        let bytes = gz_dict(&["abbey", "abbot", "crane", "crank", "slate", "zebra"].join("\n"));
+
+        let load = |bytes: &[u8]| {
+            Dictionary::new_from_bytes(bytes, WordCase::Lower, false)
+                .map(|_| ())
+                .unwrap_err()
+        };
+
+        let truncated =
+            |err: &io::Error| matches!(DictionaryError::of(err), Some(DictionaryError::Truncated));
+        let corrupt =
+            |err: &io::Error| matches!(DictionaryError::of(err), Some(DictionaryError::Corrupt(_)));
+
+        // Stopping part way through the data or the trailer
+        for len in [12, bytes.len() / 2, bytes.len() - 4] {
+            let err = load(&bytes[..len]);
+
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(truncated(&err));
+            assert_eq!(err.to_string(), "Compressed word list is truncated");
+        }
+
+        // A bad block type in the compressed data
+        let mut bad_block = bytes.clone();
+        bad_block[10] |= 0x06;
+
+        let err = load(&bad_block);
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(corrupt(&err));
+
+        // A checksum which doesn't match the data
+        let mut bad_crc = bytes.clone();
+        let crc = bad_crc.len() - 8;
+        bad_crc[crc] ^= 0xff;
+
+        let err = load(&bad_crc);
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(corrupt(&err));
+
+        // A second member cut short
+        let mut members = bytes.clone();
+        members.extend(&bytes[..bytes.len() / 2]);
+
+        assert!(truncated(&load(&members)));
+
+        // Some decoder versions report data cut short as a corrupt stream rather than an
+        // unexpected end of file, which is still truncated once the input has run out
+        let err = io::Error::new(io::ErrorKind::InvalidInput, "corrupt deflate stream");
+
+        assert!(truncated(&decompress_error(err, true)));
+
+        let err = decompress_error(
+            io::Error::new(io::ErrorKind::InvalidInput, "corrupt deflate stream"),
+            false,
+        );
+
+        assert!(corrupt(&err));
+        assert_eq!(
+            err.to_string(),
+            "Compressed word list is corrupt: corrupt deflate stream"
+        );
+
+        // Errors which aren't from decompressing have no reason
+        assert!(DictionaryError::of(&io::Error::other("other")).is_none());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn dict2_zstd() {
+        use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+
+        // Create dictionary from zstd compressed data with two words, "rusts" and "rusty"
+        let bytes = compress_to_vec("rusts\nrusty".as_bytes(), CompressionLevel::Fastest);
+        let dictionary = Dictionary::new_from_bytes(&bytes, WordCase::Lower, false).unwrap();
+
+        test_dict2(dictionary);
+
+        // A frame header with nothing after it
+        let err = Dictionary::new_from_bytes(&bytes[..5], WordCase::Lower, false)
+            .map(|_| ())
+            .unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            DictionaryError::of(&err),
+            Some(DictionaryError::Truncated)
+        ));
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn dict2_xz() {
+        // Create dictionary from xz compressed data with two words, "rusts" and "rusty"
+        let mut bytes = Vec::new();
+        lzma_rs::xz_compress(&mut "rusts\nrusty".as_bytes(), &mut bytes).unwrap();
+
+        let dictionary = Dictionary::new_from_bytes(&bytes, WordCase::Lower, false).unwrap();
+
+        test_dict2(dictionary);
+
+        // Stopping part way through
+        let err = Dictionary::new_from_bytes(&bytes[..bytes.len() / 2], WordCase::Lower, false)
+            .map(|_| ())
+            .unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            DictionaryError::of(&err),
+            Some(DictionaryError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn dict_compression_feature() {
+        // Formats built without their feature are reported rather than read as words
+        let formats: [(&[u8], &str, bool); 2] = [
+            (
+                &[0x28, 0xb5, 0x2f, 0xfd, 0x00],
+                "zstd",
+                cfg!(feature = "zstd"),
+            ),
+            (
+                &[0xfd, b'7', b'z', b'X', b'Z', 0x00],
+                "xz",
+                cfg!(feature = "xz"),
+            ),
+        ];
+
+        for (bytes, name, _) in formats.into_iter().filter(|(_, _, enabled)| !enabled) {
+            let err = Dictionary::new_from_bytes(bytes, WordCase::Lower, false)
+                .map(|_| ())
+                .unwrap_err();
+
+            assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+            assert!(matches!(
+                DictionaryError::of(&err),
+                Some(DictionaryError::Unsupported { format }) if *format == name
+            ));
+            assert_eq!(
+                err.to_string(),
+                format!("Word list is {name} compressed, which needs the {name} feature")
+            );
+        }
+    }
+
+    #[cfg(feature = "archive")]
+    fn tar_pack(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+
+        for (name, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, contents.as_bytes())
+                .unwrap();
+        }
+
+        builder.into_inner().unwrap()
+    }
+
+    #[cfg(feature = "archive")]
+    fn zip_pack(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn dict_set_tar() {
+        let bytes = tar_pack(&[
+            (
+                "meta.toml",
+                "# Test pack\nname = \"Rust\"\nlanguage = \"en\"\n",
+            ),
+            ("answers.txt", "rusts\nrusty"),
+            ("guesses.txt", "crate\ntrait\nrusty"),
+            ("README", "Not a word list"),
+        ]);
+
+        let set = DictionarySet::new_from_bytes(&bytes, WordCase::Lower, false).unwrap();
+
+        assert_eq!(set.metadata().name.as_deref(), Some("Rust"));
+        assert_eq!(set.metadata().language.as_deref(), Some("en"));
+        assert_eq!(set.guesses().metadata(), set.metadata());
+
+        test_dict2(set.answers().with_words(&[]).unwrap());
+
+        // Guesses include the answers
+        assert_eq!(set.guesses().word_count(), 4);
+
+        for word in ["crate", "trait", "rusts", "rusty"] {
+            assert!(set.guesses().contains(word));
+        }
+
+        assert!(!set.answers().contains("crate"));
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn dict_set_zip() {
+        // Entries in a directory, word list metadata and no guesses
+        let bytes = zip_pack(&[
+            ("pack/answers.txt", "#!meta name = Zipped\nrusts\nrusty"),
+            ("pack/", ""),
+        ]);
+
+        let set = DictionarySet::new_from_bytes(&bytes, WordCase::Lower, false).unwrap();
+
+        assert_eq!(set.metadata().name.as_deref(), Some("Zipped"));
+        assert_eq!(set.answers().word_count(), 2);
+        assert_eq!(set.guesses().word_count(), 2);
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn dict_set_targz() {
+        // The meta.toml length sets the length of both lists
+        let tar = tar_pack(&[
+            ("answers.txt", "rust\nrusty\nrusts\ntrait"),
+            ("guesses.txt", "crate\ncrates\nsix"),
+            (
+                "meta.toml",
+                "length = 5 # letters\n\n[sources]\nname = \"ignored\"\n",
+            ),
+        ]);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&tar).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let set = DictionarySet::new_from_bytes(&bytes, WordCase::Lower, false).unwrap();
+
+        assert_eq!(set.metadata().length, Some(5));
+        assert_eq!(set.metadata().name, None);
+        assert_eq!(set.answers().word_count(), 3);
+        assert_eq!(set.guesses().word_count(), 4);
+        assert!(set.guesses().contains("crate"));
+        assert!(!set.guesses().contains("crates"));
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn dict_set_errors() {
+        let load = |bytes: &[u8]| {
+            DictionarySet::new_from_bytes(bytes, WordCase::Lower, false)
+                .map(|_| ())
+                .unwrap_err()
+        };
+
+        let err = load(&tar_pack(&[("guesses.txt", "rusty")]));
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert_eq!(err.to_string(), "Language pack has no answers.txt");
+
+        let err = load(&zip_pack(&[("answers.txt", "rusty")])[..20]);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err
+            .to_string()
+            .starts_with("Language pack archive is corrupt: "));
+
+        let err = load(&[b'x'; 1024]);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
     fn test_dict2(dictionary: Dictionary) {
         assert_eq!(dictionary.word_count(), 2);
         assert_eq!(dictionary.tree_node_count(), 7);
-        assert_eq!(dictionary.tree_mem_usage(), 7 * 56);
+        assert_eq!(dictionary.tree_mem_usage(), 7 * 16);
 
         assert!(matches!(
             dictionary.lookup_elem_letter_num(0, Dictionary::uchar_to_u8('R')),
@@ -368,5 +1452,468 @@ mod tests {
             dictionary.lookup_elem_letter_num(4, Dictionary::uchar_to_u8('S')),
             5
         ));
+
+        let used = dictionary.letters_used();
+
+        assert_eq!(
+            ('A'..='Z')
+                .filter(|c| used[Dictionary::uchar_to_usize(*c)])
+                .collect::<String>(),
+            "RSTUY"
+        );
+    }
+
+    #[test]
+    fn dict_walk() {
+        // Create dictionary with several words sharing prefixes
+        let words = ["abbey", "abbot", "crane", "crank", "slate", "zebra"];
+        let dictionary =
+            Dictionary::new_from_string(&words.join("\n"), WordCase::Lower, false).unwrap();
+
+        assert_eq!(dictionary.word_count(), words.len());
+        assert_eq!(
+            dictionary.tree_mem_usage(),
+            dictionary.tree_node_count() * std::mem::size_of::<LetterEnt>()
+        );
+
+        // Walk each word through the tree and check it reads back
+        for word in words {
+            let mut elem = 0;
+
+            for c in word.chars() {
+                let next =
+                    dictionary.lookup_elem_letter_num(elem, Dictionary::lchar_to_usize(c) as u8);
+                assert_ne!(next, NEXT_NONE);
+                elem = next as usize;
+            }
+
+            assert_eq!(dictionary.get_word(elem), word.to_uppercase());
+            assert!(dictionary.is_word(elem));
+            assert!(dictionary.word_range().contains(&elem));
+        }
+
+        assert_eq!(dictionary.word_elems().count(), words.len());
+        assert_eq!(dictionary.word_range().len(), words.len());
+
+        // Check missing letters are not found
+        assert_eq!(
+            dictionary.lookup_elem_letter_num(0, Dictionary::uchar_to_u8('B')),
+            NEXT_NONE
+        );
+        assert_eq!(
+            dictionary.lookup_elem_letter_num(0, Dictionary::uchar_to_u8('Y')),
+            NEXT_NONE
+        );
+    }
+
+    #[test]
+    fn dict_sha256() {
+        let file = format!("../{BUNDLED_DICT}");
+
+        // The embedded hash matches the bundled word list
+        assert_eq!(file_sha256(&file).unwrap(), BUNDLED_SHA256);
+        assert!(verify_sha256(&file, &BUNDLED_SHA256.to_uppercase(), false).is_ok());
+
+        assert_eq!(bundled_sha256(BUNDLED_DICT), Some(BUNDLED_SHA256));
+        assert_eq!(bundled_sha256("words.txt"), None);
+
+        // A different hash is rejected
+        let err = verify_sha256(&file, &"0".repeat(64), false).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn dict_metadata() {
+        let words = "#!meta name = Test Words\n#!meta language = en\n#!meta length = 4\n\
+            #!meta colour = blue\ncrane\nslate\nbake\nlake\nrake";
+
+        // The length in the metadata is used instead of the most common length
+        let dictionary = Dictionary::new_from_string(words, WordCase::Normalise, false).unwrap();
+
+        assert_eq!(dictionary.word_len(), 4);
+        assert_eq!(dictionary.word_count(), 3);
+        assert_eq!(dictionary.metadata().name.as_deref(), Some("Test Words"));
+        assert_eq!(dictionary.metadata().to_string(), "Test Words (en)");
+
+        // The metadata is kept when words are added
+        let extra = ["cake".to_string()];
+        let dictionary = dictionary.with_words(&extra).unwrap();
+
+        assert_eq!(dictionary.word_count(), 4);
+        assert_eq!(dictionary.metadata().length, Some(4));
+
+        // Lists without metadata have a default description
+        let dictionary = Dictionary::new_from_string("crane", WordCase::Lower, false).unwrap();
+
+        assert_eq!(dictionary.metadata(), &Metadata::default());
+        assert_eq!(dictionary.metadata().to_string(), "Word list");
+    }
+
+    #[test]
+    fn dict_load_stats() {
+        let words = "#!meta name = Test Words\ncrane\nslate\nCRATE\nbake\nstar3\n\nlake";
+
+        let dictionary = Dictionary::new_from_string(words, WordCase::Lower, false).unwrap();
+
+        // Metadata lines aren't counted, blank lines are rejected with the wrong case
+        assert_eq!(
+            dictionary.load_stats(),
+            LoadStats {
+                lines: 7,
+                wrong_length: 2,
+                wrong_case: 3,
+                bad_rank: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn dict_export_dot() {
+        let options = LoadOptions {
+            case: WordCase::Lower,
+            length: WordLength::Any,
+        };
+
+        let dictionary = Dictionary::new_from_string("ab\nac\nb", options, false).unwrap();
+
+        let mut dot = Vec::new();
+        dictionary.export_dot(&mut dot, 1).unwrap();
+
+        // A has children below the depth limit and B is a word
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            "digraph dictionary {\n    node [shape=circle];\n    n0 [label=\"\"];\n    \
+            n1 [label=\"A\", style=dashed];\n    n0 -> n1;\n    \
+            n2 [label=\"B\", shape=doublecircle];\n    n0 -> n2;\n}\n"
+        );
+
+        let mut dot = Vec::new();
+        dictionary.export_dot(&mut dot, 2).unwrap();
+
+        assert_eq!(
+            String::from_utf8(dot)
+                .unwrap()
+                .matches("doublecircle")
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn dict_ranks() {
+        let words = "crane\t20\nslate\t5\nstare\ncrane\t10\nraise\tx\ntrace\t";
+
+        let dictionary = Dictionary::new_from_string(words, WordCase::Lower, false).unwrap();
+
+        assert!(dictionary.has_ranks());
+        assert_eq!(dictionary.word_count(), 3);
+        assert_eq!(dictionary.load_stats().bad_rank, 2);
+
+        let rank = |word| dictionary.rank(dictionary.find(word).unwrap() as usize);
+
+        // Repeated words keep the best rank
+        assert_eq!(rank("CRANE"), Some(10));
+        assert_eq!(rank("SLATE"), Some(5));
+        assert_eq!(rank("STARE"), None);
+
+        // Ranks are kept when words are added
+        let extra = ["trace".to_string()];
+        let dictionary = dictionary.with_words(&extra).unwrap();
+        let rank = |word| dictionary.rank(dictionary.find(word).unwrap() as usize);
+
+        assert_eq!(rank("CRANE"), Some(10));
+        assert_eq!(rank("TRACE"), None);
+
+        // Plain word lists have no ranks
+        let dictionary = Dictionary::new_from_string("crane", WordCase::Lower, false).unwrap();
+
+        assert!(!dictionary.has_ranks());
+        assert_eq!(
+            dictionary.rank(dictionary.find("CRANE").unwrap() as usize),
+            None
+        );
+    }
+
+    #[test]
+    fn history() {
+        let date = |s: &str| s.parse::<chrono::NaiveDate>().unwrap();
+
+        let history = History::new_from_string(
+            "# Past answers\n2024-02-29 crane\n\n2024-03-02,Slate\n2024-03-01\tBAKER\n\
+            2024-04-01 rusty",
+        )
+        .unwrap();
+
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.answer(date("2024-03-01")), Some("BAKER"));
+        assert_eq!(history.answer(date("2024-03-02")), Some("SLATE"));
+        assert_eq!(history.answer(date("2024-03-03")), None);
+        assert_eq!(
+            history.range(),
+            Some((date("2024-02-29"), date("2024-04-01")))
+        );
+
+        // Answers in a month are in date order
+        let march: Vec<_> = history.month(2024, 3).collect();
+
+        assert_eq!(
+            march,
+            vec![(date("2024-03-01"), "BAKER"), (date("2024-03-02"), "SLATE")]
+        );
+        assert_eq!(history.month(2024, 5).count(), 0);
+
+        // Bad lines are reported with the line number
+        for bad in ["2024-03-01", "2024-13-01 crane", "2024-03-01 cr4ne"] {
+            let err = History::new_from_string(&format!("2024-02-29 crane\n{bad}")).unwrap_err();
+
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(err.to_string().starts_with("Line 2: "));
+        }
+
+        assert!(History::new_from_string("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn dict_case() {
+        let words = "RUSTY\nRusts\nrusty\nrust's";
+
+        // Only the lower case word is accepted by default
+        let dictionary = Dictionary::new_from_string(words, WordCase::Lower, false).unwrap();
+        assert_eq!(dictionary.word_count(), 1);
+
+        // Upper and mixed case words are accepted when normalising. RUSTY and rusty are the same
+        // word once normalised
+        let dictionary = Dictionary::new_from_string(words, WordCase::Normalise, false).unwrap();
+        assert_eq!(dictionary.word_count(), 2);
+        assert_eq!(dictionary.tree_node_count(), 7);
+    }
+
+    #[test]
+    fn dict_contains() {
+        let words = ["abbey", "abbot", "crane", "crank"];
+        let dictionary =
+            Dictionary::new_from_string(&words.join("\n"), WordCase::Lower, false).unwrap();
+
+        for word in words {
+            let elem = dictionary.find(word).unwrap();
+
+            assert_eq!(dictionary.get_word(elem as usize), word.to_uppercase());
+            assert!(dictionary.contains(&word.to_uppercase()));
+
+            // Words can be read without allocating
+            let mut buf = [0; MAX_WORD_LEN];
+            let len = dictionary.get_word_into(elem as usize, &mut buf);
+
+            assert_eq!(&buf[..len], word.to_uppercase().as_bytes());
+            assert_eq!(
+                &dictionary.get_word_bytes::<5>(elem as usize),
+                word.to_uppercase().as_bytes()
+            );
+        }
+
+        // Prefixes, extensions and non-letters are not words
+        assert!(!dictionary.contains("cran"));
+        assert!(!dictionary.contains("cranes"));
+        assert!(!dictionary.contains("crant"));
+        assert!(!dictionary.contains("cr4ne"));
+        assert!(!dictionary.contains(""));
+    }
+
+    #[test]
+    fn dict_word_contains() {
+        let dictionary =
+            Dictionary::new_from_string("eerie\nslate", WordCase::Lower, false).unwrap();
+
+        let eerie = dictionary.find("eerie").unwrap() as usize;
+        let e = Dictionary::uchar_to_u8('E');
+        let z = Dictionary::uchar_to_u8('Z');
+
+        assert!(dictionary.word_contains(eerie, e, 3, true));
+        assert!(dictionary.word_contains(eerie, e, 2, false));
+        assert!(!dictionary.word_contains(eerie, e, 2, true));
+        assert!(!dictionary.word_contains(eerie, e, 4, false));
+
+        // Letters missing from the word are counted as zero
+        assert!(dictionary.word_contains(eerie, z, 0, true));
+        assert!(!dictionary.word_contains(eerie, z, 1, false));
+    }
+
+    #[test]
+    fn dict_with_words() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nslate", WordCase::Lower, false).unwrap();
+
+        let extra = ["Trace", "slate", "cranes", "zebra"].map(String::from);
+        let dictionary = dictionary.with_words(&extra).unwrap();
+
+        // Duplicates and words of the wrong length are ignored
+        assert_eq!(dictionary.word_count(), 4);
+        assert_eq!(dictionary.word_len(), 5);
+
+        let words = dictionary
+            .word_elems()
+            .map(|elem| dictionary.get_word(elem))
+            .collect::<Vec<_>>();
+
+        assert_eq!(words, ["CRANE", "SLATE", "TRACE", "ZEBRA"]);
+    }
+
+    #[test]
+    fn dict_pattern() {
+        let words = ["crane", "drone", "prone", "prank", "irony"];
+        let dictionary =
+            Dictionary::new_from_string(&words.join("\n"), WordCase::Lower, false).unwrap();
+
+        let find = |pattern: &str, available: Option<&str>| {
+            let available = available.map(|letters| {
+                let mut available = [false; 26];
+
+                for c in letters.chars() {
+                    available[Dictionary::uchar_to_usize(c)] = true;
+                }
+
+                available
+            });
+
+            dictionary
+                .find_pattern(pattern, available.as_ref())
+                .iter()
+                .map(|elem| dictionary.get_word(*elem as usize))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(find("?R?NE", None), ["CRANE", "DRONE", "PRONE"]);
+        assert_eq!(
+            find("_r_n_", None),
+            ["CRANE", "DRONE", "IRONY", "PRANK", "PRONE"]
+        );
+        assert_eq!(find("?R?NE", Some("AOP")), ["PRONE"]);
+        assert_eq!(find("?RON.", Some("DE")), ["DRONE"]);
+
+        // Wrong length or invalid characters match nothing
+        assert!(find("?R?N", None).is_empty());
+        assert!(find("?R-NE", None).is_empty());
+    }
+
+    #[test]
+    fn dict_any_length() {
+        let options = LoadOptions {
+            case: WordCase::Lower,
+            length: WordLength::Any,
+        };
+
+        let dictionary =
+            Dictionary::new_from_string("bee\nbeer\nbees\nbe\nrebel\nzebra", options, false)
+                .unwrap();
+
+        assert_eq!(dictionary.word_count(), 6);
+
+        // Every word is listed, including words which start longer words, shortest first
+        let words = dictionary
+            .word_elems()
+            .map(|elem| dictionary.get_word(elem))
+            .collect::<Vec<_>>();
+
+        assert_eq!(words, ["BE", "BEE", "BEER", "BEES", "REBEL", "ZEBRA"]);
+
+        // The word range starts at the first word but holds elements which aren't words
+        assert_eq!(dictionary.get_word(dictionary.word_range().start), "BE");
+        assert!(dictionary.word_range().len() > words.len());
+
+        // Words which start longer words are found
+        assert!(dictionary.contains("be"));
+        assert!(dictionary.contains("bee"));
+        assert!(dictionary.contains("beer"));
+        assert!(!dictionary.contains("bees's"));
+        assert!(!dictionary.contains("reb"));
+
+        // Words made from the letters B, E and R with at least three letters
+        let mut available = [false; 26];
+
+        for c in "BER".chars() {
+            available[Dictionary::uchar_to_usize(c)] = true;
+        }
+
+        let words = dictionary
+            .find_letters(&available, 3)
+            .iter()
+            .map(|elem| dictionary.get_word(*elem as usize))
+            .collect::<Vec<_>>();
+
+        assert_eq!(words, ["BEE", "BEER"]);
+
+        // Patterns match words of their length
+        assert_eq!(dictionary.find_pattern("?ee?", None).len(), 2);
+
+        // Exact lengths can be requested
+        let options = LoadOptions {
+            case: WordCase::Lower,
+            length: WordLength::Exactly(4),
+        };
+
+        let dictionary =
+            Dictionary::new_from_string("bee\nbeer\nbees\nbe\nrebel\nzebra", options, false)
+                .unwrap();
+
+        assert_eq!(dictionary.word_count(), 2);
+        assert_eq!(dictionary.word_len(), 4);
+        assert!(!dictionary.contains("bee"));
+    }
+
+    #[test]
+    fn dict_length() {
+        // Six letter words are in the majority
+        let words = "rusty\nrustle\nrusted\nrustic\nrusts";
+        let dictionary = Dictionary::new_from_string(words, WordCase::Lower, false).unwrap();
+
+        assert_eq!(dictionary.word_len(), 6);
+        assert_eq!(dictionary.word_count(), 3);
+        assert_eq!(dictionary.word_elems().count(), 3);
+
+        // Ties go to the shorter length
+        let words = "rusty\nrustle\nrusts\nrusted";
+        let dictionary = Dictionary::new_from_string(words, WordCase::Lower, false).unwrap();
+
+        assert_eq!(dictionary.word_len(), 5);
+        assert_eq!(dictionary.word_count(), 2);
+
+        // Empty word lists use the default length
+        let dictionary = Dictionary::new_from_string("", WordCase::Lower, false).unwrap();
+
+        assert_eq!(dictionary.word_len(), DEFAULT_WORD_LEN);
+        assert_eq!(dictionary.word_count(), 0);
+    }
+
+    #[test]
+    fn dict_order() {
+        // Create dictionary with words out of order
+        let words = ["zebra", "abbot", "crank", "abbey", "slate", "crane"];
+        let dictionary =
+            Dictionary::new_from_string(&words.join("\n"), WordCase::Lower, false).unwrap();
+
+        // Find the element for each word
+        let mut elems = words
+            .iter()
+            .map(|word| {
+                word.chars().fold(0, |elem, c| {
+                    dictionary.lookup_elem_letter_num(elem, Dictionary::lchar_to_usize(c) as u8)
+                        as usize
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Sorting the elements sorts the words
+        elems.sort();
+
+        let sorted = elems
+            .iter()
+            .map(|elem| dictionary.get_word(*elem))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sorted,
+            ["ABBEY", "ABBOT", "CRANE", "CRANK", "SLATE", "ZEBRA"]
+        );
     }
 }
@@ -0,0 +1,86 @@
+#![warn(missing_docs)]
+
+//! Number formatting functions
+
+use std::time::Duration;
+
+/// Formats numbers for display
+pub trait NumFormat {
+    /// Formats the number with thousands separators
+    fn num_format(&self) -> String;
+}
+
+macro_rules! impl_num_format {
+    ($($t:ty),*) => {
+        $(
+            impl NumFormat for $t {
+                fn num_format(&self) -> String {
+                    separate(&self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_num_format!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Formats durations for display
+pub trait DurationFormat {
+    /// Formats the duration in milliseconds or seconds
+    fn dur_format(&self) -> String;
+}
+
+impl DurationFormat for Duration {
+    fn dur_format(&self) -> String {
+        if *self < Duration::from_secs(1) {
+            format!("{:.1} ms", self.as_secs_f64() * 1000.0)
+        } else {
+            format!("{:.2} s", self.as_secs_f64())
+        }
+    }
+}
+
+/// Inserts thousands separators in to a formatted integer
+fn separate(number: &str) -> String {
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", number),
+    };
+
+    let mut result = String::with_capacity(number.len() + (digits.len() / 3));
+
+    result.push_str(sign);
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            result.push(',');
+        }
+
+        result.push(c);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn num() {
+        assert_eq!(0u32.num_format(), "0");
+        assert_eq!(999usize.num_format(), "999");
+        assert_eq!(1000usize.num_format(), "1,000");
+        assert_eq!(12431usize.num_format(), "12,431");
+        assert_eq!(1234567u64.num_format(), "1,234,567");
+        assert_eq!((-1234i32).num_format(), "-1,234");
+        assert_eq!(i8::MIN.num_format(), "-128");
+    }
+
+    #[test]
+    fn dur() {
+        assert_eq!(Duration::from_micros(800).dur_format(), "0.8 ms");
+        assert_eq!(Duration::from_micros(12345).dur_format(), "12.3 ms");
+        assert_eq!(Duration::from_millis(1500).dur_format(), "1.50 s");
+    }
+}
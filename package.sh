@@ -0,0 +1,53 @@
+#!/bin/sh
+# Packages the graphical solver as a desktop application
+#   Linux: installs solvegui with a .desktop entry and icon under PREFIX (default ~/.local)
+#   macOS: builds "Wordle Solver.app" in target/release
+#   Windows: the icon and manifest are built into solvegui.exe, ship it with words.txt.gz
+
+set -e
+
+cd "$(dirname "$0")"
+
+cargo build --bin solvegui --release
+
+assets=solvegui/assets
+version=$(sed -n 's/^version = "\(.*\)"/\1/p' Cargo.toml | head -1)
+
+case "$(uname -s)" in
+Linux)
+    prefix=${PREFIX:-$HOME/.local}
+
+    install -Dm755 target/release/solvegui "$prefix/bin/solvegui"
+    install -Dm644 words.txt.gz "$prefix/share/solvegui/words.txt.gz"
+    install -Dm644 $assets/solvegui.desktop "$prefix/share/applications/solvegui.desktop"
+    install -Dm644 $assets/wordle_logo_192x192.png \
+        "$prefix/share/icons/hicolor/192x192/apps/solvegui.png"
+
+    echo "Installed solvegui $version in $prefix"
+    ;;
+Darwin)
+    app="target/release/Wordle Solver.app"
+
+    rm -rf "$app"
+    mkdir -p "$app/Contents/MacOS" "$app/Contents/Resources"
+
+    cp target/release/solvegui "$app/Contents/MacOS/"
+    cp words.txt.gz "$app/Contents/Resources/"
+    sed "s/@VERSION@/$version/g" $assets/Info.plist > "$app/Contents/Info.plist"
+
+    # Build the icon set from the logo
+    iconset=$(mktemp -d)/solvegui.iconset
+    mkdir -p "$iconset"
+
+    for size in 16 32 64 128; do
+        sips -z $size $size $assets/wordle_logo_192x192.png --out "$iconset/icon_${size}x${size}.png" >/dev/null
+    done
+
+    iconutil -c icns "$iconset" -o "$app/Contents/Resources/solvegui.icns"
+
+    echo "Built $app version $version"
+    ;;
+*)
+    echo "Nothing to package on $(uname -s), ship target/release/solvegui with words.txt.gz"
+    ;;
+esac
@@ -0,0 +1,82 @@
+//! Adversarial (Absurdle) game
+
+use std::io::{self, BufRead, Write};
+
+use dictionary::{Dictionary, LetterNext};
+use solver::{adversary, best_guess, feedback, solved_pattern, word_letters, WordLetters};
+
+use crate::print_row;
+
+/// Plays a game where the answer is chosen lazily to keep as many words possible as it can
+/// Guesses are read from standard input, or chosen by the default strategy if auto is set or a
+/// blank line is entered
+pub fn absurdle(dictionary: &Dictionary, auto: bool) -> io::Result<()> {
+    // Get all of the words
+    let elems: Vec<LetterNext> = dictionary
+        .word_elems()
+        .map(|elem| elem as LetterNext)
+        .collect();
+
+    let answers: Vec<WordLetters> = elems
+        .iter()
+        .map(|elem| word_letters(dictionary, *elem))
+        .collect();
+
+    let mut set: Vec<usize> = (0..answers.len()).collect();
+    let mut best = None;
+    let mut turn = 0;
+
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        // Get the next guess
+        let suggestion = best
+            .get_or_insert_with(|| dictionary.get_word(elems[best_guess(&answers, &set)] as usize))
+            .clone();
+
+        let guess = if auto {
+            suggestion
+        } else {
+            print!(
+                "{} words possible, guess (blank for {suggestion}): ",
+                set.len()
+            );
+            io::stdout().flush()?;
+
+            let Some(line) = lines.next() else {
+                println!();
+                return Ok(());
+            };
+
+            let line = line?.trim().to_ascii_uppercase();
+
+            if line.is_empty() {
+                suggestion
+            } else if !dictionary.contains(&line) {
+                println!("{line} is not in the word list");
+                continue;
+            } else {
+                line
+            }
+        };
+
+        turn += 1;
+
+        // Let the adversary choose the colours
+        let letters: WordLetters = guess.chars().map(Dictionary::uchar_to_u8).collect();
+        let (pattern, remaining) = adversary(&answers, &set, &letters);
+
+        set = remaining;
+        best = None;
+
+        print_row(&feedback(
+            &guess,
+            &dictionary.get_word(elems[set[0]] as usize),
+        ));
+
+        if pattern == solved_pattern(&letters) {
+            println!("Solved in {turn} guesses");
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Batch solving of boards read from a file
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use dictionary::Dictionary;
+use serde_json::json;
+use solver::{find_words, rank_words, BoardElem, BoardRow, BoardState, GameRules, SolverArgs};
+
+/// Solves each board in a file, printing the words remaining and the suggested next guess for
+/// each line as tab separated values or JSON lines
+/// Each line of the file is a board of GUESS:PATTERN pairs separated by spaces or '/', where the
+/// pattern has 'b' or '.' for a gray letter, 'y' for yellow and 'g' for green
+/// (eg. CRANE:..y.g STAIR:g...y). Boards are printed in the BoardState form (CRANE:bbybg/...).
+/// Blank lines and lines starting with '#' are skipped
+/// Lines which can't be read are reported to stderr and the batch fails after the other lines
+pub fn batch(
+    dictionary: &Dictionary,
+    rules: &dyn GameRules,
+    file: &str,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    if rules.boards() != 1 {
+        return Err(format!("The {} rules can't be used in a batch", rules.name()).into());
+    }
+
+    let mut errors = 0;
+
+    if !json {
+        println!("line\tboard\tcount\tsuggestion\tcandidates");
+    }
+
+    for (line_no, line) in BufReader::new(File::open(file)?).lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let state = match parse_board(dictionary, rules, line) {
+            Ok(board) => board,
+            Err(e) => {
+                eprintln!("Line {line_no}: {e}");
+                errors += 1;
+                continue;
+            }
+        };
+
+        // Find the remaining words and the one giving the most information
+        let words = find_words(SolverArgs {
+            board: &state.0,
+            dictionary,
+            rules,
+            debug: false,
+            cancel: None,
+        })
+        .words;
+
+        let scores = rank_words(dictionary, &words);
+
+        let suggestion = scores
+            .iter()
+            .enumerate()
+            .fold(None, |best: Option<(usize, f32)>, (i, score)| match best {
+                Some((_, best_score)) if best_score >= *score => best,
+                _ => Some((i, *score)),
+            })
+            .map(|(i, _)| dictionary.get_word(words[i] as usize));
+
+        let candidates: Vec<String> = words
+            .iter()
+            .map(|elem| dictionary.get_word(*elem as usize))
+            .collect();
+
+        if json {
+            println!(
+                "{}",
+                json!({
+                    "line": line_no,
+                    "board": state,
+                    "count": candidates.len(),
+                    "suggestion": suggestion,
+                    "candidates": candidates,
+                })
+            );
+        } else {
+            println!(
+                "{line_no}\t{state}\t{}\t{}\t{}",
+                candidates.len(),
+                suggestion.unwrap_or_default(),
+                candidates.join(",")
+            );
+        }
+    }
+
+    if errors > 0 {
+        return Err(format!("{errors} lines couldn't be read").into());
+    }
+
+    Ok(())
+}
+
+/// Parses a board of GUESS:PATTERN pairs, checking the guesses against the rules
+fn parse_board(
+    dictionary: &Dictionary,
+    rules: &dyn GameRules,
+    line: &str,
+) -> Result<BoardState, String> {
+    let BoardState(rows) = line.parse()?;
+
+    let mut board: Vec<BoardRow> = Vec::new();
+
+    for row in rows {
+        let guess: String = row
+            .iter()
+            .map(|elem| match elem {
+                BoardElem::Empty => '_',
+                BoardElem::Unknown(c)
+                | BoardElem::Gray(c)
+                | BoardElem::Yellow(c)
+                | BoardElem::Green(c) => *c,
+            })
+            .collect();
+
+        if row.len() != dictionary.word_len() {
+            return Err(format!(
+                "{guess} isn't a word of {} letters",
+                dictionary.word_len()
+            ));
+        }
+
+        if row
+            .iter()
+            .any(|elem| matches!(elem, BoardElem::Empty | BoardElem::Unknown(_)))
+        {
+            return Err(format!("{guess} doesn't have a colour for every letter"));
+        }
+
+        if !rules.valid_guess(&board, &guess) {
+            return Err(format!(
+                "{guess} doesn't use all of the hints given by the earlier guesses"
+            ));
+        }
+
+        board.push(row);
+    }
+
+    if board.is_empty() {
+        return Err("No guesses".to_string());
+    }
+
+    if rules.rows().is_some_and(|rows| board.len() > rows) {
+        return Err(format!("Too many guesses for the {} rules", rules.name()));
+    }
+
+    Ok(BoardState(board))
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::LoadOptions;
+    use solver::{HardMode, Standard};
+
+    use super::*;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::new_from_string("crane\nslate\nsloth\nstair", LoadOptions::default(), false)
+            .unwrap()
+    }
+
+    #[test]
+    fn boards() {
+        let dictionary = dictionary();
+
+        let board = parse_board(&dictionary, &Standard, "crane:..y.g STAIR:g...y").unwrap();
+        assert_eq!(board.to_string(), "CRANE:bbybg/STAIR:gbbby");
+
+        let board = parse_board(&dictionary, &HardMode, "CRANE:bbgbb/SLATE:bbgbb").unwrap();
+        assert_eq!(board.0.len(), 2);
+    }
+
+    #[test]
+    fn bad_boards() {
+        let dictionary = dictionary();
+
+        let error =
+            |rules: &dyn GameRules, line: &str| parse_board(&dictionary, rules, line).unwrap_err();
+
+        assert_eq!(
+            error(&Standard, "CRANES:bbbbbb"),
+            "CRANES isn't a word of 5 letters"
+        );
+        assert_eq!(
+            error(&Standard, "CRANE:bb?bb"),
+            "CRANE doesn't have a colour for every letter"
+        );
+        assert_eq!(error(&Standard, "/"), "No guesses");
+        assert_eq!(
+            error(&Standard, &["CRANE:bbbbb"; 7].join("/")),
+            "Too many guesses for the standard rules"
+        );
+
+        // Hard mode needs the green A kept in place
+        assert!(parse_board(&dictionary, &Standard, "CRANE:bbgbb/SLOTH:bbbbb").is_ok());
+        assert_eq!(
+            error(&HardMode, "CRANE:bbgbb/SLOTH:bbbbb"),
+            "SLOTH doesn't use all of the hints given by the earlier guesses"
+        );
+
+        // Errors from the board form are passed on
+        assert_eq!(
+            error(&Standard, "CRANE"),
+            "CRANE isn't a GUESS:PATTERN pair"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Spelling Bee helper
+
+use dictionary::Dictionary;
+
+/// Minimum word length
+const MIN_LEN: usize = 4;
+
+/// Prints the words made from the puzzle letters which contain the centre letter
+/// Pangrams, which use every letter, are highlighted
+pub fn bee(dictionary: &Dictionary, letters: &str, center: char) {
+    let words = bee_words(dictionary, letters, center);
+
+    let pangrams = words.iter().filter(|(_, pangram)| *pangram).count();
+
+    println!("{} words found, {} pangrams", words.len(), pangrams);
+
+    for chunk in words.chunks(10) {
+        let line = chunk
+            .iter()
+            .map(|(word, pangram)| {
+                if *pangram {
+                    format!("\x1b[1;92m{word}\x1b[0m")
+                } else {
+                    word.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        println!("{line}");
+    }
+}
+
+/// Finds the words made from the puzzle letters which contain the centre letter, in alphabetical
+/// order, with whether each is a pangram
+fn bee_words(dictionary: &Dictionary, letters: &str, center: char) -> Vec<(String, bool)> {
+    let mut available = [false; 26];
+
+    for c in letters.chars().chain([center]) {
+        available[Dictionary::uchar_to_usize(c)] = true;
+    }
+
+    let letter_count = available.iter().filter(|a| **a).count();
+
+    dictionary
+        .find_letters(&available, MIN_LEN)
+        .iter()
+        .map(|elem| dictionary.get_word(*elem as usize))
+        .filter(|word| word.contains(center))
+        .map(|word| {
+            let pangram = is_pangram(&word, letter_count);
+            (word, pangram)
+        })
+        .collect()
+}
+
+/// Tests if a word uses all of the puzzle letters
+fn is_pangram(word: &str, letter_count: usize) -> bool {
+    let mut used = [false; 26];
+
+    for c in word.chars() {
+        used[Dictionary::uchar_to_usize(c)] = true;
+    }
+
+    used.iter().filter(|u| **u).count() == letter_count
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::{LoadOptions, WordCase, WordLength};
+
+    use super::*;
+
+    #[test]
+    fn words() {
+        let options = LoadOptions {
+            case: WordCase::Lower,
+            length: WordLength::Any,
+        };
+
+        let dictionary = Dictionary::new_from_string(
+            "abc\nbach\nbleach\ncable\nchef\nheel\nlace",
+            options,
+            false,
+        )
+        .unwrap();
+
+        // Words of four letters or more from the puzzle letters with the centre letter A.
+        // BLEACH uses every letter
+        assert_eq!(
+            bee_words(&dictionary, "BCEHL", 'A'),
+            [
+                ("BACH".to_string(), false),
+                ("BLEACH".to_string(), true),
+                ("CABLE".to_string(), false),
+                ("LACE".to_string(), false),
+            ]
+        );
+
+        // HEEL only uses puzzle letters but needs the centre letter to count
+        assert_eq!(
+            bee_words(&dictionary, "ABCHL", 'E')
+                .into_iter()
+                .map(|(word, _)| word)
+                .collect::<Vec<_>>(),
+            ["BLEACH", "CABLE", "HEEL", "LACE"]
+        );
+    }
+
+    #[test]
+    fn pangrams() {
+        assert!(is_pangram("BLEACH", 6));
+        assert!(is_pangram("BLEACHABLE", 6));
+        assert!(!is_pangram("CABLE", 6));
+    }
+}
@@ -0,0 +1,178 @@
+//! Opening guess comparison
+
+use std::error::Error;
+
+use dictionary::{Dictionary, LetterNext};
+use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use solver::{
+    play_answer,
+    play_partitions,
+    play_set,
+    word_letters,
+    Distribution,
+    Random,
+    WordLetters,
+    BOARD_ROWS,
+};
+
+/// Plays every dictionary word as the answer with each opener followed by the default strategy
+/// and prints a table comparing the results
+/// If a sample size is given only that many randomly chosen answers are played
+pub fn compare(
+    dictionary: &Dictionary,
+    openers: &[String],
+    threads: Option<usize>,
+    sample: Option<usize>,
+    seed: Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    // Get all of the words
+    let answers: Vec<WordLetters> = dictionary
+        .word_elems()
+        .map(|elem| word_letters(dictionary, elem as LetterNext))
+        .collect();
+
+    let all: Vec<usize> = (0..answers.len()).collect();
+
+    // Create the thread pool
+    let mut pool = ThreadPoolBuilder::new();
+
+    if let Some(threads) = threads {
+        pool = pool.num_threads(threads);
+    }
+
+    let pool = pool.build()?;
+
+    let guesses: Vec<WordLetters> = openers
+        .iter()
+        .map(|opener| opener.chars().map(Dictionary::uchar_to_u8).collect())
+        .collect();
+
+    let results = match sample {
+        Some(count) => {
+            let mut random = Random::new(seed);
+            let sample = random.sample(answers.len(), count);
+
+            eprintln!(
+                "Playing {} random answers (seed {})",
+                sample.len(),
+                random.seed()
+            );
+
+            play_sample(&pool, &answers, &all, &guesses, &sample)
+        }
+        None => play_all(&pool, &answers, &all, &guesses),
+    };
+
+    // Print the comparison table
+    print!("{:<10} {:>6} {:>7}", "Opener", "Mean", "Fail %");
+
+    for guesses in 1..=BOARD_ROWS {
+        print!(" {guesses:>6}");
+    }
+
+    println!();
+
+    for (opener, dist) in openers.iter().zip(results.iter()) {
+        print!(
+            "{:<10} {:>6.3} {:>7.2}",
+            opener,
+            dist.mean(),
+            dist.fail_rate() * 100.0
+        );
+
+        for solved in dist.solved {
+            print!(" {solved:>6}");
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Plays every answer with each opener
+fn play_all(
+    pool: &ThreadPool,
+    answers: &[WordLetters],
+    all: &[usize],
+    guesses: &[WordLetters],
+) -> Vec<Distribution> {
+    // Play the first guess for each opener, splitting the answers by the pattern they give
+    let played: Vec<_> = guesses
+        .iter()
+        .map(|guess| play_partitions(answers, all, guess, 1))
+        .collect();
+
+    // Create the progress bar
+    let total = played
+        .iter()
+        .map(|(_, partitions)| partitions.len())
+        .sum::<usize>();
+    let progress = progress_bar(total);
+
+    // Play the rest of each game in parallel
+    let results = pool.install(|| {
+        played
+            .iter()
+            .map(|(first, partitions)| {
+                let mut dist = partitions
+                    .par_iter()
+                    .progress_with(progress.clone())
+                    .map(|partition| play_set(answers, partition, 2))
+                    .reduce(Distribution::default, |mut a, b| {
+                        a.merge(&b);
+                        a
+                    });
+
+                dist.merge(first);
+                dist
+            })
+            .collect()
+    });
+
+    progress.finish_and_clear();
+
+    results
+}
+
+/// Plays a sample of the answers with each opener
+fn play_sample(
+    pool: &ThreadPool,
+    answers: &[WordLetters],
+    all: &[usize],
+    guesses: &[WordLetters],
+    sample: &[usize],
+) -> Vec<Distribution> {
+    let progress = progress_bar(guesses.len() * sample.len());
+
+    // Play each game in parallel
+    let results = pool.install(|| {
+        guesses
+            .iter()
+            .map(|guess| {
+                sample
+                    .par_iter()
+                    .progress_with(progress.clone())
+                    .map(|answer| play_answer(answers, all, guess, *answer))
+                    .reduce(Distribution::default, |mut a, b| {
+                        a.merge(&b);
+                        a
+                    })
+            })
+            .collect()
+    });
+
+    progress.finish_and_clear();
+
+    results
+}
+
+/// Creates a progress bar
+fn progress_bar(total: usize) -> ProgressBar {
+    ProgressBar::new(total as u64).with_style(
+        ProgressStyle::with_template("{wide_bar} {pos}/{len} {per_sec} eta {eta}")
+            .expect("Invalid progress bar template"),
+    )
+}
@@ -0,0 +1,92 @@
+//! Word list comparison
+
+use std::collections::BTreeSet;
+use std::error::Error;
+
+use dictionary::{Dictionary, LoadOptions};
+
+/// Loads two word lists and prints the words added and removed by the second, the lines each
+/// rejected and how many words they share
+pub fn dictdiff(
+    old_file: &str,
+    new_file: &str,
+    options: LoadOptions,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let old = Dictionary::new_from_file(old_file, options, verbose)?;
+    let new = Dictionary::new_from_file(new_file, options, verbose)?;
+
+    print_list("Old", old_file, &old);
+    print_list("New", new_file, &new);
+
+    let old_words = words(&old);
+    let new_words = words(&new);
+
+    println!();
+    print_diff("added", new_words.difference(&old_words));
+
+    println!();
+    print_diff("removed", old_words.difference(&new_words));
+
+    let both = old_words.intersection(&new_words).count();
+
+    println!();
+    println!(
+        "{both} words in both lists ({:.1}% of old, {:.1}% of new)",
+        percent(both, old_words.len()),
+        percent(both, new_words.len())
+    );
+
+    Ok(())
+}
+
+/// Prints a word list's description and load statistics
+fn print_list(label: &str, file: &str, dictionary: &Dictionary) {
+    let stats = dictionary.load_stats();
+
+    println!("{label}: {file} - {}", dictionary.metadata());
+    println!(
+        "  {} words of {} letters from {} lines, rejected {} wrong length, {} wrong case and {} \
+        bad rank",
+        dictionary.word_count(),
+        dictionary.word_len(),
+        stats.lines,
+        stats.wrong_length,
+        stats.wrong_case,
+        stats.bad_rank
+    );
+}
+
+/// Prints the words added to or removed from the list
+fn print_diff<'a>(change: &str, words: impl Iterator<Item = &'a String>) {
+    let words: Vec<&String> = words.collect();
+
+    println!("{} words {change}", words.len());
+
+    for chunk in words.chunks(10) {
+        let line = chunk
+            .iter()
+            .map(|word| word.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        println!("{line}");
+    }
+}
+
+/// Returns the words in a word list
+fn words(dictionary: &Dictionary) -> BTreeSet<String> {
+    dictionary
+        .word_elems()
+        .map(|elem| dictionary.get_word(elem))
+        .collect()
+}
+
+/// Returns a count as a percentage of a total
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
@@ -0,0 +1,51 @@
+//! Answer difficulty
+
+use std::error::Error;
+
+use dictionary::{Dictionary, LetterNext};
+use solver::{best_guess, difficulty, word_letters, WordLetters};
+
+/// Prints the difficulty of each word as the answer, playing the default strategy after each of
+/// the openers, or after the best opener if none are given
+pub fn difficulties(
+    dictionary: &Dictionary,
+    words: &[String],
+    openers: &[String],
+) -> Result<(), Box<dyn Error>> {
+    // Get all of the words
+    let elems: Vec<LetterNext> = dictionary
+        .word_elems()
+        .map(|elem| elem as LetterNext)
+        .collect();
+
+    let answers: Vec<WordLetters> = elems
+        .iter()
+        .map(|elem| word_letters(dictionary, *elem))
+        .collect();
+
+    let openers: Vec<WordLetters> = if openers.is_empty() {
+        let all: Vec<usize> = (0..answers.len()).collect();
+        let best = best_guess(&answers, &all);
+
+        println!("Opener {}", dictionary.get_word(elems[best] as usize));
+
+        vec![answers[best].clone()]
+    } else {
+        openers
+            .iter()
+            .map(|opener| opener.chars().map(Dictionary::uchar_to_u8).collect())
+            .collect()
+    };
+
+    for word in words {
+        let letters: WordLetters = word.chars().map(Dictionary::uchar_to_u8).collect();
+
+        let Some(answer) = answers.iter().position(|answer| *answer == letters) else {
+            return Err(format!("{word} is not in the word list").into());
+        };
+
+        println!("{word} {}", difficulty(&answers, answer, &openers));
+    }
+
+    Ok(())
+}
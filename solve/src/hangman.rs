@@ -0,0 +1,111 @@
+//! Hangman helper
+
+use dictionary::{Dictionary, LetterNext};
+use solver::letter_counts;
+
+use crate::print_words;
+
+/// Prints the words matching a hangman game and the best letter to guess next
+/// Letters shown in the pattern are revealed everywhere they appear so can't fill the blanks
+pub fn hangman(dictionary: &Dictionary, pattern: &str, wrong: &str, group: bool) {
+    let guessed = guessed_letters(pattern, wrong);
+
+    // Find the words with the blanks filled by letters not yet guessed
+    let available = guessed.map(|guessed| !guessed);
+    let words = dictionary.find_pattern(pattern, Some(&available));
+
+    print_words(dictionary, &words, group);
+
+    if let Some((letter, count)) = best_letter(dictionary, &words, &guessed) {
+        println!();
+        println!(
+            "Best letter to guess: {letter} (in {count} of {} words)",
+            words.len()
+        );
+    }
+}
+
+/// Returns the letters already guessed, either shown in the pattern or guessed wrongly
+fn guessed_letters(pattern: &str, wrong: &str) -> [bool; 26] {
+    let mut guessed = [false; 26];
+
+    for c in pattern.chars().chain(wrong.chars()) {
+        if c.is_ascii_alphabetic() {
+            guessed[Dictionary::uchar_to_usize(c.to_ascii_uppercase())] = true;
+        }
+    }
+
+    guessed
+}
+
+/// Returns the letter not yet guessed which is in the most words and the number of words it is
+/// in. Ties go to the earlier letter
+fn best_letter(
+    dictionary: &Dictionary,
+    words: &[LetterNext],
+    guessed: &[bool; 26],
+) -> Option<(char, usize)> {
+    letter_counts(dictionary, words)
+        .iter()
+        .enumerate()
+        .filter(|(letter, count)| !guessed[*letter] && **count > 0)
+        .fold(
+            None,
+            |best: Option<(usize, usize)>, (letter, count)| match best {
+                Some((_, best_count)) if best_count >= *count => best,
+                _ => Some((letter, *count)),
+            },
+        )
+        .map(|(letter, count)| ((letter as u8 + b'A') as char, count))
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::LoadOptions;
+
+    use super::*;
+
+    #[test]
+    fn guessed() {
+        let guessed = guessed_letters("_ra_e", "S");
+
+        let letters: String = (b'A'..=b'Z')
+            .filter(|c| guessed[(c - b'A') as usize])
+            .map(|c| c as char)
+            .collect();
+
+        assert_eq!(letters, "AERS");
+    }
+
+    #[test]
+    fn best() {
+        let dictionary = Dictionary::new_from_string(
+            "crane\ncrate\ngrape\ngrate\nirate\ntrace\nerase",
+            LoadOptions::default(),
+            false,
+        )
+        .unwrap();
+
+        let guessed = guessed_letters("_ra_e", "S");
+        let words = dictionary.find_pattern("_ra_e", Some(&guessed.map(|guessed| !guessed)));
+
+        // ERASE is left out as the E is already shown and the S was wrong
+        assert_eq!(words.len(), 6);
+
+        // T is in CRATE, GRATE, IRATE and TRACE
+        assert_eq!(best_letter(&dictionary, &words, &guessed), Some(('T', 4)));
+
+        // C, G and I are in one word each once T is shown, so the earliest letter wins
+        let guessed = guessed_letters("_rate", "S");
+        let words = dictionary.find_pattern("_rate", Some(&guessed.map(|guessed| !guessed)));
+
+        assert_eq!(best_letter(&dictionary, &words, &guessed), Some(('C', 1)));
+
+        // Nothing left to guess
+        let guessed = guessed_letters("crane", "");
+        let words = dictionary.find_pattern("crane", Some(&guessed.map(|guessed| !guessed)));
+
+        assert_eq!(words.len(), 1);
+        assert_eq!(best_letter(&dictionary, &words, &guessed), None);
+    }
+}
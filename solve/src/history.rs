@@ -0,0 +1,128 @@
+//! Past answers
+
+use std::error::Error;
+
+use chrono::NaiveDate;
+use dictionary::{Dictionary, History, LetterNext};
+use solver::{
+    best_guess,
+    feedback,
+    pattern,
+    solved_pattern,
+    word_letters,
+    WordLetters,
+    BOARD_ROWS,
+};
+
+use crate::print_row;
+
+/// Prints the answer for a date from a file of past answers and the board the default strategy
+/// plays to solve it, starting with the opener if one is given
+pub fn history(
+    dictionary: &Dictionary,
+    file: &str,
+    date: NaiveDate,
+    opener: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let history = History::new_from_file(file)?;
+    let answer = answer_for(&history, file, date)?;
+
+    println!("Answer for {date}: {answer}");
+
+    if answer.len() != dictionary.word_len() || !dictionary.contains(answer) {
+        println!("{answer} is not in the word list");
+        return Ok(());
+    }
+
+    println!();
+
+    // Get all of the words
+    let elems: Vec<LetterNext> = dictionary
+        .word_elems()
+        .map(|elem| elem as LetterNext)
+        .collect();
+
+    let answers: Vec<WordLetters> = elems
+        .iter()
+        .map(|elem| word_letters(dictionary, *elem))
+        .collect();
+
+    let target: WordLetters = answer.chars().map(Dictionary::uchar_to_u8).collect();
+
+    // Play the default strategy against the answer
+    let mut set: Vec<usize> = (0..answers.len()).collect();
+
+    for turn in 1..=BOARD_ROWS {
+        let guess = match opener {
+            Some(opener) if turn == 1 => opener.to_string(),
+            _ => dictionary.get_word(elems[best_guess(&answers, &set)] as usize),
+        };
+
+        let letters: WordLetters = guess.chars().map(Dictionary::uchar_to_u8).collect();
+        let colours = pattern(&letters, &target);
+
+        print_row(&feedback(&guess, answer));
+
+        if colours == solved_pattern(&letters) {
+            println!("Solved in {turn} guesses");
+            return Ok(());
+        }
+
+        set.retain(|i| pattern(&letters, &answers[*i]) == colours);
+    }
+
+    println!("Not solved in {BOARD_ROWS} guesses");
+
+    Ok(())
+}
+
+/// Looks up the answer for a date, explaining which dates the file covers when it has none
+fn answer_for<'a>(history: &'a History, file: &str, date: NaiveDate) -> Result<&'a str, String> {
+    history.answer(date).ok_or_else(|| match history.range() {
+        Some((first, last)) => {
+            format!("No answer for {date}, {file} has answers from {first} to {last}")
+        }
+        None => format!("No answers in {file}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    #[test]
+    fn answers() {
+        let history = History::new_from_string("2024-03-01 crane\n2024-03-03,SLATE").unwrap();
+
+        assert_eq!(answer_for(&history, "past.txt", date(1)), Ok("CRANE"));
+        assert_eq!(answer_for(&history, "past.txt", date(3)), Ok("SLATE"));
+        assert_eq!(
+            answer_for(&history, "past.txt", date(2)),
+            Err(
+                "No answer for 2024-03-02, past.txt has answers from 2024-03-01 to 2024-03-03"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            answer_for(&history, "past.txt", date(4)),
+            Err(
+                "No answer for 2024-03-04, past.txt has answers from 2024-03-01 to 2024-03-03"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let history = History::new_from_string("# No answers yet\n").unwrap();
+
+        assert_eq!(
+            answer_for(&history, "past.txt", date(1)),
+            Err("No answers in past.txt".to_string())
+        );
+    }
+}
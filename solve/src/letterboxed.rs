@@ -0,0 +1,306 @@
+//! Letter Boxed helper
+
+use dictionary::{Dictionary, LetterNext, NEXT_NONE};
+
+use crate::print_words;
+
+/// Minimum word length
+const MIN_LEN: usize = 3;
+
+/// Side number for letters not in the puzzle
+const NO_SIDE: u8 = u8::MAX;
+
+/// A word usable in a chain
+struct ChainWord {
+    /// Dictionary element
+    elem: LetterNext,
+    /// First letter number
+    first: u8,
+    /// Last letter number
+    last: u8,
+    /// Letters used, bit 0 = A
+    mask: u32,
+}
+
+/// Prints the words which can be played in a Letter Boxed puzzle and the chains with the fewest
+/// words which use every letter
+pub fn letterboxed(dictionary: &Dictionary, sides: &[String], max_words: usize, group: bool) {
+    let side_of = match letter_sides(sides) {
+        Ok(side_of) => side_of,
+        Err(c) => {
+            eprintln!("Letter {c} is on more than one side.");
+            std::process::exit(1);
+        }
+    };
+
+    // Find the words
+    let mut elems = Vec::new();
+
+    find_words_rec(dictionary, &side_of, 0, 0, NO_SIDE, &mut elems);
+
+    print_words(dictionary, &elems, group);
+
+    println!();
+
+    let chains = shortest_chains(dictionary, &side_of, &elems, max_words);
+
+    if chains.is_empty() {
+        println!("No chains of {max_words} words or fewer found");
+    } else {
+        print_chains(&chains);
+    }
+}
+
+/// Maps letters to the side they are on, returning the first letter found on more than one side
+fn letter_sides(sides: &[String]) -> Result<[u8; 26], char> {
+    let mut side_of = [NO_SIDE; 26];
+
+    for (side, letters) in sides.iter().enumerate() {
+        for c in letters.chars() {
+            let letter = Dictionary::uchar_to_usize(c);
+
+            if side_of[letter] != NO_SIDE {
+                return Err(c);
+            }
+
+            side_of[letter] = side as u8;
+        }
+    }
+
+    Ok(side_of)
+}
+
+/// Finds the chains with the fewest words, up to the maximum, which use every letter, shortest
+/// total length first
+fn shortest_chains(
+    dictionary: &Dictionary,
+    side_of: &[u8; 26],
+    elems: &[LetterNext],
+    max_words: usize,
+) -> Vec<Vec<String>> {
+    // Build the chain words
+    let words: Vec<ChainWord> = elems
+        .iter()
+        .map(|elem| chain_word(dictionary, *elem))
+        .collect();
+
+    // Index the words by first letter
+    let mut starting: [Vec<usize>; 26] = Default::default();
+
+    for (i, word) in words.iter().enumerate() {
+        starting[word.first as usize].push(i);
+    }
+
+    // Search for chains with increasing numbers of words
+    let all = side_of
+        .iter()
+        .enumerate()
+        .filter(|(_, side)| **side != NO_SIDE)
+        .fold(0, |mask, (letter, _)| mask | (1 << letter));
+
+    for len in 1..=max_words {
+        let mut chains = Vec::new();
+        let mut chain = Vec::with_capacity(len);
+
+        for i in 0..words.len() {
+            chain.push(i);
+            find_chains_rec(
+                &words,
+                &starting,
+                all,
+                len,
+                words[i].mask,
+                &mut chain,
+                &mut chains,
+            );
+            chain.pop();
+        }
+
+        if !chains.is_empty() {
+            let mut chains: Vec<Vec<String>> = chains
+                .into_iter()
+                .map(|chain| {
+                    chain
+                        .iter()
+                        .map(|i| dictionary.get_word(words[*i].elem as usize))
+                        .collect()
+                })
+                .collect();
+
+            chains.sort_by_key(|chain| {
+                (
+                    chain.iter().map(|word| word.len()).sum::<usize>(),
+                    chain.clone(),
+                )
+            });
+
+            return chains;
+        }
+    }
+
+    Vec::new()
+}
+
+/// Walks the dictionary for words using the puzzle letters without consecutive letters from the
+/// same side
+fn find_words_rec(
+    dictionary: &Dictionary,
+    side_of: &[u8; 26],
+    depth: usize,
+    elem: usize,
+    side: u8,
+    result: &mut Vec<LetterNext>,
+) {
+    if depth >= MIN_LEN && dictionary.is_word(elem) {
+        result.push(elem as LetterNext);
+    }
+
+    for letter in 0..26u8 {
+        let letter_side = side_of[letter as usize];
+
+        if letter_side == NO_SIDE || letter_side == side {
+            continue;
+        }
+
+        let next = dictionary.lookup_elem_letter_num(elem, letter);
+
+        if next != NEXT_NONE {
+            find_words_rec(
+                dictionary,
+                side_of,
+                depth + 1,
+                next as usize,
+                letter_side,
+                result,
+            );
+        }
+    }
+}
+
+/// Builds a chain word from a dictionary element
+fn chain_word(dictionary: &Dictionary, elem: LetterNext) -> ChainWord {
+    let letters: Vec<u8> = dictionary
+        .get_word(elem as usize)
+        .chars()
+        .map(Dictionary::uchar_to_u8)
+        .collect();
+
+    ChainWord {
+        elem,
+        first: letters[0],
+        last: letters[letters.len() - 1],
+        mask: letters.iter().fold(0, |mask, letter| mask | (1 << letter)),
+    }
+}
+
+/// Extends a chain with words starting with the last letter of the previous word until it has
+/// the required length, saving the chains which use every letter
+fn find_chains_rec(
+    words: &[ChainWord],
+    starting: &[Vec<usize>; 26],
+    all: u32,
+    len: usize,
+    mask: u32,
+    chain: &mut Vec<usize>,
+    result: &mut Vec<Vec<usize>>,
+) {
+    if chain.len() == len {
+        if mask == all {
+            result.push(chain.clone());
+        }
+
+        return;
+    }
+
+    let last = words[chain[chain.len() - 1]].last;
+
+    for &next in &starting[last as usize] {
+        let next_mask = mask | words[next].mask;
+
+        // Each word after the first must add a new letter
+        if next_mask != mask {
+            chain.push(next);
+            find_chains_rec(words, starting, all, len, next_mask, chain, result);
+            chain.pop();
+        }
+    }
+}
+
+/// Prints the chains
+fn print_chains(chains: &[Vec<String>]) {
+    println!("{} chains of {} words found", chains.len(), chains[0].len());
+
+    for chain in chains {
+        println!("{}", chain.join(" - "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::{LoadOptions, WordCase, WordLength};
+
+    use super::*;
+
+    fn sides(sides: &[&str]) -> Vec<String> {
+        sides.iter().map(|side| side.to_string()).collect()
+    }
+
+    fn words_and_chains(max_words: usize) -> (Vec<String>, Vec<Vec<String>>) {
+        let options = LoadOptions {
+            case: WordCase::Lower,
+            length: WordLength::Any,
+        };
+
+        let dictionary =
+            Dictionary::new_from_string("acre\ncart\ncat\nore\nox\ntaco\ntore", options, false)
+                .unwrap();
+
+        let side_of = letter_sides(&sides(&["AO", "CR", "TE"])).unwrap();
+
+        let mut elems = Vec::new();
+        find_words_rec(&dictionary, &side_of, 0, 0, NO_SIDE, &mut elems);
+
+        let words = elems
+            .iter()
+            .map(|elem| dictionary.get_word(*elem as usize))
+            .collect();
+
+        (
+            words,
+            shortest_chains(&dictionary, &side_of, &elems, max_words),
+        )
+    }
+
+    #[test]
+    fn words() {
+        // ACRE has C and R from the same side next to each other and OX uses a letter not in
+        // the puzzle
+        let (words, _) = words_and_chains(2);
+
+        assert_eq!(words, ["CART", "CAT", "ORE", "TACO", "TORE"]);
+    }
+
+    #[test]
+    fn chains() {
+        // No single word uses every letter, so the two word chains are found, shortest first
+        let (_, chains) = words_and_chains(3);
+
+        assert_eq!(
+            chains,
+            [
+                vec!["CAT", "TORE"],
+                vec!["TACO", "ORE"],
+                vec!["CART", "TORE"],
+            ]
+        );
+
+        let (_, chains) = words_and_chains(1);
+
+        assert!(chains.is_empty());
+    }
+
+    #[test]
+    fn sides_overlap() {
+        assert_eq!(letter_sides(&sides(&["ABC", "DEF", "GAH"])), Err('A'));
+    }
+}
@@ -0,0 +1,667 @@
+//! Command line Wordle solver and puzzle tools
+
+use std::error::Error;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::NaiveDate;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
+use clap_mangen::Man;
+use dictionary::{
+    bundled_sha256,
+    verify_sha256,
+    Dictionary,
+    LetterNext,
+    LoadOptions,
+    WordCase,
+    WordLength,
+};
+use numformat::{DurationFormat, NumFormat};
+use solver::{
+    find_words,
+    game_rules,
+    letter_groups,
+    long_version,
+    BoardElem,
+    GameRules,
+    SolverArgs,
+    GAME_RULES,
+    VERSION,
+};
+
+mod absurdle;
+mod batch;
+mod bee;
+mod compare;
+mod dictdiff;
+mod difficulty;
+mod hangman;
+mod history;
+mod letterboxed;
+mod openers;
+#[cfg(feature = "script")]
+mod script;
+mod tournament;
+#[cfg(feature = "update-check")]
+mod update;
+
+/// Wordle solver
+#[derive(Parser)]
+#[clap(author, version = VERSION, long_version = long_version(), about)]
+struct Args {
+    /// Word list file
+    #[clap(
+        short = 'd',
+        long = "dictionary",
+        global = true,
+        default_value_t = default_dict().into(),
+    )]
+    dictionary_file: String,
+
+    /// Expected SHA-256 hash of the word list file, checked before loading
+    /// The bundled word list is always checked
+    #[clap(long = "dict-sha256", global = true)]
+    dict_sha256: Option<String>,
+
+    /// Log file
+    #[clap(short = 'l', long = "log-file", global = true)]
+    log_file: Option<String>,
+
+    /// Accept upper and mixed case words in the word list
+    #[clap(short = 'i', long = "ignore-case", global = true)]
+    ignore_case: bool,
+
+    /// Verbose output
+    #[clap(short = 'v', long = "verbose", global = true)]
+    verbose: bool,
+
+    /// Print the time taken to load the word list. A command is optional
+    #[clap(long = "time-load", global = true)]
+    time_load: bool,
+
+    /// Game rules (standard, hard, absurdle or quordle)
+    #[clap(
+        short = 'r',
+        long = "rules",
+        global = true,
+        default_value = "standard",
+        value_parser = parse_rules,
+    )]
+    rules: &'static dyn GameRules,
+
+    /// Seed for random choices, so runs can be repeated
+    #[clap(long = "seed", global = true)]
+    seed: Option<u64>,
+
+    /// Group found words by their first letter
+    #[clap(long = "group", global = true)]
+    group: bool,
+
+    /// Write the word list tree to a Graphviz DOT file after loading the word list. A command is
+    /// optional
+    #[clap(long = "export-dot", value_name = "FILE")]
+    export_dot: Option<PathBuf>,
+
+    /// Number of letters deep to export the word list tree
+    #[clap(long = "dot-depth", default_value_t = 3, requires = "export_dot")]
+    dot_depth: usize,
+
+    /// Check whether a newer release is available, without installing it
+    #[cfg(feature = "update-check")]
+    #[clap(long = "check-update", exclusive = true)]
+    check_update: bool,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the colours for guesses played against known answers and the words which remain
+    Guess {
+        /// Answers to calculate the guess colours against, separated by commas. One is needed
+        /// for each board
+        #[clap(short = 'a', long = "against", value_parser = parse_word, value_delimiter = ',')]
+        against: Vec<String>,
+
+        /// Guessed words
+        #[clap(value_parser = parse_word, required = true)]
+        guesses: Vec<String>,
+    },
+
+    /// Score every word as an opening guess
+    Openers {
+        /// Number of threads to use (default all cores)
+        #[clap(short = 't', long = "threads")]
+        threads: Option<usize>,
+
+        /// Output file. Words already in the file are not scored again
+        #[clap(short = 'o', long = "output")]
+        output: Option<String>,
+    },
+
+    /// Compare opening guesses by playing every answer with each of them
+    Compare {
+        /// Opening guesses, separated by commas
+        #[clap(value_parser = parse_word, value_delimiter = ',', required = true)]
+        openers: Vec<String>,
+
+        /// Number of threads to use (default all cores)
+        #[clap(short = 't', long = "threads")]
+        threads: Option<usize>,
+
+        /// Play this many randomly chosen answers instead of every word
+        #[clap(short = 's', long = "sample")]
+        sample: Option<usize>,
+    },
+
+    /// Rank guessing strategies by playing every answer with each of them, comparing each with
+    /// the winner on the same answers
+    Tournament {
+        /// Strategies to play, separated by commas: entropy, expected, hybrid, frequency or
+        /// random (default all)
+        #[clap(value_delimiter = ',')]
+        players: Vec<tournament::Player>,
+
+        /// Number of threads to use (default all cores)
+        #[clap(short = 't', long = "threads")]
+        threads: Option<usize>,
+
+        /// Play this many randomly chosen answers instead of every word
+        #[clap(short = 's', long = "sample")]
+        sample: Option<usize>,
+    },
+
+    /// Rate how hard words are to find as the answer, from the rarity of their letters, their
+    /// repeated letters and the guesses the solver takes to find them
+    Difficulty {
+        /// Words to rate
+        #[clap(value_parser = parse_word, required = true)]
+        words: Vec<String>,
+
+        /// Opening guesses to play before the solver's guesses, separated by commas (default the
+        /// best opener)
+        #[clap(short = 'o', long = "openers", value_parser = parse_word, value_delimiter = ',')]
+        openers: Vec<String>,
+    },
+
+    /// Play against an adversary which picks the colours to keep as many words possible as it
+    /// can (Absurdle rules)
+    Absurdle {
+        /// Let the solver make all of the guesses
+        #[clap(long = "auto")]
+        auto: bool,
+    },
+
+    /// Find words matching a pattern, with '?', '_' or '.' matching any letter (eg. ?R?NE)
+    Pattern {
+        /// Pattern to match
+        pattern: String,
+
+        /// Letters available to fill the blanks
+        #[clap(long = "letters", value_parser = parse_word)]
+        letters: Option<String>,
+    },
+
+    /// Find the words for a hangman game and the best letter to guess next
+    Hangman {
+        /// Word with the letters found so far and '_' for the blanks (eg. _a__e)
+        #[clap(short = 'p', long = "pattern")]
+        pattern: String,
+
+        /// Letters guessed which aren't in the word
+        #[clap(short = 'w', long = "wrong", value_parser = parse_word)]
+        wrong: Option<String>,
+    },
+
+    /// Find the words for a Spelling Bee puzzle
+    Bee {
+        /// Puzzle letters
+        #[clap(long = "letters", value_parser = parse_word)]
+        letters: String,
+
+        /// Centre letter, which must be in every word
+        #[clap(long = "center", value_parser = parse_letter)]
+        center: char,
+    },
+
+    /// Find the words and shortest word chains for a Letter Boxed puzzle
+    Letterboxed {
+        /// Letters on each side, separated by commas (eg. ABC,DEF,GHI,JKL)
+        #[clap(value_parser = parse_word, value_delimiter = ',', required = true)]
+        sides: Vec<String>,
+
+        /// Maximum number of words in a chain
+        #[clap(short = 'm', long = "max-words", default_value_t = 3)]
+        max_words: usize,
+    },
+
+    /// Show the answer for a past date and the guesses the solver plays to find it
+    History {
+        /// Date of the answer (YYYY-MM-DD)
+        date: NaiveDate,
+
+        /// File of past answers with a date and answer on each line (eg. 2024-03-01 CRANE)
+        #[clap(short = 'f', long = "file", default_value = "history.txt")]
+        file: String,
+
+        /// First guess to play instead of the best word
+        #[clap(short = 'o', long = "opener", value_parser = parse_word)]
+        opener: Option<String>,
+    },
+
+    /// Solve boards read from a file, one per line, printing the words remaining and the next
+    /// guess for each board. Boards are GUESS:PATTERN pairs separated by spaces or '/', where
+    /// patterns have 'b' or '.' for gray, 'y' for yellow and 'g' for green
+    /// (eg. CRANE:..y.g STAIR:g...y)
+    Batch {
+        /// File of boards
+        file: String,
+
+        /// Print a JSON object for each board instead of tab separated values
+        #[clap(long = "json")]
+        json: bool,
+    },
+
+    /// Compare two word lists, showing the words added and removed, the lines each list rejected
+    /// and how many words they share
+    Dictdiff {
+        /// Original word list file
+        old: String,
+
+        /// Updated word list file
+        new: String,
+    },
+
+    /// Run a rhai script which plays guesses on a board and reads the words left, eg. to try out
+    /// a guessing strategy. The script is given the board as the `app` variable
+    #[cfg(feature = "script")]
+    Script {
+        /// Script file
+        file: PathBuf,
+    },
+
+    /// Print shell completions for this tool
+    Completions {
+        /// Shell to complete for
+        shell: Shell,
+    },
+
+    /// Print the man page for this tool in roff format
+    Manpage,
+}
+
+/// Runs with command line arguments, the first being the program name
+pub fn run<I, T>(args: I) -> Result<(), Box<dyn Error>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    // Parse command line arguments, keeping the parser for its program name in errors
+    let mut parser = Args::command();
+    let matches = parser
+        .try_get_matches_from_mut(args)
+        .unwrap_or_else(|e| e.exit());
+    let args = Args::from_arg_matches(&matches)?;
+
+    // Set up logging
+    solveapp::init_logging(args.log_file.as_deref())?;
+
+    #[cfg(feature = "update-check")]
+    if args.check_update {
+        return update::check_update();
+    }
+
+    // Print completions or the man page, which don't need a word list
+    match &args.command {
+        Some(Command::Completions { shell }) => {
+            let mut command = Args::command();
+            let name = command.get_name().to_string();
+
+            clap_complete::generate(*shell, &mut command, name, &mut io::stdout());
+            return Ok(());
+        }
+        Some(Command::Manpage) => {
+            Man::new(Args::command()).render(&mut io::stdout())?;
+            return Ok(());
+        }
+        _ => (),
+    }
+
+    // A command is needed for anything else
+    if args.command.is_none() && args.export_dot.is_none() && !args.time_load {
+        parser
+            .error(ErrorKind::MissingSubcommand, "a command is required")
+            .exit();
+    }
+
+    let case = if args.ignore_case {
+        WordCase::Normalise
+    } else {
+        WordCase::Lower
+    };
+
+    // Compare word lists, which doesn't need the default word list
+    if let Some(Command::Dictdiff { old, new }) = &args.command {
+        let options = LoadOptions {
+            case,
+            length: WordLength::Detect,
+        };
+
+        return dictdiff::dictdiff(old, new, options, args.verbose);
+    }
+
+    // Check we have a dictionary
+    if args.dictionary_file.is_empty() {
+        eprintln!("No dictionary file given and none of the default dictionaries could be found.");
+        eprintln!("Default dictionaries are:");
+
+        for d in DICTS {
+            eprintln!("  {d}");
+        }
+
+        std::process::exit(1);
+    }
+
+    if args.verbose {
+        println!("Wordle solver {VERSION}");
+    }
+
+    // Check the word list hasn't been corrupted or changed
+    let sha256 = args
+        .dict_sha256
+        .as_deref()
+        .or_else(|| bundled_sha256(&args.dictionary_file));
+
+    if let Some(sha256) = sha256 {
+        verify_sha256(&args.dictionary_file, sha256, args.verbose)?;
+    }
+
+    // Load words
+    let length = match &args.command {
+        Some(Command::Bee { .. } | Command::Letterboxed { .. }) => WordLength::Any,
+        _ => WordLength::Detect,
+    };
+
+    let start = Instant::now();
+
+    let dictionary = Dictionary::new_from_file(
+        &args.dictionary_file,
+        LoadOptions { case, length },
+        args.verbose,
+    )?;
+
+    if args.time_load {
+        println!(
+            "Loaded {} words from {} in {}",
+            dictionary.word_count().num_format(),
+            args.dictionary_file,
+            start.elapsed().dur_format()
+        );
+    }
+
+    // Export the word list tree
+    if let Some(path) = &args.export_dot {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        dictionary.export_dot(&mut file, args.dot_depth)?;
+        file.flush()?;
+    }
+
+    let Some(command) = &args.command else {
+        return Ok(());
+    };
+
+    match command {
+        Command::Guess { against, guesses } => {
+            check_len(&dictionary, guesses.iter().chain(against));
+            self::guesses(&dictionary, args.rules, against, guesses, args.group);
+        }
+        Command::Openers { threads, output } => {
+            openers::openers(&dictionary, *threads, output.as_deref())?;
+        }
+        Command::Compare {
+            openers,
+            threads,
+            sample,
+        } => {
+            check_len(&dictionary, openers.iter());
+            compare::compare(&dictionary, openers, *threads, *sample, args.seed)?;
+        }
+        Command::Tournament {
+            players,
+            threads,
+            sample,
+        } => {
+            let players = if players.is_empty() {
+                &tournament::Player::ALL[..]
+            } else {
+                &players[..]
+            };
+
+            tournament::tournament(&dictionary, players, *threads, *sample, args.seed)?;
+        }
+        Command::Difficulty { words, openers } => {
+            check_len(&dictionary, words.iter().chain(openers));
+            difficulty::difficulties(&dictionary, words, openers)?;
+        }
+        Command::Absurdle { auto } => absurdle::absurdle(&dictionary, *auto)?,
+        Command::Pattern { pattern, letters } => {
+            pattern_words(&dictionary, pattern, letters.as_deref(), args.group);
+        }
+        Command::Hangman { pattern, wrong } => {
+            let wrong = wrong.as_deref().unwrap_or_default();
+
+            hangman::hangman(&dictionary, pattern, wrong, args.group);
+        }
+        Command::Bee { letters, center } => bee::bee(&dictionary, letters, *center),
+        Command::Letterboxed { sides, max_words } => {
+            letterboxed::letterboxed(&dictionary, sides, *max_words, args.group);
+        }
+        Command::History { date, file, opener } => {
+            if let Some(opener) = opener {
+                check_len(&dictionary, [opener].into_iter());
+            }
+            history::history(&dictionary, file, *date, opener.as_deref())?;
+        }
+        Command::Batch { file, json } => batch::batch(&dictionary, args.rules, file, *json)?,
+        Command::Dictdiff { .. } => unreachable!("word lists are compared before loading"),
+        Command::Completions { .. } | Command::Manpage => {
+            unreachable!("completions and the man page are printed before loading")
+        }
+        #[cfg(feature = "script")]
+        Command::Script { file } => script::script(dictionary, file)?,
+    }
+
+    Ok(())
+}
+
+/// Checks words are the same length as the dictionary words, exiting if not
+fn check_len<'a>(dictionary: &Dictionary, mut words: impl Iterator<Item = &'a String>) {
+    let word_len = dictionary.word_len();
+
+    if words.any(|word| word.len() != word_len) {
+        eprintln!("Words must be {word_len} letters long to match the dictionary.");
+        std::process::exit(1);
+    }
+}
+
+/// Prints the boards and remaining words for guesses played against the answers
+fn guesses(
+    dictionary: &Dictionary,
+    rules: &dyn GameRules,
+    against: &[String],
+    guesses: &[String],
+    group: bool,
+) {
+    if against.len() != rules.boards() {
+        eprintln!(
+            "The {} rules need {} answers to play against.",
+            rules.name(),
+            rules.boards()
+        );
+        std::process::exit(1);
+    }
+
+    if rules.rows().is_some_and(|rows| guesses.len() > rows) {
+        eprintln!("Too many guesses for the {} rules.", rules.name());
+        std::process::exit(1);
+    }
+
+    for (i, answer) in against.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        // Build the board from the guesses, stopping when solved
+        let mut board = Vec::new();
+
+        for guess in guesses {
+            if !rules.valid_guess(&board, guess) {
+                eprintln!("{guess} doesn't use all of the hints given by the earlier guesses.");
+                std::process::exit(1);
+            }
+
+            let row = rules.feedback(guess, answer);
+
+            print_row(&row);
+            board.push(row);
+
+            if guess == answer {
+                break;
+            }
+        }
+
+        println!();
+
+        // Find matching words
+        let result = find_words(SolverArgs {
+            board: &board,
+            dictionary,
+            rules,
+            debug: false,
+            cancel: None,
+        });
+
+        print_words(dictionary, &result.words, group);
+    }
+}
+
+/// Prints the words matching a pattern
+fn pattern_words(dictionary: &Dictionary, pattern: &str, letters: Option<&str>, group: bool) {
+    let available = letters.map(|letters| {
+        let mut available = [false; 26];
+
+        for c in letters.chars() {
+            available[Dictionary::uchar_to_usize(c)] = true;
+        }
+
+        available
+    });
+
+    print_words(
+        dictionary,
+        &dictionary.find_pattern(pattern, available.as_ref()),
+        group,
+    );
+}
+
+/// Prints a board row with ANSI colours
+fn print_row(row: &[BoardElem]) {
+    let line = row.iter().fold(String::new(), |mut line, elem| {
+        let (c, colour) = match elem {
+            BoardElem::Empty => (' ', 100),
+            BoardElem::Unknown(c) => (*c, 40),
+            BoardElem::Gray(c) => (*c, 100),
+            BoardElem::Yellow(c) => (*c, 43),
+            BoardElem::Green(c) => (*c, 42),
+        };
+
+        line.push_str(&format!("\x1b[1;97;{colour}m {c} \x1b[0m "));
+
+        line
+    });
+
+    println!("{}", line.trim_end());
+}
+
+/// Prints the found words list, or a line for each first letter with its word count if grouping
+fn print_words(dictionary: &Dictionary, words: &[LetterNext], group: bool) {
+    println!("{} words found", words.len());
+
+    if group {
+        for (c, group) in letter_groups(dictionary, words) {
+            let line = group
+                .iter()
+                .map(|i| dictionary.get_word(words[*i] as usize))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            println!("{c} ({}): {line}", group.len());
+        }
+
+        return;
+    }
+
+    for chunk in words.chunks(10) {
+        let line = chunk
+            .iter()
+            .map(|elem| dictionary.get_word(*elem as usize))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        println!("{line}");
+    }
+}
+
+/// Parses and validates a word argument
+fn parse_word(word: &str) -> Result<String, String> {
+    if word.is_empty() || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        Err("words must only contain letters".to_string())
+    } else {
+        Ok(word.to_ascii_uppercase())
+    }
+}
+
+/// Parses and validates a single letter argument
+fn parse_letter(letter: &str) -> Result<char, String> {
+    match parse_word(letter)?.chars().collect::<Vec<_>>()[..] {
+        [c] => Ok(c),
+        _ => Err("must be a single letter".to_string()),
+    }
+}
+
+/// Parses a game rules name
+fn parse_rules(name: &str) -> Result<&'static dyn GameRules, String> {
+    game_rules(name).ok_or_else(|| {
+        let names: Vec<_> = GAME_RULES.iter().map(|rules| rules.name()).collect();
+
+        format!("must be one of {}", names.join(", "))
+    })
+}
+
+const DICTS: [&str; 3] = [
+    "words.txt",
+    "words.txt.gz",
+    "/etc/dictionaries-common/words",
+];
+
+fn default_dict() -> &'static str {
+    DICTS
+        .iter()
+        .find(|d| dict_valid(d).is_some())
+        .unwrap_or(&"")
+}
+
+fn dict_valid(dict: &str) -> Option<String> {
+    if Path::new(dict).is_file() {
+        Some(dict.into())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,136 @@
+//! Opening guess analysis
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::sync::Mutex;
+
+use dictionary::Dictionary;
+use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use solver::{empty_board, find_words, score_guess, word_letters, SolverArgs, Standard};
+
+/// Number of best openers to print
+const TOP_OPENERS: usize = 20;
+
+/// Scores every dictionary word as an opening guess against all of the dictionary words
+/// Results are appended to the output file as they are calculated so an interrupted run can be
+/// resumed
+pub fn openers(
+    dictionary: &Dictionary,
+    threads: Option<usize>,
+    output: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    // Get all of the words
+    let board = empty_board(dictionary.word_len());
+
+    let words = find_words(SolverArgs {
+        board: &board,
+        dictionary,
+        rules: &Standard,
+        debug: false,
+        cancel: None,
+    })
+    .words;
+
+    let letters: Vec<_> = words
+        .iter()
+        .map(|elem| word_letters(dictionary, *elem))
+        .collect();
+
+    // Load previous results
+    let mut results = match output {
+        Some(file) => load_results(file)?,
+        None => Vec::new(),
+    };
+
+    let done: HashSet<String> = results.iter().map(|(word, _)| word.clone()).collect();
+
+    if !done.is_empty() {
+        println!("{} openers loaded from previous run", done.len());
+    }
+
+    // Work out which words still need scoring
+    let todo: Vec<_> = words
+        .iter()
+        .zip(letters.iter())
+        .map(|(elem, letters)| (dictionary.get_word(*elem as usize), letters))
+        .filter(|(word, _)| !done.contains(word))
+        .collect();
+
+    // Open the output file for appending
+    let file = match output {
+        Some(file) => Some(Mutex::new(
+            OpenOptions::new().create(true).append(true).open(file)?,
+        )),
+        None => None,
+    };
+
+    // Create the thread pool
+    let mut pool = ThreadPoolBuilder::new();
+
+    if let Some(threads) = threads {
+        pool = pool.num_threads(threads);
+    }
+
+    let pool = pool.build()?;
+
+    // Create the progress bar
+    let progress = ProgressBar::new(todo.len() as u64).with_style(
+        ProgressStyle::with_template("{wide_bar} {pos}/{len} {per_sec} eta {eta}")
+            .expect("Invalid progress bar template"),
+    );
+
+    // Score the words
+    let scored = pool.install(|| {
+        todo.par_iter()
+            .progress_with(progress)
+            .map(|(word, guess)| {
+                let score = score_guess(guess, &letters);
+
+                if let Some(file) = &file {
+                    writeln!(file.lock().unwrap(), "{word}\t{score}")?;
+                }
+
+                Ok((word.clone(), score))
+            })
+            .collect::<io::Result<Vec<_>>>()
+    })?;
+
+    results.extend(scored);
+
+    // Print the best openers
+    results.sort_by(|(wa, a), (wb, b)| b.total_cmp(a).then(wa.cmp(wb)));
+
+    for (word, score) in results.iter().take(TOP_OPENERS) {
+        println!("{word} {score:.4}");
+    }
+
+    Ok(())
+}
+
+/// Loads opener results from a previous run. A missing file is treated as no results
+fn load_results(file: &str) -> io::Result<Vec<(String, f32)>> {
+    let file = match File::open(file) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut results = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+
+        // Ignore partially written lines
+        if let Some((word, score)) = line.split_once('\t') {
+            if let Ok(score) = score.parse() {
+                results.push((word.to_string(), score));
+            }
+        }
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,17 @@
+//! Scripts playing guesses on a board
+
+use std::error::Error;
+use std::path::Path;
+
+use dictionary::Dictionary;
+use solveapp::script::Script;
+use solveapp::SolveApp;
+
+/// Runs a rhai script against an empty board. The board follows the standard rules
+pub fn script(dictionary: Dictionary, file: &Path) -> Result<(), Box<dyn Error>> {
+    let script = Script::new(SolveApp::new(dictionary));
+
+    script.run_file(file)?;
+
+    Ok(())
+}
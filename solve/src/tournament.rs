@@ -0,0 +1,488 @@
+//! Tournament between guessing strategies
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use dictionary::{Dictionary, LetterNext};
+use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use solver::{
+    expected_guesses,
+    partition,
+    play_answer_by,
+    score_guess,
+    solved_pattern,
+    word_letters,
+    Distribution,
+    Pattern,
+    Random,
+    ScoreKind,
+    Strategy,
+    WordLetters,
+    BOARD_ROWS,
+};
+
+/// Guesses counted for a game which isn't solved, as Distribution::expected counts them
+const FAILED_GUESSES: u8 = BOARD_ROWS as u8 + 1;
+
+/// Two-sided p-value below which a difference between strategies is significant
+const SIGNIFICANCE: f64 = 0.05;
+
+/// Guessing strategy entered in a tournament
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Player {
+    /// Guess the answer left scoring best with a word ranking strategy
+    Strategy(Strategy),
+    /// Guess the answer left whose letters appear in the most of the answers left
+    Frequency,
+    /// Guess a random answer left, as a baseline
+    Random,
+}
+
+impl Player {
+    /// Every player, in the order they are listed by default
+    pub const ALL: [Self; 5] = [
+        Self::Strategy(Strategy::Entropy),
+        Self::Strategy(Strategy::Expected),
+        Self::Strategy(Strategy::Hybrid),
+        Self::Frequency,
+        Self::Random,
+    ];
+
+    /// Chooses the next guess from the answers left, returning its answer number
+    /// Ties go to the first answer
+    fn choose(self, answers: &[WordLetters], set: &[usize], random: &mut Random) -> usize {
+        if set.len() == 1 {
+            return set[0];
+        }
+
+        match self {
+            Self::Strategy(strategy) => {
+                let letters: Vec<WordLetters> = set.iter().map(|i| answers[*i].clone()).collect();
+                let kind = strategy.score_kind(set.len());
+
+                let score = |guess: &WordLetters| match kind {
+                    ScoreKind::Information => score_guess(guess, &letters),
+                    ScoreKind::Guesses => expected_guesses(guess, &letters),
+                };
+
+                best(set, |i| score(&answers[i]), |a, b| kind.compare(a, b))
+            }
+            Self::Frequency => {
+                let mut counts = [0usize; 26];
+
+                for i in set {
+                    for letter in distinct(&answers[*i]) {
+                        counts[letter as usize] += 1;
+                    }
+                }
+
+                best(
+                    set,
+                    |i| {
+                        distinct(&answers[i])
+                            .map(|letter| counts[letter as usize])
+                            .sum()
+                    },
+                    |a: usize, b| a.cmp(&b),
+                )
+            }
+            Self::Random => set[random.index(set.len())],
+        }
+    }
+}
+
+impl FromStr for Player {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "frequency" => Ok(Self::Frequency),
+            "random" => Ok(Self::Random),
+            _ => Strategy::from_str(s).map(Self::Strategy),
+        }
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Strategy(strategy) => strategy.fmt(f),
+            Self::Frequency => f.write_str("frequency"),
+            Self::Random => f.write_str("random"),
+        }
+    }
+}
+
+/// Results of one player's games
+struct Entry {
+    /// Player
+    player: Player,
+    /// Guesses taken for each answer, with failed games counted as one more than the board rows
+    guesses: Vec<u8>,
+    /// Number of games solved with each number of guesses
+    dist: Distribution,
+}
+
+/// Comparison of one player's games with another's over the same answers
+struct Paired {
+    /// Mean extra guesses taken
+    diff: f64,
+    /// Standard error of the mean extra guesses
+    error: f64,
+    /// Number of answers the player needed fewer guesses for
+    better: usize,
+    /// Number of answers the player needed more guesses for
+    worse: usize,
+    /// Two-sided p-value for the difference being chance
+    p: f64,
+}
+
+/// Plays every dictionary word as the answer with each player and prints the players ranked by
+/// their expected guesses, with each compared to the winner over the same answers
+/// If a sample size is given only that many randomly chosen answers are played
+pub fn tournament(
+    dictionary: &Dictionary,
+    players: &[Player],
+    threads: Option<usize>,
+    sample: Option<usize>,
+    seed: Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    // Get all of the words
+    let answers: Vec<WordLetters> = dictionary
+        .word_elems()
+        .map(|elem| word_letters(dictionary, elem as LetterNext))
+        .collect();
+
+    let all: Vec<usize> = (0..answers.len()).collect();
+
+    // Create the thread pool
+    let mut pool = ThreadPoolBuilder::new();
+
+    if let Some(threads) = threads {
+        pool = pool.num_threads(threads);
+    }
+
+    let pool = pool.build()?;
+
+    // The random player's choices are repeatable with the seed
+    let seed = Random::new(seed).seed();
+
+    let played = match sample {
+        Some(count) => Random::new(Some(seed)).sample(answers.len(), count),
+        None => all.clone(),
+    };
+
+    eprintln!(
+        "Playing {} answers with {} strategies (seed {seed})",
+        played.len(),
+        players.len()
+    );
+
+    let progress = progress_bar(players.len() * played.len());
+
+    let mut entries: Vec<Entry> = players
+        .iter()
+        .map(|player| {
+            let guesses = match sample {
+                Some(_) => play_sample(&pool, &answers, &all, &played, *player, seed, &progress),
+                None => play_all(&pool, &answers, &all, *player, seed, &progress),
+            };
+
+            let mut dist = Distribution::default();
+
+            for guesses in &guesses {
+                match guesses {
+                    &FAILED_GUESSES => dist.failed += 1,
+                    n => dist.solved[*n as usize - 1] += 1,
+                }
+            }
+
+            Entry {
+                player: *player,
+                guesses,
+                dist,
+            }
+        })
+        .collect();
+
+    progress.finish_and_clear();
+
+    // Rank by expected guesses, then by failures
+    entries.sort_by(|a, b| {
+        a.dist
+            .expected()
+            .total_cmp(&b.dist.expected())
+            .then(a.dist.failed.cmp(&b.dist.failed))
+    });
+
+    // Print the ranking table
+    print!(
+        "{:<4} {:<10} {:>8} {:>6} {:>7}",
+        "Rank", "Strategy", "Expected", "Mean", "Fail %"
+    );
+
+    for guesses in 1..=BOARD_ROWS {
+        print!(" {guesses:>6}");
+    }
+
+    println!();
+
+    for (rank, entry) in entries.iter().enumerate() {
+        print!(
+            "{:<4} {:<10} {:>8.3} {:>6.3} {:>7.2}",
+            rank + 1,
+            entry.player.to_string(),
+            entry.dist.expected(),
+            entry.dist.mean(),
+            entry.dist.fail_rate() * 100.0
+        );
+
+        for solved in entry.dist.solved {
+            print!(" {solved:>6}");
+        }
+
+        println!();
+    }
+
+    // Compare the others with the winner answer by answer
+    let Some((winner, others)) = entries.split_first() else {
+        return Ok(());
+    };
+
+    if others.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "Compared with {} on the same answers (* significant at p < {SIGNIFICANCE})",
+        winner.player
+    );
+    println!(
+        "{:<10} {:>7} {:>19} {:>7} {:>7} {:>9}",
+        "Strategy", "Extra", "95% interval", "Better", "Worse", "p"
+    );
+
+    for entry in others {
+        let paired = paired(&entry.guesses, &winner.guesses);
+        let interval = format!(
+            "{:+.3} to {:+.3}",
+            paired.diff - 1.96 * paired.error,
+            paired.diff + 1.96 * paired.error
+        );
+
+        println!(
+            "{:<10} {:>+7.3} {:>19} {:>7} {:>7} {:>9.2e}{}",
+            entry.player.to_string(),
+            paired.diff,
+            interval,
+            paired.better,
+            paired.worse,
+            paired.p,
+            if paired.p < SIGNIFICANCE { " *" } else { "" }
+        );
+    }
+
+    Ok(())
+}
+
+/// Plays every answer with a player, returning the guesses taken for each
+/// The first guess splits the answers by the pattern they give, and the groups are played in
+/// parallel
+fn play_all(
+    pool: &ThreadPool,
+    answers: &[WordLetters],
+    all: &[usize],
+    player: Player,
+    seed: u64,
+    progress: &ProgressBar,
+) -> Vec<u8> {
+    let mut guesses = vec![0; answers.len()];
+
+    let first = player.choose(answers, all, &mut node_random(seed, all, 1));
+    let groups: Vec<(Pattern, Vec<usize>)> = partition(answers, all, &answers[first])
+        .into_iter()
+        .collect();
+
+    let results: Vec<Vec<(usize, u8)>> = pool.install(|| {
+        groups
+            .par_iter()
+            .map(|(pattern, group)| {
+                let mut results = Vec::with_capacity(group.len());
+
+                if *pattern == solved_pattern(&answers[first]) {
+                    results.extend(group.iter().map(|answer| (*answer, 1)));
+                } else {
+                    play_group(answers, group, 2, player, seed, &mut results);
+                }
+
+                progress.inc(group.len() as u64);
+                results
+            })
+            .collect()
+    });
+
+    for (answer, taken) in results.into_iter().flatten() {
+        guesses[answer] = taken;
+    }
+
+    guesses
+}
+
+/// Plays a group of answers left after the same guesses, starting on the given turn, adding the
+/// guesses taken for each answer to the results
+fn play_group(
+    answers: &[WordLetters],
+    set: &[usize],
+    turn: usize,
+    player: Player,
+    seed: u64,
+    results: &mut Vec<(usize, u8)>,
+) {
+    if turn > BOARD_ROWS {
+        results.extend(set.iter().map(|answer| (*answer, FAILED_GUESSES)));
+        return;
+    }
+
+    let guess = player.choose(answers, set, &mut node_random(seed, set, turn));
+
+    for (pattern, group) in partition(answers, set, &answers[guess]) {
+        if pattern == solved_pattern(&answers[guess]) {
+            results.extend(group.iter().map(|answer| (*answer, turn as u8)));
+        } else {
+            play_group(answers, &group, turn + 1, player, seed, results);
+        }
+    }
+}
+
+/// Plays a sample of the answers with a player, each game starting with all of the answers
+/// possible, returning the guesses taken for each answer in the sample
+fn play_sample(
+    pool: &ThreadPool,
+    answers: &[WordLetters],
+    all: &[usize],
+    sample: &[usize],
+    player: Player,
+    seed: u64,
+    progress: &ProgressBar,
+) -> Vec<u8> {
+    // The first guess is the same for every answer unless it is random
+    let first = player.choose(answers, all, &mut node_random(seed, all, 1));
+
+    pool.install(|| {
+        sample
+            .par_iter()
+            .progress_with(progress.clone())
+            .map(|answer| {
+                let mut random = Random::new(Some(seed ^ mix(*answer as u64)));
+                let first = match player {
+                    Player::Random => all[random.index(all.len())],
+                    _ => first,
+                };
+
+                let dist = play_answer_by(answers, all, &answers[first], *answer, |set| {
+                    player.choose(answers, set, &mut random)
+                });
+
+                dist.solved
+                    .iter()
+                    .position(|solved| *solved > 0)
+                    .map_or(FAILED_GUESSES, |i| i as u8 + 1)
+            })
+            .collect()
+    })
+}
+
+/// Compares the guesses taken by a player with another player's for the same answers
+fn paired(guesses: &[u8], against: &[u8]) -> Paired {
+    let diffs: Vec<f64> = guesses
+        .iter()
+        .zip(against)
+        .map(|(a, b)| f64::from(*a) - f64::from(*b))
+        .collect();
+
+    let n = diffs.len() as f64;
+    let diff = diffs.iter().sum::<f64>() / n;
+    let variance = diffs.iter().map(|d| (d - diff).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+    let error = (variance / n).sqrt();
+
+    // Normal approximation to the paired t-test, which is close with this many answers
+    let p = if error > 0.0 {
+        erfc((diff / error).abs() / std::f64::consts::SQRT_2)
+    } else if diff == 0.0 {
+        1.0
+    } else {
+        0.0
+    };
+
+    Paired {
+        diff,
+        error,
+        better: diffs.iter().filter(|d| **d < 0.0).count(),
+        worse: diffs.iter().filter(|d| **d > 0.0).count(),
+        p,
+    }
+}
+
+/// Complementary error function, accurate to 1.5e-7 (Abramowitz and Stegun 7.1.26)
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+
+    poly * (-x * x).exp()
+}
+
+/// Returns the best scoring answer in a set, the first on a tie
+fn best<S: Copy>(
+    set: &[usize],
+    score: impl Fn(usize) -> S,
+    compare: impl Fn(S, S) -> Ordering,
+) -> usize {
+    set.iter()
+        .map(|i| (*i, score(*i)))
+        .fold(None, |best: Option<(usize, S)>, (i, score)| match best {
+            Some((_, best_score)) if compare(best_score, score) != Ordering::Less => best,
+            _ => Some((i, score)),
+        })
+        .map(|(i, _)| i)
+        .expect("Empty answer set")
+}
+
+/// Returns the distinct letter numbers in a word
+fn distinct(word: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    word.iter()
+        .enumerate()
+        .filter(|(i, letter)| !word[..*i].contains(letter))
+        .map(|(_, letter)| *letter)
+}
+
+/// Creates the random generator for choosing a guess for a group of answers on a turn, so the
+/// random player's games don't depend on the order the groups are played in
+fn node_random(seed: u64, set: &[usize], turn: usize) -> Random {
+    Random::new(Some(
+        seed ^ mix(set[0] as u64) ^ mix(turn as u64 + 1).rotate_left(32),
+    ))
+}
+
+/// Spreads the bits of a number (SplitMix64 finaliser)
+fn mix(n: u64) -> u64 {
+    let n = (n ^ (n >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let n = (n ^ (n >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+    n ^ (n >> 31)
+}
+
+/// Creates a progress bar
+fn progress_bar(total: usize) -> ProgressBar {
+    ProgressBar::new(total as u64).with_style(
+        ProgressStyle::with_template("{wide_bar} {pos}/{len} {per_sec} eta {eta}")
+            .expect("Invalid progress bar template"),
+    )
+}
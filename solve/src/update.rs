@@ -0,0 +1,65 @@
+//! Check for a newer release
+
+use std::error::Error;
+use std::io;
+use std::process::Command;
+
+use semver::Version;
+
+/// GitHub API address of the latest release
+const LATEST_RELEASE: &str = "https://api.github.com/repos/andywarduk/wordle/releases/latest";
+
+/// Asks GitHub for the latest release and reports whether it's newer than this version. Uses
+/// curl to fetch the release so no TLS stack is built in, so curl must be on the PATH
+pub fn check_update() -> Result<(), Box<dyn Error>> {
+    let output = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--location",
+            "--max-time",
+            "10",
+            "--header",
+            "Accept: application/vnd.github+json",
+            LATEST_RELEASE,
+        ])
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => {
+                "Checking for updates needs curl, which isn't installed or isn't on the PATH"
+                    .to_string()
+            }
+            _ => format!("Unable to run curl: {e}"),
+        })?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Unable to fetch the latest release: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    let release: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+    let Some(tag) = release["tag_name"].as_str() else {
+        return Err("Latest release has no tag".into());
+    };
+
+    let latest = Version::parse(tag.trim_start_matches('v'))
+        .map_err(|e| format!("Unable to parse release version {tag}: {e}"))?;
+    let current = Version::parse(env!("CARGO_PKG_VERSION"))?;
+
+    if latest > current {
+        println!("Version {latest} is available, this is version {current}");
+
+        if let Some(url) = release["html_url"].as_str() {
+            println!("Download it from {url}");
+        }
+    } else {
+        println!("Version {current} is up to date");
+    }
+
+    Ok(())
+}
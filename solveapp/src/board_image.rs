@@ -0,0 +1,538 @@
+//! Board import from a screenshot of a game, and export as an image
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+use solver::{BoardElem, BoardRow};
+
+use crate::tile_rgb;
+
+/// Tile colour
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tile {
+    Gray,
+    Yellow,
+    Green,
+}
+
+/// Tile colours from the game's light, dark and high contrast themes
+const TILE_COLOURS: [([u8; 3], Tile); 8] = [
+    ([0x78, 0x7c, 0x7e], Tile::Gray),
+    ([0xc9, 0xb4, 0x58], Tile::Yellow),
+    ([0x6a, 0xaa, 0x64], Tile::Green),
+    ([0x3a, 0x3a, 0x3c], Tile::Gray),
+    ([0xb5, 0x9f, 0x3b], Tile::Yellow),
+    ([0x53, 0x8d, 0x4e], Tile::Green),
+    ([0x85, 0xc0, 0xf9], Tile::Yellow),
+    ([0xf5, 0x79, 0x3a], Tile::Green),
+];
+
+/// Largest squared distance from a tile colour for a pixel to match it
+const MAX_DISTANCE: u32 = 40 * 40;
+
+/// Shortest run of tile coloured pixels which can be a tile
+const MIN_TILE: usize = 8;
+
+/// Size of an exported tile in pixels
+const EXPORT_TILE: usize = 48;
+
+/// Gap between exported tiles and around the board in pixels
+const EXPORT_GAP: usize = 6;
+
+/// Width of the border of an exported tile without a colour in pixels
+const EXPORT_BORDER: usize = 2;
+
+/// Size of each pixel of an exported letter
+const EXPORT_SCALE: usize = 4;
+
+/// Background colour of an exported board
+const EXPORT_BACKGROUND: [u8; 3] = [0xff, 0xff, 0xff];
+
+/// Border colour of an exported tile without a colour
+const EXPORT_BORDER_COLOUR: [u8; 3] = [0xd3, 0xd6, 0xda];
+
+/// 5x7 pixel letters A to Z for exported tiles, a row per byte with the leftmost pixel in bit 4
+const FONT: [[u8; 7]; 26] = [
+    [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11],
+    [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e],
+    [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e],
+    [0x1e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1e],
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f],
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10],
+    [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f],
+    [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11],
+    [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e],
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c],
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f],
+    [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11],
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+    [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e],
+    [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10],
+    [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d],
+    [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11],
+    [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e],
+    [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e],
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04],
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a],
+    [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11],
+    [0x11, 0x11, 0x11, 0x0a, 0x04, 0x04, 0x04],
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f],
+];
+
+/// Image with each pixel quantized to a tile colour
+struct TileImage {
+    width: usize,
+    pixels: Vec<Option<Tile>>,
+}
+
+/// Run of tile coloured pixels on a line of the image
+#[derive(Clone, Copy, Debug)]
+struct Run {
+    len: usize,
+    tile: Tile,
+}
+
+/// Reads the board from a PNG screenshot of a game given the words guessed
+/// Rows of tiles are found from the top of the image, so the keyboard below the board is ignored
+pub fn board_from_image(path: &Path, words: &[String]) -> io::Result<Vec<BoardRow>> {
+    board_from_png(BufReader::new(File::open(path)?), words)
+}
+
+/// Reads the board from PNG data given the words guessed
+fn board_from_png(reader: impl Read, words: &[String]) -> io::Result<Vec<BoardRow>> {
+    let cols = words.first().map(|word| word.len()).unwrap_or_default();
+
+    if cols == 0
+        || words
+            .iter()
+            .any(|word| word.len() != cols || !word.chars().all(|c| c.is_ascii_alphabetic()))
+    {
+        return Err(invalid(
+            "Words must be letters only and all the same length",
+        ));
+    }
+
+    let image = decode(reader)?;
+    let rows = image.tile_rows(cols);
+
+    if rows.len() < words.len() {
+        return Err(invalid(&format!(
+            "Found {} rows of tiles in the image but {} words were given",
+            rows.len(),
+            words.len()
+        )));
+    }
+
+    Ok(words
+        .iter()
+        .zip(rows)
+        .map(|(word, row)| {
+            word.chars()
+                .zip(row)
+                .map(|(c, tile)| {
+                    let c = c.to_ascii_uppercase();
+
+                    match tile {
+                        Tile::Gray => BoardElem::Gray(c),
+                        Tile::Yellow => BoardElem::Yellow(c),
+                        Tile::Green => BoardElem::Green(c),
+                    }
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Decodes a PNG image, quantizing each pixel to a tile colour
+fn decode(reader: impl Read) -> io::Result<TileImage> {
+    let mut decoder = Decoder::new(reader);
+    decoder.set_transformations(Transformations::normalize_to_color8());
+
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+
+    let channels = match info.color_type {
+        ColorType::Rgb => 3,
+        ColorType::Rgba => 4,
+        _ => return Err(invalid("Only colour images can be imported")),
+    };
+
+    let pixels = buf[..info.buffer_size()]
+        .chunks_exact(info.line_size)
+        .flat_map(|line| {
+            line[..info.width as usize * channels]
+                .chunks_exact(channels)
+                .map(quantize)
+        })
+        .collect();
+
+    Ok(TileImage {
+        width: info.width as usize,
+        pixels,
+    })
+}
+
+/// Finds the tile colour nearest to a pixel, if close enough
+fn quantize(pixel: &[u8]) -> Option<Tile> {
+    TILE_COLOURS
+        .iter()
+        .map(|(colour, tile)| {
+            let distance: u32 = colour
+                .iter()
+                .zip(pixel)
+                .map(|(a, b)| (*a as i32 - *b as i32).unsigned_abs().pow(2))
+                .sum();
+
+            (distance, *tile)
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, _)| *distance <= MAX_DISTANCE)
+        .map(|(_, tile)| tile)
+}
+
+impl TileImage {
+    /// Finds rows of the given number of tiles from the top of the image
+    /// A row is a band of lines each crossing one run of tile colour per tile. Letters on the
+    /// tiles can split a row into more than one band, so bands starting within a tile's height
+    /// of the last row are skipped
+    fn tile_rows(&self, cols: usize) -> Vec<Vec<Tile>> {
+        let mut rows = Vec::new();
+        let mut next_y = 0;
+        let mut band: Vec<(usize, Vec<Run>)> = Vec::new();
+
+        let height = self.pixels.len() / self.width;
+
+        for y in 0..=height {
+            let runs = if y < height {
+                self.tile_line(y, cols)
+            } else {
+                None
+            };
+
+            match runs {
+                Some(runs) => band.push((y, runs)),
+                None if !band.is_empty() => {
+                    let (start, _) = band[0];
+                    let (_, runs) = &band[band.len() / 2];
+                    let size = runs[0].len;
+
+                    // Ignore borders of empty tiles and bands within the last row
+                    if band.len() * 10 >= size && start >= next_y {
+                        rows.push(runs.iter().map(|run| run.tile).collect());
+                        next_y = start + size;
+                    }
+
+                    band.clear();
+                }
+                None => (),
+            }
+        }
+
+        rows
+    }
+
+    /// Returns the runs on a line if it crosses the given number of similar sized tiles
+    fn tile_line(&self, y: usize, cols: usize) -> Option<Vec<Run>> {
+        let line = &self.pixels[y * self.width..(y + 1) * self.width];
+
+        let mut runs = Vec::new();
+        let mut x = 0;
+
+        while x < line.len() {
+            let Some(tile) = line[x] else {
+                x += 1;
+                continue;
+            };
+
+            // Count the run of pixels of any tile colour, and those matching its first pixel
+            let len = line[x..].iter().take_while(|p| p.is_some()).count();
+            let matching = line[x..x + len]
+                .iter()
+                .filter(|p| **p == Some(tile))
+                .count();
+
+            if len >= MIN_TILE && matching * 2 > len {
+                runs.push(Run { len, tile });
+            }
+
+            x += len;
+        }
+
+        let max = runs.iter().map(|run| run.len).max()?;
+
+        if runs.len() == cols && runs.iter().all(|run| run.len * 4 >= max * 3) {
+            Some(runs)
+        } else {
+            None
+        }
+    }
+}
+
+/// Saves the rows of a board with letters as a PNG image in the game's light theme colours
+pub fn save_board_image(path: &Path, board: &[BoardRow]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    file.write_all(&board_to_png(board)?)?;
+    file.flush()
+}
+
+/// Draws the rows of a board with letters as a PNG image in the game's light theme colours
+/// Coloured tiles have a white letter, and tiles without a colour have a border and a black letter
+pub fn board_to_png(board: &[BoardRow]) -> io::Result<Vec<u8>> {
+    let rows: Vec<&BoardRow> = board
+        .iter()
+        .filter(|row| row.iter().any(|elem| *elem != BoardElem::Empty))
+        .collect();
+
+    let cols = rows.first().map_or(0, |row| row.len());
+
+    if cols == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The board is empty",
+        ));
+    }
+
+    let width = EXPORT_GAP + cols * (EXPORT_TILE + EXPORT_GAP);
+    let height = EXPORT_GAP + rows.len() * (EXPORT_TILE + EXPORT_GAP);
+
+    let mut pixels = vec![EXPORT_BACKGROUND; width * height];
+
+    let mut fill = |x: usize, y: usize, w: usize, h: usize, colour: [u8; 3]| {
+        for py in y..y + h {
+            pixels[py * width + x..py * width + x + w].fill(colour);
+        }
+    };
+
+    for (rn, row) in rows.iter().enumerate() {
+        for (cn, elem) in row.iter().enumerate() {
+            let x = EXPORT_GAP + cn * (EXPORT_TILE + EXPORT_GAP);
+            let y = EXPORT_GAP + rn * (EXPORT_TILE + EXPORT_GAP);
+
+            // Draw the tile, with white letters on coloured tiles and black letters otherwise
+            let ink = match tile_rgb(elem) {
+                Some(colour) => {
+                    fill(x, y, EXPORT_TILE, EXPORT_TILE, colour);
+                    EXPORT_BACKGROUND
+                }
+                None => {
+                    let inner = EXPORT_TILE - 2 * EXPORT_BORDER;
+
+                    fill(x, y, EXPORT_TILE, EXPORT_TILE, EXPORT_BORDER_COLOUR);
+                    fill(
+                        x + EXPORT_BORDER,
+                        y + EXPORT_BORDER,
+                        inner,
+                        inner,
+                        EXPORT_BACKGROUND,
+                    );
+                    [0; 3]
+                }
+            };
+
+            let letter = match elem {
+                BoardElem::Empty => continue,
+                BoardElem::Unknown(c)
+                | BoardElem::Gray(c)
+                | BoardElem::Yellow(c)
+                | BoardElem::Green(c) => c.to_ascii_uppercase(),
+            };
+
+            if !letter.is_ascii_uppercase() {
+                continue;
+            }
+
+            // Draw the letter in the middle of the tile
+            let lx = x + (EXPORT_TILE - 5 * EXPORT_SCALE) / 2;
+            let ly = y + (EXPORT_TILE - 7 * EXPORT_SCALE) / 2;
+
+            for (gy, bits) in FONT[(letter as u8 - b'A') as usize].iter().enumerate() {
+                for gx in (0..5).filter(|gx| bits & (0x10 >> gx) != 0) {
+                    let (px, py) = (lx + gx * EXPORT_SCALE, ly + gy * EXPORT_SCALE);
+
+                    fill(px, py, EXPORT_SCALE, EXPORT_SCALE, ink);
+                }
+            }
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = Encoder::new(&mut png, width as u32, height as u32);
+    encoder.set_color(ColorType::Rgb);
+    encoder.set_depth(BitDepth::Eight);
+
+    encoder
+        .write_header()?
+        .write_image_data(pixels.concat().as_slice())?;
+
+    Ok(png)
+}
+
+/// Creates an invalid data error
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use solver::feedback;
+
+    use super::*;
+
+    /// Tile size in the test images
+    const SIZE: usize = 40;
+    /// Gap between tiles in the test images
+    const GAP: usize = 6;
+
+    /// Draws a screenshot with a board of 6 rows, the first filled with the given colours, and a
+    /// keyboard row beneath
+    fn screenshot(rows: &[&str], dark: bool) -> Vec<u8> {
+        let (background, gray, yellow, green) = if dark {
+            ([0x12, 0x12, 0x13], 3, 4, 5)
+        } else {
+            ([0xff, 0xff, 0xff], 0, 1, 2)
+        };
+
+        let width = 5 * (SIZE + GAP) + 40;
+        let height = 9 * (SIZE + GAP) + 40;
+        let mut pixels = vec![background; width * height];
+
+        let mut fill = |x: usize, y: usize, w: usize, h: usize, colour: [u8; 3]| {
+            for py in y..y + h {
+                for px in x..x + w {
+                    pixels[py * width + px] = colour;
+                }
+            }
+        };
+
+        for row in 0..6 {
+            for col in 0..5 {
+                let x = 20 + col * (SIZE + GAP);
+                let y = 20 + row * (SIZE + GAP);
+
+                match rows.get(row).map(|colours| colours.as_bytes()[col]) {
+                    Some(colour) => {
+                        let tile = match colour {
+                            b'.' => gray,
+                            b'y' => yellow,
+                            _ => green,
+                        };
+
+                        // Tile with a white letter in the middle
+                        fill(x, y, SIZE, SIZE, TILE_COLOURS[tile].0);
+                        fill(x + 12, y + 10, 16, 20, [0xff, 0xff, 0xff]);
+                    }
+                    None => {
+                        // Empty tile border
+                        let border = if dark {
+                            TILE_COLOURS[3].0
+                        } else {
+                            [0xd3, 0xd6, 0xda]
+                        };
+
+                        fill(x, y, SIZE, SIZE, border);
+                        fill(x + 2, y + 2, SIZE - 4, SIZE - 4, background);
+                    }
+                }
+            }
+        }
+
+        // Keyboard row of narrower keys
+        for key in 0..10 {
+            fill(20 + key * 22, height - 60, 18, 40, TILE_COLOURS[gray].0);
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .unwrap()
+            .write_image_data(pixels.concat().as_slice())
+            .unwrap();
+
+        png
+    }
+
+    fn board_text(board: &[BoardRow]) -> Vec<String> {
+        board
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|elem| match elem {
+                        BoardElem::Gray(c) => c.to_ascii_lowercase(),
+                        BoardElem::Yellow(_) => 'y',
+                        BoardElem::Green(_) => 'g',
+                        _ => ' ',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn import() {
+        let words = ["CRANE".to_string(), "TRACE".to_string()];
+
+        for dark in [false, true] {
+            let png = screenshot(&["..y.g", "gyggg"], dark);
+            let board = board_from_png(png.as_slice(), &words).unwrap();
+
+            assert_eq!(board_text(&board), ["cryng", "gyggg"]);
+        }
+    }
+
+    #[test]
+    fn export() {
+        let board = vec![
+            feedback("CRANE", "TRACE"),
+            feedback("TRACE", "TRACE"),
+            [BoardElem::Unknown('S')]
+                .into_iter()
+                .chain([BoardElem::Empty; 4])
+                .collect(),
+        ];
+
+        // Coloured rows read back from the image
+        let words = ["CRANE".to_string(), "TRACE".to_string()];
+        let png = board_to_png(&board[..2]).unwrap();
+
+        assert_eq!(board_from_png(png.as_slice(), &words).unwrap(), board[..2]);
+
+        // Rows without colours are drawn but not imported
+        let png = board_to_png(&board).unwrap();
+
+        assert_eq!(board_from_png(png.as_slice(), &words).unwrap(), board[..2]);
+
+        // Nothing to draw
+        assert!(board_to_png(&[vec![BoardElem::Empty; 5]]).is_err());
+    }
+
+    #[test]
+    fn import_errors() {
+        let png = screenshot(&["..y.g"], true);
+
+        // More words than rows
+        let words = ["CRANE".to_string(), "TRACE".to_string()];
+        assert!(board_from_png(png.as_slice(), &words).is_err());
+
+        // Wrong word length finds no rows
+        let words = ["CRANES".to_string()];
+        assert!(board_from_png(png.as_slice(), &words).is_err());
+
+        // Invalid words
+        let words = ["CR4NE".to_string()];
+        assert!(board_from_png(png.as_slice(), &words).is_err());
+
+        // Not a PNG
+        let words = ["CRANE".to_string()];
+        assert!(board_from_png(&b"not a png"[..], &words).is_err());
+    }
+}
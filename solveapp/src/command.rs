@@ -0,0 +1,179 @@
+//! Board commands, the single entry point for changing the board
+
+use log::info;
+use solver::{BoardRow, BOARD_ROWS};
+
+use crate::{CountBound, SolveApp, TileColour};
+
+/// Change to make to the board, eg. from a front end, script or recording
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoardCommand {
+    /// Type an upper case letter at the cursor
+    Add(char),
+    /// Remove the last letter typed
+    Remove,
+    /// Replace the board with rows, leaving the cursor at the start of the next row
+    Import(Vec<BoardRow>),
+    /// Select the row the column toggles act on
+    SelectRow(usize),
+    /// Toggle a column on the selected row. Green letters need toggling twice in a row
+    ToggleCol(usize),
+    /// Move a cell on to its next colour
+    Toggle {
+        /// Board row
+        row: usize,
+        /// Board column
+        col: usize,
+    },
+    /// Move a cell back to its previous colour
+    ToggleBack {
+        /// Board row
+        row: usize,
+        /// Board column
+        col: usize,
+    },
+    /// Set the colour of a cell
+    SetColour {
+        /// Board row
+        row: usize,
+        /// Board column
+        col: usize,
+        /// New colour
+        colour: TileColour,
+    },
+    /// Clear the colour of a cell so the letter gives no information
+    ClearColour {
+        /// Board row
+        row: usize,
+        /// Board column
+        col: usize,
+    },
+    /// Set the fewest and most times an upper case letter appears, with no most if unlimited
+    SetCount {
+        /// Letter counted
+        letter: char,
+        /// Fewest times the letter appears
+        min: u8,
+        /// Most times the letter appears
+        max: Option<u8>,
+    },
+    /// Move one bound of an upper case letter's count up or down by one
+    StepCount {
+        /// Letter counted
+        letter: char,
+        /// Bound to move
+        bound: CountBound,
+        /// Move the bound up rather than down
+        up: bool,
+    },
+    /// Clear all of the letter counts set by hand
+    ClearCounts,
+}
+
+/// Result of applying a board command
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardEffect {
+    /// The board or letter counts changed, so the words need calculating again
+    Changed,
+    /// Another row was selected for the column toggles
+    Selected,
+    /// The command made no difference, eg. removing a letter from an empty board
+    Unchanged,
+    /// The command was refused, with the reason
+    Rejected(String),
+}
+
+impl BoardEffect {
+    /// Returns true if the command changed the board or the selected row
+    pub fn changed(&self) -> bool {
+        matches!(self, Self::Changed | Self::Selected)
+    }
+}
+
+impl SolveApp {
+    /// Applies a command to the board, returning its effect. Commands outside the board are
+    /// rejected rather than panicking, and each command is logged with its effect
+    pub fn apply(&mut self, command: BoardCommand) -> BoardEffect {
+        let effect = match self.check(&command) {
+            Some(reason) => BoardEffect::Rejected(reason),
+            None => self.run(&command),
+        };
+
+        info!("Board command {command:?}: {effect:?}");
+
+        effect
+    }
+
+    /// Returns the reason a command can't be applied to the board, if any
+    fn check(&self, command: &BoardCommand) -> Option<String> {
+        let cols = self.cols();
+
+        match *command {
+            BoardCommand::Add(letter)
+            | BoardCommand::SetCount { letter, .. }
+            | BoardCommand::StepCount { letter, .. }
+                if !letter.is_ascii_uppercase() =>
+            {
+                Some(format!("{letter:?} is not an upper case letter"))
+            }
+            BoardCommand::Import(ref rows) if rows.len() > BOARD_ROWS => {
+                Some(format!("At most {BOARD_ROWS} rows can be imported"))
+            }
+            BoardCommand::Import(ref rows) if rows.iter().any(|row| row.len() != cols) => {
+                Some(format!("Imported rows must have {cols} columns"))
+            }
+            BoardCommand::SelectRow(row) if row >= BOARD_ROWS => {
+                Some(format!("Row {} is off the board", row + 1))
+            }
+            BoardCommand::ToggleCol(col) if col >= cols => {
+                Some(format!("Column {} is off the board", col + 1))
+            }
+            BoardCommand::Toggle { row, col }
+            | BoardCommand::ToggleBack { row, col }
+            | BoardCommand::SetColour { row, col, .. }
+            | BoardCommand::ClearColour { row, col }
+                if row >= BOARD_ROWS || col >= cols =>
+            {
+                Some(format!(
+                    "Row {} column {} is off the board",
+                    row + 1,
+                    col + 1
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Runs a command which has been checked
+    fn run(&mut self, command: &BoardCommand) -> BoardEffect {
+        self.rejected = None;
+
+        let changed = match *command {
+            BoardCommand::Add(c) => self.add(c),
+            BoardCommand::Remove => self.remove(),
+            BoardCommand::Import(ref rows) => self.import(rows),
+            BoardCommand::SelectRow(row) => {
+                return if self.select_row(row) {
+                    BoardEffect::Selected
+                } else {
+                    BoardEffect::Unchanged
+                };
+            }
+            BoardCommand::ToggleCol(col) => self.toggle_col(col),
+            BoardCommand::Toggle { row, col } => self.toggle(row, col),
+            BoardCommand::ToggleBack { row, col } => self.toggle_back(row, col),
+            BoardCommand::SetColour { row, col, colour } => self.set_colour(row, col, colour),
+            BoardCommand::ClearColour { row, col } => self.clear_colour(row, col),
+            BoardCommand::SetCount { letter, min, max } => self.set_count(letter, min, max),
+            BoardCommand::StepCount { letter, bound, up } => self.step_count(letter, bound, up),
+            BoardCommand::ClearCounts => self.clear_counts(),
+        };
+
+        // Colour changes contradicting the board and green columns toggled once give a reason
+        match (changed, &self.rejected) {
+            (true, _) => BoardEffect::Changed,
+            (false, Some(reason)) => BoardEffect::Rejected(reason.clone()),
+            (false, None) => BoardEffect::Unchanged,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Personal word list additions
+
+use std::fs::{create_dir_all, read_to_string, rename, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::paths::{config_path, data_path};
+
+/// Extra words file name
+const EXTRA_WORDS_FILE: &str = "extra-words.txt";
+
+/// Words added by the user which are missing from the word list
+pub struct ExtraWords;
+
+impl ExtraWords {
+    /// Loads the extra words, returning an empty list if the file can't be read
+    pub fn load() -> Vec<String> {
+        Self::path()
+            .and_then(|path| read_to_string(path).ok())
+            .map(|contents| {
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Appends a word to the extra words file
+    pub fn save(word: &str) -> io::Result<()> {
+        let Some(path) = Self::path() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Unable to determine data directory",
+            ));
+        };
+
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        writeln!(file, "{}", word.to_ascii_lowercase())
+    }
+
+    /// Returns the path to the extra words file, moving it from the configuration directory
+    /// where it used to be kept
+    fn path() -> Option<PathBuf> {
+        let path = data_path(EXTRA_WORDS_FILE)?;
+
+        if let Some(old) = config_path(EXTRA_WORDS_FILE) {
+            if old != path && old.is_file() && !path.exists() {
+                let moved = path
+                    .parent()
+                    .map_or(Ok(()), create_dir_all)
+                    .and_then(|_| rename(&old, &path));
+
+                if moved.is_err() {
+                    // Carry on using the old file
+                    return Some(old);
+                }
+            }
+        }
+
+        Some(path)
+    }
+}
@@ -1,106 +1,616 @@
+use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
+use std::thread::{self, JoinHandle};
+use std::{cmp, io};
+
 use dictionary::{Dictionary, LetterNext};
-use solver::{find_words, SolverArgs};
-pub use solver::{BoardElem, BOARD_COLS, BOARD_ROWS};
+use log::info;
+use numformat::{DurationFormat, NumFormat};
+pub use solver::{
+    build_info,
+    feedback,
+    long_version,
+    BoardElem,
+    BoardRow,
+    Candidates,
+    Contradiction,
+    LetterInformation,
+    Random,
+    ScoreKind,
+    SolveStats,
+    Strategy,
+    BOARD_ROWS,
+    VERSION,
+};
+use solver::{
+    empty_board,
+    filter_words_into,
+    find_words_into,
+    find_words_with,
+    letter_counts,
+    letter_groups,
+    letter_information,
+    partition_sizes,
+    rank_letters_strategy,
+    rank_words_strategy,
+    word_letters,
+    CancelToken,
+    Constraints,
+    GameRules,
+    Pattern,
+    SolverArgs,
+    Standard,
+    WordLetters,
+};
+
+#[cfg(feature = "image")]
+pub mod board_image;
+mod command;
+mod extra_words;
+mod logging;
+#[cfg(feature = "packs")]
+pub mod packs;
+pub mod paths;
+#[cfg(feature = "script")]
+pub mod script;
+mod settings;
+
+pub use command::{BoardCommand, BoardEffect};
+pub use extra_words::ExtraWords;
+pub use logging::init_logging;
+pub use settings::{Settings, ThemeMode};
+
+/// Share of the candidates a letter must appear in to be described as common
+const COMMON_LETTER_SHARE: f32 = 0.25;
 
-/// Found words list
-#[derive(Hash)]
-pub struct Words(Option<Vec<LetterNext>>);
+/// Fewest and most times letters appear, with no most if unlimited
+type LetterCounts = BTreeMap<char, (u8, Option<u8>)>;
+
+/// Found words list, with the word scores if ranking
+#[derive(Default)]
+pub struct Words {
+    /// Dictionary elements of the words found, or None if not calculated
+    elems: Option<Vec<LetterNext>>,
+    /// Score of each word if ranking
+    scores: Option<Vec<f32>>,
+    /// Best and worst scores
+    range: (f32, f32),
+}
 
 impl Words {
+    /// Creates a word list without scores
+    fn new(elems: Vec<LetterNext>) -> Self {
+        Self {
+            elems: Some(elems),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a word list with a score for each word
+    fn ranked(elems: Vec<LetterNext>, scores: Vec<f32>, kind: ScoreKind) -> Self {
+        let best = scores.iter().copied().max_by(|a, b| kind.compare(*a, *b));
+        let worst = scores.iter().copied().min_by(|a, b| kind.compare(*a, *b));
+
+        Self {
+            elems: Some(elems),
+            scores: Some(scores),
+            range: (best.unwrap_or_default(), worst.unwrap_or_default()),
+        }
+    }
+
     /// Get count of words found or None if not calculated
     pub fn count(&self) -> Option<usize> {
-        self.0.as_ref().map(|words| words.len())
+        self.elems.as_ref().map(|words| words.len())
+    }
+
+    /// Get the dictionary element of a word
+    fn elem(&self, elem: usize) -> Option<LetterNext> {
+        self.elems
+            .as_ref()
+            .and_then(|elems| elems.get(elem).copied())
+    }
+
+    /// Get the score of a word if ranking
+    pub fn score(&self, elem: usize) -> Option<f32> {
+        self.scores
+            .as_ref()
+            .and_then(|scores| scores.get(elem).copied())
+    }
+
+    /// Get how good the score of a word is compared to the other words if ranking, from 0 for the
+    /// worst score to 1 for the best
+    pub fn heat(&self, elem: usize) -> Option<f32> {
+        let score = self.score(elem)?;
+        let (best, worst) = self.range;
+
+        if best == worst {
+            Some(1.0)
+        } else {
+            Some((score - worst) / (best - worst))
+        }
+    }
+}
+
+impl Hash for Words {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.elems.hash(state);
+
+        // Scores are hashed by their bits so drawing is redone when the scores change
+        if let Some(scores) = &self.scores {
+            scores.iter().for_each(|score| score.to_bits().hash(state));
+        }
+    }
+}
+
+/// Word list sort order
+/// Words with equal scores are always in alphabetical order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WordSort {
+    /// Alphabetical order
+    Alpha,
+    /// Highest score first
+    Score,
+}
+
+/// Colour of a board letter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileColour {
+    /// Letter not in the word
+    Gray,
+    /// Letter in the word in a different place
+    Yellow,
+    /// Letter in the word in this place
+    Green,
+}
+
+impl TileColour {
+    /// Returns the board element for a letter with the colour
+    pub fn elem(self, c: char) -> BoardElem {
+        match self {
+            Self::Gray => BoardElem::Gray(c),
+            Self::Yellow => BoardElem::Yellow(c),
+            Self::Green => BoardElem::Green(c),
+        }
+    }
+
+    /// Returns the colour of a board element, or None if it is empty or has no colour yet
+    pub fn of(elem: BoardElem) -> Option<Self> {
+        match elem {
+            BoardElem::Gray(_) => Some(Self::Gray),
+            BoardElem::Yellow(_) => Some(Self::Yellow),
+            BoardElem::Green(_) => Some(Self::Green),
+            BoardElem::Empty | BoardElem::Unknown(_) => None,
+        }
     }
 }
 
+/// Bound of a letter count changed by hand
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CountBound {
+    /// Fewest times the letter appears
+    Min,
+    /// Most times the letter appears
+    Max,
+}
+
+/// Words left by each colour pattern a word could be given if it's played next
+#[derive(Clone, Debug)]
+pub struct Preview {
+    /// Word previewed
+    pub word: String,
+    /// Board row for each colour pattern with the number of words it leaves, most words first
+    pub groups: Vec<(BoardRow, usize)>,
+}
+
+impl Preview {
+    /// Returns the most words any colour pattern leaves
+    pub fn worst(&self) -> usize {
+        self.groups.first().map_or(0, |(_, words)| *words)
+    }
+}
+
+/// Solver statistics for the calculation made with a number of completed board rows
+#[derive(Clone, Copy, Debug)]
+pub struct RowStats {
+    /// Number of completed board rows
+    pub row: usize,
+    /// Number of words found
+    pub words: usize,
+    /// The previous words were filtered rather than searching the dictionary
+    pub filtered: bool,
+    /// Solver statistics
+    pub stats: SolveStats,
+}
+
+/// Candidates left by a completed board row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowHistory {
+    /// Board row
+    pub row: usize,
+    /// Word played on the row
+    pub word: String,
+    /// Number of candidates before the row was played
+    pub before: usize,
+    /// Number of candidates left after the row
+    pub after: usize,
+}
+
 /// App holds the state of the application
 pub struct SolveApp {
     /// Current board
-    board: [[BoardElem; BOARD_COLS]; BOARD_ROWS],
+    board: Vec<BoardRow>,
+    /// Board cells filled automatically from known green letters
+    auto: Vec<Vec<bool>>,
+    /// Board cells whose colour was cleared, left without a colour when the next row is started
+    cleared: Vec<Vec<bool>>,
     /// Current row
     row: usize,
     /// Current column
     col: usize,
+    /// Row the column number toggles act on, following the typing unless another row is selected
+    active: usize,
     /// Dictionary
     dictionary: Dictionary,
+    /// Extra words accepted as guesses but never the answer, from a language pack
+    guesses: Option<Dictionary>,
     /// Words
     words: Words,
+    /// Solver statistics
+    stats: Option<SolveStats>,
+    /// Solver statistics for each number of completed rows
+    row_stats: Vec<RowStats>,
+    /// Number of candidates left after each number of completed rows, from no rows up
+    history: Vec<usize>,
+    /// Completed solver board rows and letter counts set by hand the history was counted with
+    history_key: Option<(Vec<BoardRow>, LetterCounts)>,
+    /// Set of words found
+    candidates: Option<Candidates>,
+    /// Constraints the words were found with
+    constraints: Option<Constraints>,
+    /// Ranking enabled
+    ranking: bool,
+    /// Word list sort order
+    sort: WordSort,
+    /// Word ranking strategy
+    strategy: Strategy,
+    /// Fill known green letters when typing a new row
+    lock_greens: bool,
+    /// Mark letters proven absent by earlier rows gray when typed
+    mark_absent: bool,
+    /// Leave rows which aren't dictionary words out of the calculation
+    reject_unknown: bool,
+    /// Reject colour changes which contradict the rest of the board
+    strict: bool,
+    /// Why the last colour change wasn't made
+    rejected: Option<String>,
+    /// Row, column and board generation of a green letter toggled once by column number, which
+    /// is changed if toggled again before anything else changes
+    unlock: Option<(usize, usize, u64)>,
+    /// Count of board edits, used to tell if the words need calculating again
+    generation: u64,
+    /// Board generation the words were last calculated for
+    calculated: Option<u64>,
+    /// Scores from the last ranking
+    rank_cache: Option<RankCache>,
+    /// Rank words on another thread, leaving them unranked until the scores are collected
+    background: bool,
+    /// Ranking running on another thread, if any
+    rank_job: Option<RankJob>,
+    /// Words pinned to the shortlist in the order pinned, kept while they fit the board
+    shortlist: Vec<LetterNext>,
+    /// Search filtering the words shown, in upper case
+    search: Option<String>,
+    /// Keep the words eliminated by the latest edit
+    show_eliminated: bool,
+    /// Words found before the latest edit which no longer fit the board
+    eliminated: Vec<LetterNext>,
+    /// Fewest and most times letters appear, set by hand to narrow the counts from the board
+    counts: LetterCounts,
+}
+
+/// Word scores kept so the words don't need ranking again when only the order or the typed
+/// letters change
+struct RankCache {
+    /// Strategy the words were ranked with
+    strategy: Strategy,
+    /// How the words were scored
+    kind: ScoreKind,
+    /// Hash of the words scored
+    hash: u64,
+    /// Words scored, in alphabetical order
+    words: Vec<LetterNext>,
+    /// Score of each word
+    scores: Vec<f32>,
+}
+
+impl RankCache {
+    /// Hashes a list of words
+    fn hash(words: &[LetterNext]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        words.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Words being ranked on another thread
+struct RankJob {
+    /// Strategy the words are being ranked with
+    strategy: Strategy,
+    /// Hash of the words being scored
+    hash: u64,
+    /// Words being scored, in alphabetical order
+    words: Vec<LetterNext>,
+    /// Token to stop the ranking when the words are no longer wanted
+    cancel: CancelToken,
+    /// Thread scoring the words, returning None if cancelled
+    handle: JoinHandle<Option<(ScoreKind, Vec<f32>)>>,
 }
 
 impl SolveApp {
     /// Creates the application
+    /// The board has a column for each letter of the dictionary's words
     pub fn new(dictionary: Dictionary) -> Self {
         Self {
-            board: [[BoardElem::Empty; BOARD_COLS]; BOARD_ROWS],
+            board: empty_board(dictionary.word_len()),
+            auto: vec![vec![false; dictionary.word_len()]; BOARD_ROWS],
+            cleared: vec![vec![false; dictionary.word_len()]; BOARD_ROWS],
             row: 0,
             col: 0,
+            active: 0,
             dictionary,
-            words: Words(None),
+            guesses: None,
+            words: Words::default(),
+            stats: None,
+            row_stats: Vec::new(),
+            history: Vec::new(),
+            history_key: None,
+            candidates: None,
+            constraints: None,
+            ranking: false,
+            sort: WordSort::Alpha,
+            strategy: Strategy::default(),
+            lock_greens: false,
+            mark_absent: false,
+            reject_unknown: false,
+            strict: false,
+            rejected: None,
+            unlock: None,
+            generation: 0,
+            calculated: None,
+            rank_cache: None,
+            background: false,
+            rank_job: None,
+            shortlist: Vec::new(),
+            search: None,
+            show_eliminated: false,
+            eliminated: Vec::new(),
+            counts: BTreeMap::new(),
         }
     }
 
     /// Add a letter to the board
     pub fn add(&mut self, c: char) -> bool {
+        self.generation += 1;
+
         // Any space left on the board?
         if self.row >= BOARD_ROWS {
             return false;
         }
 
+        // Starting a row confirms the colours of the row before it, so letters without a colour
+        // are not in the solution. Letters whose colour was cleared are left without one
+        if self.col == 0 && self.row > 0 {
+            let row = self.row - 1;
+
+            for (elem, cleared) in self.board[row].iter_mut().zip(&self.cleared[row]) {
+                if let (BoardElem::Unknown(letter), false) = (*elem, cleared) {
+                    *elem = BoardElem::Gray(letter);
+                }
+            }
+        }
+
         // Set board element to the letter
-        // Search through board rows for matching letter in this column and copy if found
+        // Search through board rows for matching letter in this column and copy if found,
+        // otherwise mark gray if known to be absent
         self.board[self.row][self.col] = self
-                    .board
-                    .iter()
-                    .find(|row| matches!(row[self.col], BoardElem::Green(oc) | BoardElem::Yellow(oc) if oc == c))
-                    .map(|row| row[self.col])
-                    .unwrap_or(BoardElem::Gray(c));
+            .board
+            .iter()
+            .find(|row| {
+                matches!(
+                    row[self.col],
+                    BoardElem::Green(oc) | BoardElem::Yellow(oc) if oc == c
+                )
+            })
+            .map(|row| row[self.col])
+            .unwrap_or_else(|| {
+                if self.mark_absent && self.absent(c) {
+                    BoardElem::Gray(c)
+                } else {
+                    BoardElem::Unknown(c)
+                }
+            });
+
+        // Typing selects the row typed on
+        self.active = self.row;
 
         // Move to the next board element
-        self.col += 1;
+        self.advance();
 
-        if self.col == BOARD_COLS {
-            self.col = 0;
-            self.row += 1;
+        // Fill any following columns with known green letters
+        if self.lock_greens {
+            self.fill_greens();
         }
 
         true
     }
 
+    /// Returns true if earlier rows show a letter isn't in the solution, ie. it is gray and never
+    /// yellow or green
+    fn absent(&self, c: char) -> bool {
+        let rows = &self.board[..self.row];
+
+        rows.iter()
+            .flatten()
+            .any(|elem| matches!(elem, BoardElem::Gray(oc) if *oc == c))
+            && !rows.iter().flatten().any(
+                |elem| matches!(elem, BoardElem::Yellow(oc) | BoardElem::Green(oc) if *oc == c),
+            )
+    }
+
     /// Remove last letter from the board
+    /// Letters filled from known green letters are removed along with the letter before them
     pub fn remove(&mut self) -> bool {
-        // Any letters on this row?
-        if self.col > 0 {
-            // Yes - remove it
-            self.col -= 1;
-        } else if self.row > 0 {
-            // No - move to last row
-            self.row -= 1;
-            self.col = BOARD_COLS - 1;
+        self.generation += 1;
+
+        let mut removed = false;
+
+        loop {
+            // Any letters on this row?
+            if self.col > 0 {
+                // Yes - remove it
+                self.col -= 1;
+            } else if self.row > 0 {
+                // No - move to last row
+                self.row -= 1;
+                self.col = self.cols() - 1;
+            } else {
+                // No, and no previous row to move to
+                break;
+            }
+
+            // Set board element to empty
+            self.board[self.row][self.col] = BoardElem::Empty;
+            self.cleared[self.row][self.col] = false;
+            removed = true;
+
+            if !std::mem::take(&mut self.auto[self.row][self.col]) {
+                break;
+            }
+        }
+
+        // Select the row with the last letter left
+        self.active = if self.col == 0 {
+            self.row.saturating_sub(1)
         } else {
-            // No, and no previous row to move to
+            self.row
+        };
+
+        removed
+    }
+
+    /// Moves to the next board element
+    fn advance(&mut self) {
+        self.col += 1;
+
+        if self.col == self.cols() {
+            self.col = 0;
+            self.row += 1;
+        }
+    }
+
+    /// Fills board elements from the current position with green letters found in earlier rows
+    /// Rows are never completely filled
+    fn fill_greens(&mut self) {
+        if self.row >= BOARD_ROWS || self.row == 0 {
+            return;
+        }
+
+        let greens: Vec<Option<char>> = (0..self.cols())
+            .map(|col| {
+                self.board[..self.row]
+                    .iter()
+                    .find_map(|row| match row[col] {
+                        BoardElem::Green(c) => Some(c),
+                        _ => None,
+                    })
+            })
+            .collect();
+
+        if greens.iter().all(Option::is_some) {
+            return;
+        }
+
+        while self.col < self.cols() {
+            let Some(c) = greens[self.col] else {
+                break;
+            };
+
+            self.board[self.row][self.col] = BoardElem::Green(c);
+            self.auto[self.row][self.col] = true;
+
+            self.advance();
+        }
+    }
+
+    /// Replaces the board with imported rows, leaving the cursor at the start of the next row
+    /// Returns false if there are too many rows or a row is the wrong length
+    pub fn import(&mut self, rows: &[BoardRow]) -> bool {
+        let cols = self.cols();
+
+        if rows.len() > BOARD_ROWS || rows.iter().any(|row| row.len() != cols) {
+            return false;
+        }
+
+        self.generation += 1;
+        self.board = empty_board(cols);
+        self.board[..rows.len()].clone_from_slice(rows);
+        self.auto = vec![vec![false; cols]; BOARD_ROWS];
+        self.cleared = vec![vec![false; cols]; BOARD_ROWS];
+        self.row = rows.len();
+        self.col = 0;
+        self.active = rows.len().saturating_sub(1);
+
+        true
+    }
+
+    /// Returns the row the column number toggles act on, or None if the board is empty
+    pub fn active_row(&self) -> Option<usize> {
+        self.board[self.active]
+            .iter()
+            .any(|elem| !matches!(elem, BoardElem::Empty))
+            .then_some(self.active)
+    }
+
+    /// Selects the row for the column number toggles to act on
+    /// Returns false if the row has no letters or is already selected
+    pub fn select_row(&mut self, rownum: usize) -> bool {
+        if rownum == self.active
+            || !self
+                .board
+                .get(rownum)
+                .is_some_and(|row| row.iter().any(|elem| !matches!(elem, BoardElem::Empty)))
+        {
             return false;
         }
 
-        // Set board element to empty
-        self.board[self.row][self.col] = BoardElem::Empty;
+        self.active = rownum;
+        self.unlock = None;
 
         true
     }
 
-    /// Toggle a column on the current row
+    /// Toggle a column on the active row
+    /// Green letters need toggling twice in a row so a mistyped column number doesn't remove a
+    /// known green letter
     pub fn toggle_col(&mut self, colnum: usize) -> bool {
-        let rownum = if colnum >= self.col {
-            if self.row > 0 {
-                Some(self.row - 1)
-            } else {
-                None
-            }
-        } else {
-            Some(self.row)
-        };
+        let rownum = self.active_row();
 
-        if colnum < BOARD_COLS {
+        if colnum < self.cols() {
             if let Some(rownum) = rownum {
+                if matches!(self.board[rownum][colnum], BoardElem::Green(_))
+                    && self.unlock != Some((rownum, colnum, self.generation))
+                {
+                    self.unlock = Some((rownum, colnum, self.generation));
+                    self.rejected = Some(format!(
+                        "Column {} is green, press {} again to change it",
+                        colnum + 1,
+                        colnum + 1
+                    ));
+                    return false;
+                }
+
+                self.unlock = None;
                 self.toggle(rownum, colnum)
             } else {
                 false
@@ -110,96 +620,2516 @@ impl SolveApp {
         }
     }
 
+    /// Returns true if a column has a green letter on any row
+    pub fn col_locked(&self, colnum: usize) -> bool {
+        self.board
+            .iter()
+            .any(|row| matches!(row[colnum], BoardElem::Green(_)))
+    }
+
     /// Toggle a board cell between Gray, Yellow and Green
     pub fn toggle(&mut self, rownum: usize, colnum: usize) -> bool {
-        // Get the character we're toggling
-        if let Some(c) = match self.board[rownum][colnum] {
-            BoardElem::Gray(c) | BoardElem::Yellow(c) | BoardElem::Green(c) => Some(c),
+        match self.next_colour(rownum, colnum) {
+            Some(colour) => self.set_colour(rownum, colnum, colour),
+            None => false,
+        }
+    }
+
+    /// Returns the colour toggling a board cell would set, or None if the cell is empty
+    /// Yellow letters become gray rather than green if the column already has a green letter
+    pub fn next_colour(&self, rownum: usize, colnum: usize) -> Option<TileColour> {
+        match self.board[rownum][colnum] {
+            BoardElem::Unknown(_) | BoardElem::Gray(_) => Some(TileColour::Yellow),
+            BoardElem::Yellow(_) => {
+                if self
+                    .board
+                    .iter()
+                    .any(|row| matches!(row[colnum], BoardElem::Green(_)))
+                {
+                    Some(TileColour::Gray)
+                } else {
+                    Some(TileColour::Green)
+                }
+            }
+            BoardElem::Green(_) => Some(TileColour::Gray),
             BoardElem::Empty => None,
-        } {
-            // Work out what to convert the board element to
-            let new = match self.board[rownum][colnum] {
-                BoardElem::Gray(c) => BoardElem::Yellow(c),
-                BoardElem::Yellow(c) => {
-                    if self
-                        .board
-                        .iter()
-                        .any(|row| matches!(row[colnum], BoardElem::Green(_)))
-                    {
-                        BoardElem::Gray(c)
-                    } else {
-                        BoardElem::Green(c)
-                    }
+        }
+    }
+
+    /// Toggle a board cell between Gray, Yellow and Green in the opposite direction to toggle
+    pub fn toggle_back(&mut self, rownum: usize, colnum: usize) -> bool {
+        match self.prev_colour(rownum, colnum) {
+            Some(colour) => self.set_colour(rownum, colnum, colour),
+            None => false,
+        }
+    }
+
+    /// Returns the colour toggling a board cell backwards would set, or None if the cell is empty
+    /// Gray letters become yellow rather than green if the column already has a green letter
+    pub fn prev_colour(&self, rownum: usize, colnum: usize) -> Option<TileColour> {
+        match self.board[rownum][colnum] {
+            BoardElem::Unknown(_) | BoardElem::Gray(_) => {
+                if self
+                    .board
+                    .iter()
+                    .any(|row| matches!(row[colnum], BoardElem::Green(_)))
+                {
+                    Some(TileColour::Yellow)
+                } else {
+                    Some(TileColour::Green)
                 }
-                BoardElem::Green(c) => BoardElem::Gray(c),
-                BoardElem::Empty => unreachable!(),
-            };
+            }
+            BoardElem::Yellow(_) => Some(TileColour::Gray),
+            BoardElem::Green(_) => Some(TileColour::Yellow),
+            BoardElem::Empty => None,
+        }
+    }
 
-            // Set new board element value on all rows where applicable
-            for (rn, row) in self.board.iter_mut().enumerate() {
-                match row[colnum] {
-                    BoardElem::Gray(oc) | BoardElem::Yellow(oc) | BoardElem::Green(oc)
-                        if oc == c =>
-                    {
-                        // If the letter appears elsewhere on the row, don't set automatically
-                        if rn == rownum
+    /// Sets the colour of a board cell, returning false if the cell is empty or already that
+    /// colour
+    /// The letter is set to the same colour in the same column on other rows unless it appears
+    /// elsewhere on that row
+    pub fn set_colour(&mut self, rownum: usize, colnum: usize, colour: TileColour) -> bool {
+        // Get the character we're setting
+        let c = match self.board[rownum][colnum] {
+            BoardElem::Unknown(c)
+            | BoardElem::Gray(c)
+            | BoardElem::Yellow(c)
+            | BoardElem::Green(c) => c,
+            BoardElem::Empty => return false,
+        };
+
+        if TileColour::of(self.board[rownum][colnum]) == Some(colour) {
+            return false;
+        }
+
+        let new = colour.elem(c);
+
+        // Set new board element value on all rows where applicable
+        let mut board = self.board.clone();
+
+        for (rn, row) in board.iter_mut().enumerate() {
+            match row[colnum] {
+                // If the letter appears elsewhere on the row, don't set automatically
+                BoardElem::Unknown(oc)
+                | BoardElem::Gray(oc)
+                | BoardElem::Yellow(oc)
+                | BoardElem::Green(oc)
+                    if oc == c
+                        && (rn == rownum
                             || !row.iter().enumerate().any(|(cn, elem)| {
                                 cn != colnum
-                                    && matches!(*elem, BoardElem::Yellow(oc) | BoardElem::Green(oc) if oc == c)
-                            })
-                        {
-                            row[colnum] = new;
-                        }
-                    }
-                    _ => (),
+                                    && matches!(
+                                        *elem,
+                                        BoardElem::Yellow(oc) | BoardElem::Green(oc) if oc == c
+                                    )
+                            })) =>
+                {
+                    row[colnum] = new;
                 }
+                _ => (),
             }
+        }
 
-            true
-        } else {
-            false
+        // Check the change doesn't contradict the rest of the board
+        if self.strict {
+            let before = self.solver_constraints(&self.board).validate().err();
+
+            if let Err(contradiction) = self.solver_constraints(&board).validate() {
+                if before != Some(contradiction) {
+                    info!("Rejected colour change: {contradiction}");
+                    self.rejected = Some(contradiction.to_string());
+                    return false;
+                }
+            }
+        }
+
+        self.board = board;
+        self.rejected = None;
+        self.generation += 1;
+
+        true
+    }
+
+    /// Clears the colour of a board cell so the letter gives no information, returning false if
+    /// the cell is empty or has no colour
+    pub fn clear_colour(&mut self, rownum: usize, colnum: usize) -> bool {
+        let elem = &mut self.board[rownum][colnum];
+
+        match *elem {
+            BoardElem::Gray(c) | BoardElem::Yellow(c) | BoardElem::Green(c) => {
+                *elem = BoardElem::Unknown(c);
+                self.cleared[rownum][colnum] = true;
+                self.generation += 1;
+                true
+            }
+            BoardElem::Empty | BoardElem::Unknown(_) => false,
         }
     }
 
     /// Calculate valid words
+    /// If the board hasn't been edited since the last calculation the words are only sorted again
     pub fn calculate(&mut self) {
-        // Wait for at least one complete row
-        if self.row > 0 {
+        self.rejected = None;
+
+        if self.calculated == Some(self.generation) {
+            if self.ranking && self.words.elems.is_some() {
+                self.sort_words();
+            }
+
+            return;
+        }
+
+        self.calculated = Some(self.generation);
+
+        // Scores being found for the previous board are no longer wanted
+        self.cancel_ranking();
+
+        let (board, rejected) = self.solver_board();
+
+        // Wait for at least one complete row, unless letter counts have been set by hand
+        if self.row > rejected || !self.counts.is_empty() {
             // Create solver arguments
             let args = SolverArgs {
-                board: &self.board,
+                board: &board,
                 dictionary: &self.dictionary,
+                rules: &Standard,
                 debug: false,
+                cancel: None,
+            };
+
+            let constraints = self.solver_constraints(&board);
+
+            // Reuse the previous word list's allocation for the results
+            let buffer = self.words.elems.take().unwrap_or_default();
+
+            // If the constraints have only been tightened filter the previous words, otherwise
+            // search the dictionary
+            let (result, filtered) = match (&self.candidates, &self.constraints) {
+                (Some(candidates), Some(prev)) if constraints.implies(prev) => (
+                    filter_words_into(args, &constraints, candidates, buffer),
+                    true,
+                ),
+                _ => (find_words_into(args, &constraints, buffer), false),
+            };
+
+            let method = if filtered { "filtered" } else { "searched" };
+
+            let words = result.words;
+
+            self.update_history(&board, words.len());
+
+            self.stats = Some(result.stats);
+
+            // Replace the statistics for this and any later rows
+            self.row_stats.retain(|stats| stats.row < self.row);
+            self.row_stats.push(RowStats {
+                row: self.row,
+                words: words.len(),
+                filtered,
+                stats: result.stats,
+            });
+            // Unpin words which no longer fit the board
+            self.shortlist.retain(|elem| {
+                let keep = result.candidates.contains(*elem);
+
+                if !keep {
+                    info!("Unpinned {}", self.dictionary.get_word(*elem as usize));
+                }
+
+                keep
+            });
+
+            // Keep the words the edit eliminated if wanted
+            self.eliminated = match &self.candidates {
+                Some(prev) if self.show_eliminated => prev
+                    .iter()
+                    .filter(|elem| !result.candidates.contains(*elem))
+                    .collect(),
+                _ => Vec::new(),
             };
 
-            // Save the word list
-            self.words = Words(Some(find_words(args)));
+            self.candidates = Some(result.candidates);
+            self.constraints = Some(constraints);
+
+            info!(
+                "Calculated ({method}) {} words in {:?} ({} nodes) for board {}",
+                words.len(),
+                result.stats.elapsed,
+                result.stats.nodes,
+                self.board_text()
+            );
+
+            if self.ranking {
+                // Score the words, unless the same words were scored last time
+                let hash = RankCache::hash(&words);
+
+                let (kind, scores) = match self.rank_cache.take() {
+                    Some(cache)
+                        if cache.strategy == self.strategy
+                            && cache.hash == hash
+                            && cache.words == words =>
+                    {
+                        info!("Reusing scores for {} words", words.len());
+                        (cache.kind, cache.scores)
+                    }
+                    _ if self.background => {
+                        // Show the words unranked until the scores are collected
+                        self.start_ranking(hash, &words);
+                        self.words = Words::new(words);
+                        return;
+                    }
+                    _ => rank_words_strategy(
+                        &self.dictionary,
+                        &words,
+                        self.strategy,
+                        &CancelToken::new(),
+                    )
+                    .expect("ranking isn't cancelled"),
+                };
+
+                self.rank_cache = Some(RankCache {
+                    strategy: self.strategy,
+                    kind,
+                    hash,
+                    words,
+                    scores,
+                });
+
+                self.sort_words();
+            } else {
+                // Save the word list
+                self.words = Words::new(words);
+            }
         } else {
             // Word list should be empty
-            self.words = Words(None);
+            self.clear_words();
+            self.row_stats.clear();
         }
     }
 
-    /// Get reference to the board
-    pub fn board(&self) -> &[[BoardElem; BOARD_COLS]; BOARD_ROWS] {
-        &self.board
+    /// Starts scoring the words on another thread
+    fn start_ranking(&mut self, hash: u64, words: &[LetterNext]) {
+        let letters: Vec<WordLetters> = words
+            .iter()
+            .map(|elem| word_letters(&self.dictionary, *elem))
+            .collect();
+
+        let strategy = self.strategy;
+        let cancel = CancelToken::new();
+        let token = cancel.clone();
+
+        info!("Ranking {} words in the background", words.len());
+
+        let handle = thread::spawn(move || rank_letters_strategy(&letters, strategy, &token));
+
+        self.rank_job = Some(RankJob {
+            strategy,
+            hash,
+            words: words.to_vec(),
+            cancel,
+            handle,
+        });
     }
 
-    /// Get reference to the words
-    pub fn words(&self) -> &Words {
-        &self.words
+    /// Collects the scores from a finished background ranking and sorts the words by them.
+    /// Returns true if the word list has changed
+    pub fn poll_ranking(&mut self) -> bool {
+        if !self
+            .rank_job
+            .as_ref()
+            .is_some_and(|job| job.handle.is_finished())
+        {
+            return false;
+        }
+
+        let job = self.rank_job.take().expect("ranking job is running");
+
+        let Ok(Some((kind, scores))) = job.handle.join() else {
+            return false;
+        };
+
+        info!("Ranked {} words in the background", job.words.len());
+
+        self.rank_cache = Some(RankCache {
+            strategy: job.strategy,
+            kind,
+            hash: job.hash,
+            words: job.words,
+            scores,
+        });
+
+        self.sort_words();
+
+        true
     }
 
-    /// Get word list word
-    pub fn get_word(&self, elem: usize) -> Option<String> {
-        if let Some(words) = &self.words.0 {
-            if elem < words.len() {
-                Some(self.dictionary.get_word(words[elem] as usize))
-            } else {
-                None
+    /// Stops any background ranking. The words are left unranked until calculated again
+    pub fn cancel_ranking(&mut self) {
+        if let Some(job) = self.rank_job.take() {
+            info!("Cancelled ranking {} words", job.words.len());
+            job.cancel.cancel();
+        }
+    }
+
+    /// Returns true if words are being ranked in the background
+    pub fn ranking_pending(&self) -> bool {
+        self.rank_job.is_some()
+    }
+
+    /// Rank words on another thread rather than while calculating. The scores are collected by
+    /// polling
+    pub fn set_background(&mut self, background: bool) {
+        self.background = background;
+    }
+
+    /// Counts the candidates left after each number of completed rows of the solver board,
+    /// ending with the words just found. Counts for rows unchanged since the last calculation are
+    /// kept
+    fn update_history(&mut self, board: &[BoardRow], words: usize) {
+        let key = (board[..self.row].to_vec(), self.counts.clone());
+
+        // Keep the counts for the rows which haven't changed, unless the letter counts have
+        let same = match &self.history_key {
+            Some((rows, counts)) if *counts == key.1 => {
+                rows.iter().zip(&key.0).take_while(|(a, b)| a == b).count() + 1
+            }
+            _ => 0,
+        };
+
+        self.history.truncate(same.min(self.row));
+        self.history_key = None;
+
+        // Count the candidates for each missing number of rows
+        while self.history.len() < self.row {
+            let mut rows = empty_board(self.cols());
+            rows[..self.history.len()].clone_from_slice(&board[..self.history.len()]);
+
+            let args = SolverArgs {
+                board: &rows,
+                dictionary: &self.dictionary,
+                rules: &Standard,
+                debug: false,
+                cancel: None,
+            };
+
+            let result = find_words_with(args, &self.solver_constraints(&rows));
+
+            self.history.push(result.words.len());
+        }
+
+        self.history.push(words);
+        self.history_key = Some(key);
+    }
+
+    /// Returns the board the words are found with, where rows rejected as unknown words are left
+    /// empty, and the number of rows rejected
+    fn solver_board(&self) -> (Vec<BoardRow>, usize) {
+        let mut board = self.board.clone();
+        let mut rejected = 0;
+
+        for (rownum, row) in board.iter_mut().enumerate().take(self.row) {
+            if self.reject_unknown && self.row_valid(rownum) == Some(false) {
+                *row = vec![BoardElem::Empty; self.cols()];
+                rejected += 1;
             }
-        } else {
-            None
         }
+
+        (board, rejected)
+    }
+
+    /// Builds the constraints on the words from the solver board, narrowed by the letter counts
+    /// set by hand
+    fn solver_constraints(&self, board: &[BoardRow]) -> Constraints {
+        let mut constraints = Standard.constraints(board, self.cols());
+
+        for (letter, (min, max)) in &self.counts {
+            constraints.restrict_count(*letter, *min, *max);
+        }
+
+        constraints
+    }
+
+    /// Sets the word list and scores from the last ranking in the current sort order
+    fn sort_words(&mut self) {
+        let Some(cache) = &self.rank_cache else {
+            return;
+        };
+
+        let mut order: Vec<usize> = (0..cache.words.len()).collect();
+
+        if self.sort == WordSort::Score {
+            // Sort words by best score. The sort is stable so equal scores stay in alphabetical
+            // order
+            order.sort_by(|a, b| cache.kind.compare(cache.scores[*b], cache.scores[*a]));
+        }
+
+        // Refill the current lists rather than allocating new ones
+        let mut elems = self.words.elems.take().unwrap_or_default();
+        let mut scores = self.words.scores.take().unwrap_or_default();
+
+        elems.clear();
+        elems.extend(order.iter().map(|i| cache.words[*i]));
+        scores.clear();
+        scores.extend(order.iter().map(|i| cache.scores[*i]));
+
+        self.words = Words::ranked(elems, scores, cache.kind);
+    }
+
+    /// Clears the word list and the state it was calculated from
+    fn clear_words(&mut self) {
+        self.words = Words::default();
+        self.stats = None;
+        self.candidates = None;
+        self.eliminated.clear();
+        self.constraints = None;
+        self.calculated = None;
+    }
+
+    /// Enable or disable word ranking
+    pub fn set_ranking(&mut self, ranking: bool) {
+        self.generation += 1;
+        self.ranking = ranking;
+    }
+
+    /// Set the word ranking strategy
+    pub fn set_strategy(&mut self, strategy: Strategy) {
+        self.generation += 1;
+        self.strategy = strategy;
+    }
+
+    /// Get the word ranking strategy
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Returns how the words were scored by the last ranking, if ranking
+    pub fn score_kind(&self) -> Option<ScoreKind> {
+        self.words
+            .scores
+            .as_ref()
+            .and(self.rank_cache.as_ref())
+            .map(|cache| cache.kind)
+    }
+
+    /// Returns the fewest and most times an upper case letter appears in the words fitting the
+    /// board, with no most if there is no limit. Counts set by hand are included
+    pub fn letter_count(&self, letter: char) -> (u8, Option<u8>) {
+        let (board, _) = self.solver_board();
+
+        self.solver_constraints(&board).count(letter)
+    }
+
+    /// Returns the letter count set by hand for an upper case letter
+    pub fn count_set(&self, letter: char) -> Option<(u8, Option<u8>)> {
+        self.counts.get(&letter).copied()
+    }
+
+    /// Sets the fewest and most times an upper case letter appears, narrowing the count from
+    /// the board. A count of zero or more clears it. Returns true if the count was changed
+    pub fn set_count(&mut self, letter: char, min: u8, max: Option<u8>) -> bool {
+        if !letter.is_ascii_uppercase() {
+            return false;
+        }
+
+        let old = if (min, max) == (0, None) {
+            self.counts.remove(&letter)
+        } else {
+            self.counts.insert(letter, (min, max))
+        };
+
+        if old == self.count_set(letter) {
+            return false;
+        }
+
+        self.generation += 1;
+
+        true
+    }
+
+    /// Moves one bound of an upper case letter's count up or down by one from the count the
+    /// words were found with, keeping the other bound set by hand. A most of the word length or
+    /// more is no limit. Returns true if the count was changed
+    pub fn step_count(&mut self, letter: char, bound: CountBound, up: bool) -> bool {
+        let cols = self.cols() as u8;
+
+        let (min, max) = self.letter_count(letter);
+        let (set_min, set_max) = self.count_set(letter).unwrap_or((0, None));
+
+        let (min, max) = match (bound, up) {
+            (CountBound::Min, true) => (cmp::min(min + 1, cols), set_max),
+            (CountBound::Min, false) => (min.saturating_sub(1), set_max),
+            (CountBound::Max, true) => (set_min, max.map(|max| max + 1).filter(|max| *max < cols)),
+            (CountBound::Max, false) => (set_min, Some(max.unwrap_or(cols).saturating_sub(1))),
+        };
+
+        self.set_count(letter, min, max)
+    }
+
+    /// Clears all of the letter counts set by hand. Returns true if there were any
+    pub fn clear_counts(&mut self) -> bool {
+        if self.counts.is_empty() {
+            return false;
+        }
+
+        self.counts.clear();
+        self.generation += 1;
+
+        true
+    }
+
+    /// Enable or disable filling known green letters when typing a new row
+    pub fn set_lock_greens(&mut self, lock_greens: bool) {
+        self.lock_greens = lock_greens;
+    }
+
+    /// Returns true if known green letters are filled when typing a new row
+    pub fn lock_greens(&self) -> bool {
+        self.lock_greens
+    }
+
+    /// Enable or disable marking letters proven absent by earlier rows gray when typed
+    pub fn set_mark_absent(&mut self, mark_absent: bool) {
+        self.mark_absent = mark_absent;
+    }
+
+    /// Returns true if letters proven absent by earlier rows are marked gray when typed
+    pub fn mark_absent(&self) -> bool {
+        self.mark_absent
+    }
+
+    /// Enable or disable leaving rows which aren't dictionary words out of the calculation
+    pub fn set_reject_unknown(&mut self, reject_unknown: bool) {
+        self.generation += 1;
+        self.reject_unknown = reject_unknown;
+    }
+
+    /// Returns true if rows which aren't dictionary words are left out of the calculation
+    pub fn reject_unknown(&self) -> bool {
+        self.reject_unknown
+    }
+
+    /// Enable or disable rejecting colour changes which contradict the rest of the board
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns true if colour changes which contradict the rest of the board are rejected
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Enable or disable keeping the words eliminated by the latest edit
+    pub fn set_show_eliminated(&mut self, show_eliminated: bool) {
+        self.show_eliminated = show_eliminated;
+
+        if !show_eliminated {
+            self.eliminated.clear();
+        }
+    }
+
+    /// Returns true if the words eliminated by the latest edit are kept
+    pub fn show_eliminated(&self) -> bool {
+        self.show_eliminated
+    }
+
+    /// Returns the words found before the latest edit which no longer fit the board, in
+    /// alphabetical order, leaving out words not matching the search. Empty unless enabled with
+    /// set_show_eliminated
+    pub fn eliminated(&self) -> Vec<String> {
+        self.eliminated
+            .iter()
+            .map(|elem| self.dictionary.get_word(*elem as usize))
+            .filter(|word| match &self.search {
+                Some(search) => search_range(word, search).is_some(),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Returns why the last colour change wasn't made, because it contradicted the board in strict
+    /// mode or needs confirming, until the board is next changed or calculated
+    pub fn rejected(&self) -> Option<&str> {
+        self.rejected.as_deref()
+    }
+
+    /// Returns true if a completed board row is a dictionary word or an accepted guess, or None
+    /// if the row isn't complete
+    pub fn row_valid(&self, row: usize) -> Option<bool> {
+        self.row_word(row).map(|word| {
+            self.dictionary.contains(&word)
+                || self
+                    .guesses
+                    .as_ref()
+                    .is_some_and(|guesses| guesses.contains(&word))
+        })
+    }
+
+    /// Returns the word in a completed board row, or None if the row isn't complete
+    fn row_word(&self, row: usize) -> Option<String> {
+        if row < self.row {
+            Some(
+                self.board[row]
+                    .iter()
+                    .map(|elem| match elem {
+                        BoardElem::Unknown(c)
+                        | BoardElem::Gray(c)
+                        | BoardElem::Yellow(c)
+                        | BoardElem::Green(c) => *c,
+                        BoardElem::Empty => ' ',
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Returns the most recent completed board row which isn't a dictionary word
+    pub fn unknown_word(&self) -> Option<String> {
+        (0..self.row)
+            .rev()
+            .find(|row| self.row_valid(*row) == Some(false))
+            .and_then(|row| self.row_word(row))
+    }
+
+    /// Sets the words accepted as guesses as well as the dictionary words, such as the guesses
+    /// in a language pack. These are never found as the answer
+    pub fn set_guesses(&mut self, guesses: Dictionary) {
+        self.guesses = Some(guesses);
+
+        // Rows left out as unknown words may be accepted now
+        self.generation += 1;
+    }
+
+    /// Adds words to the dictionary, rebuilding it
+    pub fn add_words(&mut self, words: &[String]) -> io::Result<()> {
+        if words.is_empty() {
+            return Ok(());
+        }
+
+        self.dictionary = self.dictionary.with_words(words)?;
+
+        // Dictionary elements have changed so the next calculation must search and rank again
+        self.generation += 1;
+        self.candidates = None;
+        self.constraints = None;
+        self.rank_cache = None;
+
+        Ok(())
+    }
+
+    /// Adds the most recent unknown word on the board to the dictionary and saves it to the
+    /// extra words file. Returns the word added, if any
+    pub fn learn_unknown_word(&mut self) -> io::Result<Option<String>> {
+        let Some(word) = self.unknown_word() else {
+            return Ok(None);
+        };
+
+        ExtraWords::save(&word)?;
+        self.add_words(std::slice::from_ref(&word))?;
+
+        info!("Added {word} to the extra words");
+
+        Ok(Some(word))
+    }
+
+    /// Returns true if a board cell was filled from a known green letter
+    pub fn is_auto(&self, row: usize, col: usize) -> bool {
+        self.auto[row][col]
+    }
+
+    /// Get reference to the board cells filled from known green letters
+    pub fn auto(&self) -> &Vec<Vec<bool>> {
+        &self.auto
+    }
+
+    /// Returns true if word ranking is enabled
+    pub fn ranking(&self) -> bool {
+        self.ranking
+    }
+
+    /// Set the word list sort order
+    pub fn set_sort(&mut self, sort: WordSort) {
+        self.sort = sort;
+    }
+
+    /// Get the word list sort order
+    pub fn sort(&self) -> WordSort {
+        self.sort
+    }
+
+    /// Returns the completed and partial board rows as text for logging
+    /// Each row is the row letters followed by the colours (? = unknown, . = gray, y = yellow,
+    /// g = green)
+    fn board_text(&self) -> String {
+        self.board
+            .iter()
+            .filter(|row| !matches!(row[0], BoardElem::Empty))
+            .map(|row| {
+                let (letters, colours): (String, String) = row
+                    .iter()
+                    .map(|elem| match elem {
+                        BoardElem::Empty => (' ', ' '),
+                        BoardElem::Unknown(c) => (*c, '?'),
+                        BoardElem::Gray(c) => (*c, '.'),
+                        BoardElem::Yellow(c) => (*c, 'y'),
+                        BoardElem::Green(c) => (*c, 'g'),
+                    })
+                    .unzip();
+
+                format!("{}:{}", letters.trim_end(), colours.trim_end())
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Get reference to the board
+    pub fn board(&self) -> &Vec<BoardRow> {
+        &self.board
+    }
+
+    /// Get the number of board columns (the dictionary word length)
+    pub fn cols(&self) -> usize {
+        self.dictionary.word_len()
+    }
+
+    /// Get the letters used by the dictionary words, in alphabetical order
+    pub fn alphabet(&self) -> Vec<char> {
+        let used = self.dictionary.letters_used();
+
+        ('A'..='Z')
+            .filter(|c| used[Dictionary::uchar_to_usize(*c)])
+            .collect()
+    }
+
+    /// Get reference to the words
+    pub fn words(&self) -> &Words {
+        &self.words
+    }
+
+    /// Get the set of words found
+    pub fn candidates(&self) -> Option<&Candidates> {
+        self.candidates.as_ref()
+    }
+
+    /// Get the letters which may appear in a board column, indexed by letter number, from the
+    /// last calculation
+    pub fn allowed_letters(&self, col: usize) -> Option<[bool; 26]> {
+        match &self.constraints {
+            Some(constraints) if col < self.cols() => Some(constraints.allowed_letters(col)),
+            _ => None,
+        }
+    }
+
+    /// Get the statistics from the last calculation
+    pub fn stats(&self) -> Option<SolveStats> {
+        self.stats
+    }
+
+    /// Get the statistics from the calculation for each number of completed rows
+    pub fn row_stats(&self) -> &[RowStats] {
+        &self.row_stats
+    }
+
+    /// Get the number of candidates before and after each completed board row, or nothing if
+    /// the words haven't been calculated
+    pub fn row_history(&self) -> Vec<RowHistory> {
+        if self.words.elems.is_none() {
+            return Vec::new();
+        }
+
+        self.history
+            .windows(2)
+            .enumerate()
+            .map(|(row, counts)| RowHistory {
+                row,
+                word: self.board[row]
+                    .iter()
+                    .filter_map(|elem| match elem {
+                        BoardElem::Empty => None,
+                        BoardElem::Unknown(c)
+                        | BoardElem::Gray(c)
+                        | BoardElem::Yellow(c)
+                        | BoardElem::Green(c) => Some(*c),
+                    })
+                    .collect(),
+                before: counts[0],
+                after: counts[1],
+            })
+            .collect()
+    }
+
+    /// Get the statistics from the last calculation as display text
+    pub fn stats_text(&self) -> Option<String> {
+        match (self.words.count(), self.stats) {
+            (Some(count), Some(stats)) => Some(format!(
+                "{} candidates in {}, {} nodes",
+                count.num_format(),
+                stats.elapsed.dur_format(),
+                stats.nodes.num_format()
+            )),
+            _ => None,
+        }
+    }
+
+    /// Get the word list description and size as display text
+    pub fn word_list_text(&self) -> String {
+        format!(
+            "{}, {} words",
+            self.dictionary.metadata(),
+            self.dictionary.word_count().num_format()
+        )
+    }
+
+    /// Get the status bar text, with the statistics from the last calculation and why the board
+    /// can't be solved if any
+    pub fn status_text(&self) -> String {
+        let text = match self.stats_text() {
+            Some(stats) => format!("{stats} - {}", self.word_list_text()),
+            None => self.word_list_text(),
+        };
+
+        match self.contradiction() {
+            Some(contradiction) => format!("{contradiction} - {text}"),
+            None => text,
+        }
+    }
+
+    /// Returns the rows of the board with letters as lines of tiles in ANSI colours, for pasting
+    /// into a terminal
+    pub fn board_ansi(&self) -> String {
+        self.board
+            .iter()
+            .filter(|row| row.iter().any(|elem| *elem != BoardElem::Empty))
+            .map(|row| {
+                let tiles: Vec<String> = row
+                    .iter()
+                    .map(|elem| {
+                        let letter = match elem {
+                            BoardElem::Empty => ' ',
+                            BoardElem::Unknown(c)
+                            | BoardElem::Gray(c)
+                            | BoardElem::Yellow(c)
+                            | BoardElem::Green(c) => *c,
+                        };
+
+                        match tile_rgb(elem) {
+                            Some([r, g, b]) => {
+                                format!("\x1b[1;97;48;2;{r};{g};{b}m {letter} \x1b[0m")
+                            }
+                            None => format!("\x1b[1m {letter} \x1b[0m"),
+                        }
+                    })
+                    .collect();
+
+                format!("{}\n", tiles.join(" "))
+            })
+            .collect()
+    }
+
+    /// Returns why no word can fit the board as last calculated, if the colours contradict each
+    /// other
+    pub fn contradiction(&self) -> Option<Contradiction> {
+        self.constraints.as_ref()?.validate().err()
+    }
+
+    /// Get word list word
+    pub fn get_word(&self, elem: usize) -> Option<String> {
+        if let Some(words) = &self.words.elems {
+            if elem < words.len() {
+                Some(self.dictionary.get_word(words[elem] as usize))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get word list word score if ranking
+    pub fn get_score(&self, elem: usize) -> Option<f32> {
+        self.words.score(elem)
+    }
+
+    /// Get how good a word list word's score is from 0 (the worst) to 1 (the best) if ranking
+    pub fn get_heat(&self, elem: usize) -> Option<f32> {
+        self.words.heat(elem)
+    }
+
+    /// Groups the found words by their first letter, returning the letters in alphabetical order
+    /// with the word list indexes of their words
+    pub fn word_groups(&self) -> Vec<(char, Vec<usize>)> {
+        let shown = self.shown_words();
+
+        let elems: Vec<LetterNext> = shown
+            .iter()
+            .filter_map(|elem| self.words.elem(*elem))
+            .collect();
+
+        letter_groups(&self.dictionary, &elems)
+            .into_iter()
+            .map(|(c, group)| (c, group.into_iter().map(|i| shown[i]).collect()))
+            .collect()
+    }
+
+    /// Sets the search filtering the words shown, or clears it if empty. The search matches part
+    /// of a word, with '?', '_' or '.' matching any letter. The board constraints aren't changed
+    pub fn set_search(&mut self, search: &str) {
+        self.search = (!search.is_empty()).then(|| search.to_ascii_uppercase());
+    }
+
+    /// Returns the search filtering the words shown
+    pub fn search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
+
+    /// Returns the letters of a word list word matched by the search, or None if the search
+    /// doesn't match or there is no search
+    pub fn search_match(&self, elem: usize) -> Option<Range<usize>> {
+        search_range(&self.get_word(elem)?, self.search.as_ref()?)
+    }
+
+    /// Returns the word list indexes of the words shown, which are the words matching the search
+    /// if there is one
+    pub fn shown_words(&self) -> Vec<usize> {
+        let words = 0..self.words.count().unwrap_or_default();
+
+        match self.search {
+            Some(_) => words
+                .filter(|elem| self.search_match(*elem).is_some())
+                .collect(),
+            None => words.collect(),
+        }
+    }
+
+    /// Pins a word list word to the shortlist, or unpins it if already pinned
+    /// Returns true if the word is now pinned, or None if there is no such word
+    pub fn toggle_pin(&mut self, elem: usize) -> Option<bool> {
+        let elem = self.words.elem(elem)?;
+
+        match self.shortlist.iter().position(|pinned| *pinned == elem) {
+            Some(pos) => {
+                self.shortlist.remove(pos);
+                Some(false)
+            }
+            None => {
+                self.shortlist.push(elem);
+                Some(true)
+            }
+        }
+    }
+
+    /// Returns true if a word list word is pinned to the shortlist
+    pub fn is_pinned(&self, elem: usize) -> bool {
+        self.words
+            .elem(elem)
+            .is_some_and(|elem| self.shortlist.contains(&elem))
+    }
+
+    /// Get the shortlisted words in the order they were pinned
+    pub fn shortlist(&self) -> Vec<String> {
+        self.shortlist
+            .iter()
+            .map(|elem| self.dictionary.get_word(*elem as usize))
+            .collect()
+    }
+
+    /// Previews the words left by each colour pattern if a word list word is played next
+    pub fn preview(&self, elem: usize) -> Option<Preview> {
+        let elems = self.words.elems.as_ref()?;
+        let guess = word_letters(&self.dictionary, *elems.get(elem)?);
+
+        let answers: Vec<WordLetters> = elems
+            .iter()
+            .map(|elem| word_letters(&self.dictionary, *elem))
+            .collect();
+        let set: Vec<usize> = (0..answers.len()).collect();
+
+        // Build the board row for each pattern. Each column is a base 3 digit (0 = gray,
+        // 1 = yellow, 2 = green)
+        let mut groups: Vec<(BoardRow, usize)> = partition_sizes(&answers, &set, &guess)
+            .into_iter()
+            .map(|(pattern, words)| {
+                let row = guess
+                    .iter()
+                    .enumerate()
+                    .map(|(col, letter)| {
+                        let c = (b'A' + letter) as char;
+
+                        match (pattern / Pattern::pow(3, col as u32)) % 3 {
+                            0 => BoardElem::Gray(c),
+                            1 => BoardElem::Yellow(c),
+                            _ => BoardElem::Green(c),
+                        }
+                    })
+                    .collect();
+
+                (row, words)
+            })
+            .collect();
+
+        // Most words first. The sort is stable so ties stay in pattern order
+        groups.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Some(Preview {
+            word: guess.iter().map(|letter| (b'A' + letter) as char).collect(),
+            groups,
+        })
+    }
+
+    /// Get the word list index of the best scoring word if ranking
+    pub fn best_word(&self) -> Option<usize> {
+        let kind = self.score_kind()?;
+
+        self.words.scores.as_ref().and_then(|scores| {
+            scores
+                .iter()
+                .enumerate()
+                .max_by(|(ia, a), (ib, b)| kind.compare(**a, **b).then(ib.cmp(ia)))
+                .map(|(i, _)| i)
+        })
+    }
+
+    /// Explains the best scoring word in a sentence, from the letters it tests which aren't
+    /// coloured on the board yet and the candidates each colour pattern it can be given leaves
+    pub fn explain_best(&self) -> Option<String> {
+        let preview = self.preview(self.best_word()?)?;
+        let elems = self.words.elems.as_ref()?;
+
+        if elems.len() == 1 {
+            return Some(format!("{} is the only candidate", preview.word));
+        }
+
+        let tested = self.tested_letters();
+
+        // New letters in the word, and how many are in a good share of the candidates
+        let counts = letter_counts(&self.dictionary, elems);
+        let common = elems.len() as f32 * COMMON_LETTER_SHARE;
+
+        let mut new: Vec<char> = preview
+            .word
+            .chars()
+            .filter(|c| !tested.contains(c))
+            .collect();
+
+        new.sort();
+        new.dedup();
+
+        let new_common = new
+            .iter()
+            .filter(|c| counts[(**c as u8 - b'A') as usize] as f32 >= common)
+            .count();
+
+        let letters = match (new.len(), new_common) {
+            (0, _) => "tests no new letters".to_string(),
+            (1, 1) => "tests 1 new common letter".to_string(),
+            (1, 0) => "tests 1 new uncommon letter".to_string(),
+            (n, c) if n == c => format!("tests {n} new common letters"),
+            (n, 0) => format!("tests {n} new uncommon letters"),
+            (n, c) => format!("tests {n} new letters, {c} common,"),
+        };
+
+        // Each candidate is equally likely to be the answer, so a pattern leaving n candidates
+        // is given n times
+        let average = preview
+            .groups
+            .iter()
+            .map(|(_, words)| words * words)
+            .sum::<usize>() as f32
+            / elems.len() as f32;
+
+        Some(format!(
+            "{} {letters} and on average leaves {average:.1} candidates; worst case {}",
+            preview.word,
+            preview.worst()
+        ))
+    }
+
+    /// Get the information each letter not coloured on the board yet would give about the
+    /// candidates if probed, most information first. Letters giving none are left out
+    pub fn letter_information(&self) -> Vec<LetterInformation> {
+        let Some(elems) = &self.words.elems else {
+            return Vec::new();
+        };
+
+        let tested = self.tested_letters();
+
+        let mut info: Vec<LetterInformation> = letter_information(&self.dictionary, elems)
+            .into_iter()
+            .filter(|info| info.bits > 0.0 && !tested.contains(&info.letter))
+            .collect();
+
+        // The sort is stable so ties stay in letter order
+        info.sort_by(|a, b| b.bits.total_cmp(&a.bits));
+
+        info
+    }
+
+    /// Returns the letters with a colour on the board
+    fn tested_letters(&self) -> Vec<char> {
+        self.board
+            .iter()
+            .flatten()
+            .filter_map(|elem| match elem {
+                BoardElem::Gray(c) | BoardElem::Yellow(c) | BoardElem::Green(c) => Some(*c),
+                BoardElem::Empty | BoardElem::Unknown(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns the colour of a tile in the game's light theme, or None if it has no colour
+pub(crate) fn tile_rgb(elem: &BoardElem) -> Option<[u8; 3]> {
+    match elem {
+        BoardElem::Gray(_) => Some([0x78, 0x7c, 0x7e]),
+        BoardElem::Yellow(_) => Some([0xc9, 0xb4, 0x58]),
+        BoardElem::Green(_) => Some([0x6a, 0xaa, 0x64]),
+        BoardElem::Empty | BoardElem::Unknown(_) => None,
+    }
+}
+
+/// Finds the first part of a word matching a search, with '?', '_' or '.' matching any letter
+fn search_range(word: &str, search: &str) -> Option<Range<usize>> {
+    let (word, search) = (word.as_bytes(), search.as_bytes());
+
+    (0..(word.len() + 1).checked_sub(search.len())?)
+        .find(|start| {
+            word[*start..]
+                .iter()
+                .zip(search)
+                .all(|(w, s)| matches!(s, b'?' | b'_' | b'.') || w == s)
+        })
+        .map(|start| start..start + search.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::WordCase;
+
+    use super::*;
+
+    fn app_words(app: &SolveApp) -> Vec<String> {
+        (0..app.words().count().unwrap_or(0))
+            .map(|i| app.get_word(i).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn word_order() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        // Alphabetical by default
+        app.calculate();
+        assert_eq!(
+            app_words(&app),
+            ["BRAKE", "CRANE", "CRANK", "DRANK", "PRANK"]
+        );
+
+        // Alphabetical when ranking and sorting alphabetically
+        app.set_ranking(true);
+        app.calculate();
+        assert_eq!(
+            app_words(&app),
+            ["BRAKE", "CRANE", "CRANK", "DRANK", "PRANK"]
+        );
+
+        // By score then alphabetical when sorting by score
+        app.set_sort(WordSort::Score);
+        app.calculate();
+        assert_eq!(
+            app_words(&app),
+            ["CRANE", "CRANK", "DRANK", "PRANK", "BRAKE"]
+        );
+        assert_eq!(app.best_word(), Some(0));
+    }
+
+    #[test]
+    fn background_ranking() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+        app.set_ranking(true);
+        app.set_sort(WordSort::Score);
+        app.set_background(true);
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        // Words are listed unranked while the scores are found
+        app.calculate();
+        assert!(app.ranking_pending());
+        assert_eq!(app.score_kind(), None);
+        assert_eq!(
+            app_words(&app),
+            ["BRAKE", "CRANE", "CRANK", "DRANK", "PRANK"]
+        );
+
+        // Changing the strategy cancels the ranking and starts again
+        let cancel = app.rank_job.as_ref().unwrap().cancel.clone();
+
+        app.set_strategy(Strategy::Expected);
+        app.calculate();
+        assert!(cancel.is_cancelled());
+        assert!(app.ranking_pending());
+
+        // The words are sorted once the scores are collected
+        while !app.poll_ranking() {
+            thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        assert!(!app.ranking_pending());
+        assert_eq!(app.score_kind(), Some(ScoreKind::Guesses));
+
+        let scores = app.words.scores.clone().unwrap();
+        assert!(scores.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // Cancelling leaves the words unranked
+        app.set_strategy(Strategy::Entropy);
+        app.calculate();
+        app.cancel_ranking();
+        assert!(!app.ranking_pending());
+        assert!(!app.poll_ranking());
+        assert_eq!(app.score_kind(), None);
+    }
+
+    #[test]
+    fn strategy() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+        app.set_ranking(true);
+        app.set_sort(WordSort::Score);
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        app.calculate();
+        assert_eq!(app.score_kind(), Some(ScoreKind::Information));
+
+        // Changing the strategy ranks again, putting the fewest expected guesses first
+        app.set_strategy(Strategy::Expected);
+        app.calculate();
+        assert_eq!(app.score_kind(), Some(ScoreKind::Guesses));
+        assert_eq!(app.best_word(), Some(0));
+
+        let scores = app.words.scores.clone().unwrap();
+        assert!(scores.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(scores[0] < scores[4]);
+
+        // The best word is hottest and the worst coldest
+        let last = scores.len() - 1;
+        assert_eq!(app.get_heat(0), Some(1.0));
+        assert_eq!(app.get_heat(last), Some(0.0));
+        assert!((0..=last).all(|elem| (0.0..=1.0).contains(&app.get_heat(elem).unwrap())));
+
+        // No scores without ranking
+        app.set_ranking(false);
+        app.calculate();
+        assert_eq!(app.score_kind(), None);
+        assert_eq!(app.best_word(), None);
+        assert_eq!(app.get_heat(0), None);
+    }
+
+    #[test]
+    fn rank_cache() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+        app.set_ranking(true);
+        app.set_sort(WordSort::Score);
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        app.calculate();
+        let hash = app.rank_cache.as_ref().unwrap().hash;
+        let generation = app.generation;
+
+        // Changing the sort order without editing the board only sorts the words again
+        app.set_sort(WordSort::Alpha);
+        app.calculate();
+        assert_eq!(app.calculated, Some(generation));
+        assert_eq!(
+            app_words(&app),
+            ["BRAKE", "CRANE", "CRANK", "DRANK", "PRANK"]
+        );
+
+        // Typing a letter is an edit but finds the same words, so the scores are reused
+        app.add('C');
+        app.calculate();
+        assert_ne!(app.calculated, Some(generation));
+        assert_eq!(app.rank_cache.as_ref().unwrap().hash, hash);
+
+        // Ruling out words ranks again
+        app.toggle(1, 0);
+        app.toggle(1, 0);
+        app.toggle(1, 0);
+        app.calculate();
+        assert_eq!(app_words(&app), ["BRAKE", "DRANK", "PRANK"]);
+        assert_eq!(
+            app.words.scores.as_ref().map(|scores| scores.len()),
+            Some(3)
+        );
+        assert_ne!(app.rank_cache.as_ref().unwrap().hash, hash);
+    }
+
+    #[test]
+    fn shortlist() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        app.calculate();
+        assert_eq!(
+            app_words(&app),
+            ["BRAKE", "CRANE", "CRANK", "DRANK", "PRANK"]
+        );
+
+        // Pin PRANK then CRANE, and pin and unpin BRAKE
+        assert_eq!(app.toggle_pin(4), Some(true));
+        assert_eq!(app.toggle_pin(1), Some(true));
+        assert_eq!(app.toggle_pin(0), Some(true));
+        assert_eq!(app.toggle_pin(0), Some(false));
+        assert_eq!(app.toggle_pin(5), None);
+
+        assert!(app.is_pinned(1));
+        assert!(!app.is_pinned(0));
+        assert_eq!(app.shortlist(), ["PRANK", "CRANE"]);
+
+        // Pinned words are kept when the words are sorted again
+        app.set_ranking(true);
+        app.set_sort(WordSort::Score);
+        app.calculate();
+        assert_eq!(app.shortlist(), ["PRANK", "CRANE"]);
+
+        // CRANE is unpinned when a gray C rules it out
+        "CRANK".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        app.toggle(1, 0);
+        app.toggle(1, 0);
+        app.toggle(1, 0);
+        app.calculate();
+        assert_eq!(app.shortlist(), ["PRANK"]);
+    }
+
+    #[test]
+    fn preview() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        assert!(app.preview(0).is_none());
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        app.calculate();
+
+        // CRANE leaves DRANK and PRANK together, or solves, or splits out BRAKE or CRANK
+        let preview = app.preview(1).unwrap();
+
+        assert_eq!(preview.word, "CRANE");
+        assert_eq!(preview.worst(), 2);
+        assert_eq!(preview.groups.len(), 4);
+        assert_eq!(
+            preview.groups.iter().map(|(_, words)| words).sum::<usize>(),
+            5
+        );
+
+        let (row, _) = &preview.groups[0];
+        assert_eq!(
+            row.iter()
+                .map(|elem| TileColour::of(*elem).unwrap())
+                .collect::<Vec<_>>(),
+            [
+                TileColour::Gray,
+                TileColour::Green,
+                TileColour::Green,
+                TileColour::Green,
+                TileColour::Gray
+            ]
+        );
+
+        assert!(app.preview(5).is_none());
+    }
+
+    #[test]
+    fn explain_best() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+        app.set_ranking(true);
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        app.calculate();
+
+        // C, N and E are new. CRANE leaves one or two of the five candidates
+        assert_eq!(
+            app.explain_best().as_deref(),
+            Some(
+                "CRANE tests 3 new common letters and on average leaves 1.4 candidates; \
+                 worst case 2"
+            )
+        );
+
+        // Only CRANE is left after playing it
+        app.apply(BoardCommand::Import(vec![
+            feedback("STAIR", "CRANE"),
+            feedback("CRANE", "CRANE"),
+        ]));
+        app.calculate();
+
+        assert_eq!(
+            app.explain_best().as_deref(),
+            Some("CRANE is the only candidate")
+        );
+
+        // Nothing to explain without ranking
+        app.set_ranking(false);
+        app.calculate();
+
+        assert_eq!(app.explain_best(), None);
+    }
+
+    #[test]
+    fn letter_information() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        assert!(app.letter_information().is_empty());
+
+        "STAIR".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // A green, R yellow
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+        app.toggle(0, 4);
+
+        app.calculate();
+
+        // K splits BRAKE, CRANE and the rest, C and E split two words from three, and B, D, N and
+        // P split one word from four. A and R are on the board
+        let info = app.letter_information();
+
+        assert_eq!(
+            info.iter().map(|info| info.letter).collect::<String>(),
+            "KCEBDNP"
+        );
+        assert!(info.windows(2).all(|pair| pair[0].bits >= pair[1].bits));
+    }
+
+    #[test]
+    fn six_letters() {
+        let dictionary =
+            Dictionary::new_from_string("planet\nplanes\nplaice\nrocket", WordCase::Lower, false)
+                .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        assert_eq!(app.cols(), 6);
+        assert!(app.board().iter().all(|row| row.len() == 6));
+
+        // The sixth letter completes the row
+        "PLANE".chars().for_each(|c| {
+            app.add(c);
+        });
+        app.calculate();
+        assert_eq!(app.words().count(), None);
+
+        app.add('S');
+
+        // PLANE green, S gray
+        for col in 0..5 {
+            app.toggle(0, col);
+            app.toggle(0, col);
+        }
+
+        assert!(app.toggle_col(5));
+        assert!(app.toggle_col(5));
+
+        // Green needs toggling twice to change
+        assert!(!app.toggle_col(5));
+        assert!(app.toggle_col(5));
+
+        app.calculate();
+        assert_eq!(app_words(&app), ["PLANET"]);
+
+        // S is ruled out of the sixth column and the green columns are fixed
+        let allowed = app.allowed_letters(5).unwrap();
+        assert!(allowed[Dictionary::uchar_to_usize('T')]);
+        assert!(!allowed[Dictionary::uchar_to_usize('S')]);
+        assert_eq!(
+            app.allowed_letters(0)
+                .unwrap()
+                .iter()
+                .filter(|a| **a)
+                .count(),
+            1
+        );
+        assert_eq!(app.allowed_letters(6), None);
+
+        // Removing from the next row moves back to the sixth column
+        app.remove();
+        app.calculate();
+        assert_eq!(app.words().count(), None);
+        assert_eq!(app.allowed_letters(0), None);
+    }
+
+    #[test]
+    fn set_colour() {
+        let dictionary =
+            Dictionary::new_from_string("crane\ncrank\nslate", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        "CRANECRANK".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // Colours can be set directly
+        assert_eq!(app.next_colour(1, 0), Some(TileColour::Yellow));
+        assert!(app.set_colour(1, 0, TileColour::Green));
+        assert!(matches!(app.board()[1][0], BoardElem::Green('C')));
+
+        // The same letter in the column on other rows follows
+        assert!(matches!(app.board()[0][0], BoardElem::Green('C')));
+
+        // Setting the same colour again changes nothing
+        assert!(!app.set_colour(1, 0, TileColour::Green));
+
+        // Toggling moves green to gray and yellow to green
+        assert!(app.set_colour(0, 1, TileColour::Yellow));
+        assert_eq!(app.next_colour(1, 0), Some(TileColour::Gray));
+        assert_eq!(app.next_colour(0, 1), Some(TileColour::Green));
+
+        // Clearing only changes the one cell
+        assert!(app.clear_colour(1, 0));
+        assert!(matches!(app.board()[1][0], BoardElem::Unknown('C')));
+        assert!(matches!(app.board()[0][0], BoardElem::Green('C')));
+        assert!(!app.clear_colour(1, 0));
+
+        // Toggling back reverses the cycle, skipping green if the column has one
+        assert_eq!(app.prev_colour(0, 0), Some(TileColour::Yellow));
+        assert_eq!(app.prev_colour(0, 1), Some(TileColour::Gray));
+        assert_eq!(app.prev_colour(0, 2), Some(TileColour::Green));
+        assert!(app.set_colour(0, 0, TileColour::Gray));
+        assert_eq!(app.prev_colour(0, 0), Some(TileColour::Green));
+        assert!(app.toggle_back(0, 0));
+        assert!(matches!(app.board()[0][0], BoardElem::Green('C')));
+        assert!(app.clear_colour(1, 0));
+        assert_eq!(app.prev_colour(1, 0), Some(TileColour::Yellow));
+
+        // Empty cells can't be coloured
+        assert_eq!(app.next_colour(2, 0), None);
+        assert_eq!(app.prev_colour(2, 0), None);
+        assert!(!app.toggle_back(2, 0));
+        assert!(!app.set_colour(2, 0, TileColour::Gray));
+        assert!(!app.clear_colour(2, 0));
+    }
+
+    #[test]
+    fn lock_greens() {
+        let dictionary =
+            Dictionary::new_from_string("crane\ncrank\nprank", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+        app.set_lock_greens(true);
+
+        let row_text = |app: &SolveApp, row: usize| {
+            app.board()[row]
+                .iter()
+                .map(|elem| match elem {
+                    BoardElem::Empty => '_',
+                    BoardElem::Unknown(c) | BoardElem::Gray(c) | BoardElem::Yellow(c) => *c,
+                    BoardElem::Green(c) => c.to_ascii_lowercase(),
+                })
+                .collect::<String>()
+        };
+
+        // TRAIN with R and A green
+        "TRAIN".chars().for_each(|c| {
+            app.add(c);
+        });
+        app.toggle(0, 1);
+        app.toggle(0, 1);
+        app.toggle(0, 2);
+        app.toggle(0, 2);
+
+        // Nothing filled until the next letter is typed
+        assert_eq!(row_text(&app, 1), "_____");
+
+        // R and A are filled after the first letter
+        app.add('C');
+        assert_eq!(row_text(&app, 1), "Cra__");
+        assert!(!app.is_auto(1, 0));
+        assert!(app.is_auto(1, 1));
+        assert!(app.is_auto(1, 2));
+
+        app.add('N');
+        app.add('E');
+        assert_eq!(row_text(&app, 1), "CraNE");
+
+        // Filled letters are removed with the letter before them
+        app.remove();
+        app.remove();
+        app.remove();
+        assert_eq!(row_text(&app, 1), "_____");
+        assert_eq!(row_text(&app, 0), "TraIN");
+    }
+
+    #[test]
+    fn row_stats() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nprank\ntrace", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        let rows = |app: &SolveApp| {
+            app.row_stats()
+                .iter()
+                .map(|stats| (stats.row, stats.words, stats.filtered))
+                .collect::<Vec<_>>()
+        };
+
+        "CRANE".chars().for_each(|c| {
+            app.add(c);
+        });
+        app.calculate();
+        assert_eq!(rows(&app), [(1, 3, false)]);
+
+        // Tightening the constraints replaces the row's statistics
+        app.toggle(0, 1);
+        app.toggle(0, 1);
+        app.calculate();
+        assert_eq!(rows(&app), [(1, 3, true)]);
+
+        // Each completed row adds statistics
+        "PRANK".chars().for_each(|c| {
+            app.add(c);
+        });
+        app.calculate();
+        assert_eq!(
+            rows(&app).iter().map(|(row, ..)| *row).collect::<Vec<_>>(),
+            [1, 2]
+        );
+
+        // Removing a row drops its statistics
+        app.remove();
+        app.calculate();
+        assert_eq!(rows(&app).len(), 1);
+    }
+
+    #[test]
+    fn row_history() {
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        let history = |app: &SolveApp| {
+            app.row_history()
+                .iter()
+                .map(|row| format!("{} {}: {}>{}", row.row, row.word, row.before, row.after))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(history(&app).is_empty());
+
+        // STAIR with A green and R yellow leaves all but SLATE
+        app.apply(BoardCommand::Import(vec![feedback("STAIR", "CRANE")]));
+        app.calculate();
+        assert_eq!(history(&app), ["0 STAIR: 6>5"]);
+
+        // CRANE solves it
+        app.apply(BoardCommand::Import(vec![
+            feedback("STAIR", "CRANE"),
+            feedback("CRANE", "CRANE"),
+        ]));
+        app.calculate();
+        assert_eq!(history(&app), ["0 STAIR: 6>5", "1 CRANE: 5>1"]);
+
+        // Changing the first row counts both rows again
+        app.toggle(0, 0);
+        app.calculate();
+        assert_eq!(history(&app), ["0 STAIR: 6>0", "1 CRANE: 0>0"]);
+
+        // A letter count set by hand changes every row
+        app.toggle_back(0, 0);
+        app.set_count('E', 2, None);
+        app.calculate();
+        assert_eq!(history(&app), ["0 STAIR: 0>0", "1 CRANE: 0>0"]);
+
+        app.clear_counts();
+        app.calculate();
+        assert_eq!(history(&app), ["0 STAIR: 6>5", "1 CRANE: 5>1"]);
+    }
+
+    #[test]
+    fn unknown_letters() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nprank\ntrace", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        // Typed letters have no colour and don't rule out any words
+        "CRANE".chars().for_each(|c| {
+            app.add(c);
+        });
+        assert_eq!(app.board_text(), "CRANE:?????");
+
+        app.calculate();
+        assert_eq!(app.words().count(), Some(3));
+
+        // Toggling a letter only sets that letter
+        app.toggle(0, 1);
+        app.toggle(0, 1);
+        app.toggle(0, 0);
+        assert_eq!(app.board_text(), "CRANE:yg???");
+
+        app.calculate();
+        assert_eq!(app.words().count(), Some(1));
+
+        // Starting the next row turns the letters without a colour gray
+        app.add('T');
+        assert_eq!(app.board_text(), "CRANE:yg.../T:?");
+
+        // Gray letters toggle to yellow like letters without a colour
+        app.toggle(0, 2);
+        assert_eq!(app.board_text(), "CRANE:ygy../T:?");
+
+        // A cleared colour stays cleared as more letters are typed
+        assert!(app.clear_colour(0, 3));
+        app.add('R');
+        assert_eq!(app.board_text(), "CRANE:ygy?./TR:?g");
+
+        // Starting the next row again only confirms the letters typed without a colour
+        app.remove();
+        app.remove();
+        app.remove();
+        app.add('E');
+        assert_eq!(app.board_text(), "CRANE:ygy??");
+        app.add('T');
+        assert_eq!(app.board_text(), "CRANE:ygy?./T:?");
+
+        // Rows before the one left aren't changed
+        assert!(app.clear_colour(0, 4));
+        "RUCKS".chars().for_each(|c| {
+            app.add(c);
+        });
+        assert_eq!(app.board_text(), "CRANE:ygy??/TRUCK:.g.../S:?");
+    }
+
+    #[test]
+    fn mark_absent() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nprank\ntrace", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+        app.set_mark_absent(true);
+
+        // CRANE with R green and E yellow
+        "CRANE".chars().for_each(|c| {
+            app.add(c);
+        });
+        app.toggle(0, 1);
+        app.toggle(0, 1);
+        app.toggle(0, 4);
+
+        // A and C are gray once the row is confirmed, R and E are copied from the same column
+        "TRACE".chars().for_each(|c| {
+            app.add(c);
+        });
+        assert_eq!(app.board_text(), "CRANE:.g..y/TRACE:?g..y");
+
+        // E is yellow so isn't absent, and P hasn't been played
+        "PE".chars().for_each(|c| {
+            app.add(c);
+        });
+        assert_eq!(app.board_text(), "CRANE:.g..y/TRACE:.g..y/PE:??");
+
+        // Disabled leaves all letters without a colour
+        app.set_mark_absent(false);
+        app.remove();
+        app.remove();
+        "NA".chars().for_each(|c| {
+            app.add(c);
+        });
+        assert_eq!(app.board_text(), "CRANE:.g..y/TRACE:.g..y/NA:??");
+    }
+
+    #[test]
+    fn strict() {
+        let dictionary =
+            Dictionary::new_from_string("aback\nstair\ntacit", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+        app.set_strict(true);
+
+        let unknown = |word: &str| word.chars().map(BoardElem::Unknown).collect::<BoardRow>();
+
+        assert!(app.import(&[unknown("ABACK"), unknown("STAIR"), unknown("TACIT")]));
+
+        // ABACK with the first A yellow, the second green and C gray. The green A is copied to
+        // STAIR
+        assert!(app.set_colour(0, 0, TileColour::Yellow));
+        assert!(app.set_colour(0, 2, TileColour::Green));
+        assert!(app.set_colour(0, 3, TileColour::Gray));
+        assert_eq!(app.rejected(), None);
+        assert_eq!(app.board_text(), "ABACK:y?g.?/STAIR:??g??/TACIT:?????");
+
+        // A can't be gray in STAIR where it's green in ABACK
+        assert!(!app.toggle(1, 2));
+        assert_eq!(
+            app.rejected(),
+            Some("A is gray in column 3 where it is green")
+        );
+
+        // Only one letter can be green in a column
+        assert!(!app.set_colour(2, 2, TileColour::Green));
+        assert_eq!(app.rejected(), Some("A and C are both green in column 3"));
+
+        // C isn't in the word
+        assert!(!app.set_colour(2, 2, TileColour::Yellow));
+        assert_eq!(
+            app.rejected(),
+            Some("C is in the word at least 1 times but can appear at most 0 times")
+        );
+        assert_eq!(app.board_text(), "ABACK:y?g.?/STAIR:??g??/TACIT:?????");
+
+        // A change which fits clears the reason
+        assert!(app.set_colour(1, 0, TileColour::Gray));
+        assert_eq!(app.rejected(), None);
+
+        // Contradictions are allowed when not strict
+        app.set_strict(false);
+        assert!(app.set_colour(2, 2, TileColour::Green));
+        assert_eq!(app.rejected(), None);
+    }
+
+    #[test]
+    fn green_lock() {
+        let dictionary = Dictionary::new_from_string("crane", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        "CRANE".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // Toggle R to green
+        assert!(app.toggle_col(1));
+        assert!(app.toggle_col(1));
+        assert!(app.col_locked(1));
+        assert!(!app.col_locked(0));
+
+        // The first toggle of a green letter needs confirming
+        assert!(!app.toggle_col(1));
+        assert_eq!(
+            app.rejected(),
+            Some("Column 2 is green, press 2 again to change it")
+        );
+        assert!(app.toggle_col(1));
+        assert_eq!(app.rejected(), None);
+        assert!(!app.col_locked(1));
+
+        // Another change in between needs the green confirming again
+        assert!(app.toggle_col(1));
+        assert!(app.toggle_col(1));
+        assert!(!app.toggle_col(1));
+        assert!(app.toggle_col(0));
+        assert!(!app.toggle_col(1));
+        assert!(app.toggle_col(1));
+
+        // Clicking isn't guarded
+        assert!(app.toggle_col(1));
+        assert!(app.toggle_col(1));
+        assert!(app.toggle(0, 1));
+    }
+
+    #[test]
+    fn search() {
+        let dictionary = Dictionary::new_from_string(
+            "slate\ncrane\nstare\ncrate\nabbey",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        // Letters without a colour leave every word
+        "QQQQQ".chars().for_each(|c| {
+            app.add(c);
+        });
+        app.calculate();
+        assert_eq!(app.shown_words(), [0, 1, 2, 3, 4]);
+        assert_eq!(app.search_match(0), None);
+
+        // Substrings match anywhere in the word
+        app.set_search("ate");
+        assert_eq!(app.search(), Some("ATE"));
+        assert_eq!(app.shown_words(), [2, 3]);
+        assert_eq!(app.search_match(2), Some(2..5));
+        assert_eq!(app.search_match(0), None);
+
+        // Wildcards match any letter
+        app.set_search("?RA");
+        assert_eq!(app.shown_words(), [1, 2]);
+        assert_eq!(app.search_match(1), Some(0..3));
+
+        assert_eq!(app.word_groups(), [('C', vec![1, 2])]);
+
+        // Searches longer than the words match nothing
+        app.set_search("crates");
+        assert!(app.shown_words().is_empty());
+
+        app.set_search("");
+        assert_eq!(app.search(), None);
+        assert_eq!(app.shown_words().len(), 5);
+    }
+
+    #[test]
+    fn eliminated() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        "CRANE".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // Not kept unless enabled. E gray eliminates BRAKE and CRANE
+        app.set_colour(0, 4, TileColour::Gray);
+        app.calculate();
+        assert!(app.eliminated().is_empty());
+
+        app.set_show_eliminated(true);
+
+        // C green eliminates DRANK and PRANK
+        app.set_colour(0, 0, TileColour::Green);
+        app.calculate();
+        assert_eq!(app.eliminated(), ["DRANK", "PRANK"]);
+
+        // Recalculating without an edit keeps them
+        app.calculate();
+        assert_eq!(app.eliminated(), ["DRANK", "PRANK"]);
+
+        // The search applies
+        app.set_search("D");
+        assert_eq!(app.eliminated(), ["DRANK"]);
+        app.set_search("");
+
+        // C gray brings them back and eliminates CRANK
+        app.set_colour(0, 0, TileColour::Gray);
+        app.calculate();
+        assert_eq!(app.eliminated(), ["CRANK"]);
+
+        app.set_show_eliminated(false);
+        assert!(app.eliminated().is_empty());
+    }
+
+    #[test]
+    fn contradiction() {
+        let dictionary =
+            Dictionary::new_from_string("slate\nstale", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        let mut rows = vec![feedback("CRANE", "SLATE"), feedback("SPACE", "SLATE")];
+
+        assert!(app.import(&rows));
+        app.calculate();
+        assert_eq!(app.contradiction(), None);
+        assert_eq!(app_words(&app), ["SLATE", "STALE"]);
+
+        rows[0][4] = BoardElem::Yellow('E');
+
+        assert!(app.import(&rows));
+        app.calculate();
+        assert_eq!(
+            app.contradiction(),
+            Some(Contradiction::YellowGreen {
+                letter: 'E',
+                col: 4
+            })
+        );
+        assert!(app_words(&app).is_empty());
+        assert!(app
+            .status_text()
+            .starts_with("E is yellow in column 5 where it is green - 0 candidates"));
+    }
+
+    #[test]
+    fn letter_counts() {
+        let dictionary = Dictionary::new_from_string(
+            "fence\nhence\nlevee\nmetal\nspree",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        // Counts set by hand are searched without a board
+        assert!(app.step_count('E', CountBound::Min, true));
+        assert!(app.step_count('E', CountBound::Min, true));
+        assert_eq!(app.count_set('E'), Some((2, None)));
+
+        app.calculate();
+        assert_eq!(app_words(&app), ["FENCE", "HENCE", "LEVEE", "SPREE"]);
+
+        // A most below the word length is a limit
+        assert!(app.step_count('E', CountBound::Max, false));
+        assert_eq!(app.count_set('E'), Some((2, Some(4))));
+        assert!(app.step_count('E', CountBound::Max, false));
+        assert!(app.step_count('E', CountBound::Max, false));
+
+        app.calculate();
+        assert_eq!(app.letter_count('E'), (2, Some(2)));
+        assert_eq!(app_words(&app), ["FENCE", "HENCE", "SPREE"]);
+
+        // Hand counts narrow the board's counts
+        assert!(app.import(&[feedback("THYME", "SPREE")]));
+        assert!(app.set_count('N', 0, Some(0)));
+
+        app.calculate();
+        assert_eq!(app.letter_count('E'), (2, Some(2)));
+        assert_eq!(app.letter_count('N'), (0, Some(0)));
+        assert_eq!(app_words(&app), ["SPREE"]);
+
+        // Moving a bound up past the word length removes the limit
+        assert!(app.step_count('N', CountBound::Max, true));
+        assert_eq!(app.count_set('N'), Some((0, Some(1))));
+        assert!(!app.set_count('N', 0, Some(1)));
+
+        assert!(app.clear_counts());
+        assert!(!app.clear_counts());
+
+        app.calculate();
+        assert_eq!(app.letter_count('E'), (1, None));
+        assert_eq!(app_words(&app), ["FENCE", "LEVEE", "SPREE"]);
+    }
+
+    #[test]
+    fn board_commands() {
+        let dictionary =
+            Dictionary::new_from_string("crane\ntrace", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        assert_eq!(app.apply(BoardCommand::Remove), BoardEffect::Unchanged);
+
+        for c in "CRANE".chars() {
+            assert_eq!(app.apply(BoardCommand::Add(c)), BoardEffect::Changed);
+        }
+
+        for col in [1, 2, 4] {
+            let colour = TileColour::Green;
+
+            assert_eq!(
+                app.apply(BoardCommand::SetColour {
+                    row: 0,
+                    col,
+                    colour
+                }),
+                BoardEffect::Changed
+            );
+        }
+
+        assert_eq!(
+            app.apply(BoardCommand::Toggle { row: 0, col: 0 }),
+            BoardEffect::Changed
+        );
+
+        app.calculate();
+        assert_eq!(app_words(&app), ["TRACE"]);
+
+        // Commands off the board are rejected rather than panicking
+        assert!(matches!(
+            app.apply(BoardCommand::Toggle { row: 0, col: 5 }),
+            BoardEffect::Rejected(_)
+        ));
+        assert!(matches!(
+            app.apply(BoardCommand::ClearColour {
+                row: BOARD_ROWS,
+                col: 0
+            }),
+            BoardEffect::Rejected(_)
+        ));
+        assert!(matches!(
+            app.apply(BoardCommand::Add('c')),
+            BoardEffect::Rejected(_)
+        ));
+        assert!(matches!(
+            app.apply(BoardCommand::Import(vec![vec![BoardElem::Gray('A')]])),
+            BoardEffect::Rejected(_)
+        ));
+
+        // Green columns need toggling twice, the first giving the reason
+        assert_eq!(
+            app.apply(BoardCommand::ToggleCol(4)),
+            BoardEffect::Rejected("Column 5 is green, press 5 again to change it".into())
+        );
+        assert_eq!(app.apply(BoardCommand::ToggleCol(4)), BoardEffect::Changed);
+
+        // Selecting a row doesn't need the words calculating again
+        app.apply(BoardCommand::Add('S'));
+
+        assert_eq!(app.apply(BoardCommand::SelectRow(0)), BoardEffect::Selected);
+        assert_eq!(
+            app.apply(BoardCommand::SelectRow(0)),
+            BoardEffect::Unchanged
+        );
+        assert!(BoardEffect::Selected.changed());
+        assert!(!BoardEffect::Rejected(String::new()).changed());
+    }
+
+    #[test]
+    fn board_ansi() {
+        let dictionary = Dictionary::new_from_string("crane", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        assert_eq!(app.board_ansi(), "");
+
+        assert!(app.import(&[feedback("CRANE", "TRACE")]));
+        app.add('S');
+
+        let gray = "\x1b[1;97;48;2;120;124;126m";
+        let yellow = "\x1b[1;97;48;2;201;180;88m";
+        let green = "\x1b[1;97;48;2;106;170;100m";
+        let plain = "\x1b[1m";
+        let reset = "\x1b[0m";
+
+        let empty = format!("{plain}   {reset}");
+
+        assert_eq!(
+            app.board_ansi(),
+            format!(
+                "{yellow} C {reset} {green} R {reset} {green} A {reset} {gray} N {reset} \
+                {green} E {reset}\n\
+                {plain} S {reset} {empty} {empty} {empty} {empty}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn active_row() {
+        let dictionary = Dictionary::new_from_string("crane", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        assert_eq!(app.active_row(), None);
+        assert!(!app.toggle_col(0));
+
+        "CRANESTAI".chars().for_each(|c| {
+            app.add(c);
+        });
+        assert_eq!(app.active_row(), Some(1));
+
+        // Backspacing keeps the partly typed row, so its empty columns can't be toggled
+        app.remove();
+        assert_eq!(app.active_row(), Some(1));
+        assert!(!app.toggle_col(4));
+        assert!(app.toggle_col(0));
+        assert_eq!(app.board()[1][0], BoardElem::Yellow('S'));
+
+        // Selecting the first row toggles its letters
+        assert!(app.select_row(0));
+        assert!(!app.select_row(0));
+        assert!(!app.select_row(2));
+        assert!(app.toggle_col(4));
+        assert_eq!(app.board()[0][4], BoardElem::Yellow('E'));
+
+        // Typing selects the row typed on again
+        app.add('R');
+        assert_eq!(app.active_row(), Some(1));
+
+        // Removing a whole row selects the row before it
+        (0..4).for_each(|_| {
+            app.remove();
+        });
+        assert_eq!(app.active_row(), Some(0));
+    }
+
+    #[test]
+    fn unknown_words() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nlight\nprank", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        // LIGHS isn't a word but is used by default
+        "LIGHSCRANE".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        assert_eq!(app.row_valid(0), Some(false));
+        assert_eq!(app.row_valid(1), Some(true));
+        assert_eq!(app.row_valid(2), None);
+
+        // CRANE has no colours yet so only LIGHS rules out words
+        app.calculate();
+        assert_eq!(app_words(&app), ["CRANE", "PRANK"]);
+
+        // Rejecting unknown words leaves the row out
+        app.set_reject_unknown(true);
+        app.calculate();
+        assert_eq!(app_words(&app), ["CRANE", "LIGHT", "PRANK"]);
+
+        // No accepted rows
+        app.remove();
+        app.calculate();
+        assert_eq!(app.words().count(), None);
+    }
+
+    #[test]
+    fn guesses() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nprank", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        assert_eq!(app.alphabet().into_iter().collect::<String>(), "ACEKNPR");
+
+        "LIGHT".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        assert_eq!(app.row_valid(0), Some(false));
+
+        // Accepted as a guess but not found as an answer
+        app.set_guesses(
+            Dictionary::new_from_string("crane\nlight\nprank", WordCase::Lower, false).unwrap(),
+        );
+
+        assert_eq!(app.row_valid(0), Some(true));
+        assert_eq!(app.unknown_word(), None);
+
+        app.set_reject_unknown(true);
+        app.calculate();
+        assert_eq!(app_words(&app), ["CRANE", "PRANK"]);
+        assert_eq!(app.alphabet().len(), 7);
+    }
+
+    #[test]
+    fn add_words() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nprank", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        "PLANK".chars().for_each(|c| {
+            app.add(c);
+        });
+
+        // P, A, N and K green
+        for col in [0, 2, 3, 4] {
+            app.toggle(0, col);
+            app.toggle(0, col);
+        }
+
+        app.calculate();
+        assert_eq!(app.unknown_word(), Some("PLANK".to_string()));
+        assert_eq!(app_words(&app), ["PRANK"]);
+
+        // Adding the word makes the row valid and includes it in later searches
+        app.add_words(&["plank".to_string(), "prawn".to_string()])
+            .unwrap();
+        assert_eq!(app.unknown_word(), None);
+        assert_eq!(app.row_valid(0), Some(true));
+
+        // L has no colour so both words match
+        app.calculate();
+        assert_eq!(app_words(&app), ["PLANK", "PRANK"]);
+
+        app.toggle(0, 1);
+        app.toggle(0, 1);
+        app.calculate();
+        assert_eq!(app_words(&app), ["PLANK"]);
+    }
+
+    #[test]
+    fn incremental() {
+        let dictionary =
+            Dictionary::new_from_file("../words.txt.gz", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        // Check the incremental result against a full search after every edit
+        let check = |app: &mut SolveApp| {
+            app.calculate();
+
+            let words = app_words(app);
+
+            app.constraints = None;
+            app.calculate();
+
+            assert_eq!(words, app_words(app));
+        };
+
+        for c in "STAIRCRANE".chars() {
+            app.add(c);
+            check(&mut app);
+        }
+
+        for col in [2, 2, 4, 1, 1, 0, 2] {
+            app.toggle_col(col);
+            check(&mut app);
+        }
+
+        for _ in 0..3 {
+            app.remove();
+            check(&mut app);
+        }
+
+        for c in "NK".chars() {
+            app.add(c);
+            check(&mut app);
+        }
+
+        app.toggle(0, 4);
+        check(&mut app);
+    }
+
+    #[test]
+    fn import() {
+        let dictionary =
+            Dictionary::new_from_string("crane\nprank\ntrace", WordCase::Lower, false).unwrap();
+
+        let mut app = SolveApp::new(dictionary);
+
+        app.add('S');
+
+        let row = |colours: &str| {
+            "CRANE"
+                .chars()
+                .zip(colours.chars())
+                .map(|(c, colour)| match colour {
+                    'y' => BoardElem::Yellow(c),
+                    'g' => BoardElem::Green(c),
+                    _ => BoardElem::Gray(c),
+                })
+                .collect::<BoardRow>()
+        };
+
+        // Imported rows replace the board and typing continues on the next row
+        assert!(app.import(&[row(".ggg.")]));
+        app.add('P');
+        assert_eq!(app.board_text(), "CRANE:.ggg./P:?");
+
+        app.calculate();
+        assert_eq!(app.words().count(), Some(1));
+
+        // Rows of the wrong length are rejected
+        assert!(!app.import(&[vec![BoardElem::Gray('A')]]));
+        assert_eq!(app.board_text(), "CRANE:.ggg./P:?");
     }
 }
@@ -0,0 +1,23 @@
+//! Logging to a file
+
+use std::fs::File;
+use std::io;
+
+use env_logger::{Env, Target};
+
+/// Default log filter, with debug messages from the solver crates and the front ends
+const LOG_FILTER: &str =
+    "warn,dictionary=debug,solver=debug,solveapp=debug,solve=debug,solvetui=debug,solvegui=debug";
+
+/// Sets up logging to a file if requested. RUST_LOG overrides the default filter
+pub fn init_logging(log_file: Option<&str>) -> io::Result<()> {
+    if let Some(log_file) = log_file {
+        let file = File::create(log_file)?;
+
+        env_logger::Builder::from_env(Env::default().default_filter_or(LOG_FILTER))
+            .target(Target::Pipe(Box::new(file)))
+            .init();
+    }
+
+    Ok(())
+}
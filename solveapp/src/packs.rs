@@ -0,0 +1,420 @@
+//! Language packs installed in the data directory
+//!
+//! A language pack is a tar or zip archive holding the answers, the extra words accepted as
+//! guesses and a description of the language, saved as NAME.pack in the packs directory
+
+use std::fs::{create_dir_all, read_dir, remove_file, rename, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use dictionary::{DictionarySet, LoadOptions};
+use log::{info, warn};
+
+use crate::paths::packs_dir;
+
+/// Language pack file extension
+pub const PACK_EXTENSION: &str = "pack";
+
+/// Name the default language pack is saved as when downloaded
+pub const DEFAULT_PACK: &str = "english";
+
+/// Extension added to a language pack file while it is downloading
+const PART_EXTENSION: &str = "part";
+
+/// Size of the chunks a download is saved in, with progress reported after each
+const DOWNLOAD_CHUNK: usize = 64 * 1024;
+
+/// Seconds allowed to connect to the server
+const CONNECT_TIMEOUT: &str = "30";
+
+/// Seconds allowed for the whole download
+const DOWNLOAD_TIMEOUT: &str = "600";
+
+/// Language pack found in the packs directory
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanguagePack {
+    /// Pack name, the file name without the extension
+    pub name: String,
+    /// Path to the pack file
+    pub path: PathBuf,
+}
+
+impl LanguagePack {
+    /// Finds the language packs installed, in name order. A missing packs directory has no packs
+    pub fn scan() -> Vec<Self> {
+        let Some(dir) = packs_dir() else {
+            return Vec::new();
+        };
+
+        match Self::scan_dir(&dir) {
+            Ok(packs) => packs,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                warn!(
+                    "Unable to read the language packs in {}: {e}",
+                    dir.display()
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Finds an installed language pack by name
+    pub fn find(name: &str) -> io::Result<Self> {
+        Self::scan()
+            .into_iter()
+            .find(|pack| pack.name == name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("No language pack named {name}, see --list-packs"),
+                )
+            })
+    }
+
+    /// Loads the language pack's answers and guesses
+    pub fn load(
+        &self,
+        options: impl Into<LoadOptions>,
+        verbose: bool,
+    ) -> io::Result<DictionarySet> {
+        DictionarySet::new_from_archive(&self.file(), options, verbose)
+    }
+
+    /// Returns the path to the pack file as a string
+    pub fn file(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+
+    /// Describes the language pack for a list of the packs installed, loading it to count its
+    /// words
+    pub fn describe(&self, options: impl Into<LoadOptions>) -> String {
+        match self.load(options, false) {
+            Ok(set) => format!(
+                "{}: {}, {} answers, {} guesses",
+                self.name,
+                set.metadata(),
+                set.answers().word_count(),
+                set.guesses().word_count()
+            ),
+            Err(e) => format!("{}: unable to load: {e}", self.name),
+        }
+    }
+
+    /// Downloads a language pack into the packs directory, calling a function with the bytes
+    /// received so far and the total if known as the download progresses. Uses curl so no TLS
+    /// stack is built in, so curl must be on the PATH. The pack is checked it can be loaded
+    /// before it replaces any pack of the same name
+    pub fn download(
+        url: &str,
+        name: &str,
+        mut progress: impl FnMut(u64, Option<u64>),
+    ) -> io::Result<Self> {
+        let Some(dir) = packs_dir() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Unable to determine data directory",
+            ));
+        };
+
+        create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{name}.{PACK_EXTENSION}"));
+        let part = path.with_extension(format!("{PACK_EXTENSION}.{PART_EXTENSION}"));
+
+        info!("Downloading language pack {name} from {url}");
+
+        let result = Self::fetch(url, &part, &mut progress).and_then(|_| {
+            DictionarySet::new_from_archive(&part.to_string_lossy(), LoadOptions::default(), false)
+                .map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Downloaded file isn't a language pack: {e}"),
+                    )
+                })
+        });
+
+        if let Err(e) = result {
+            let _ = remove_file(&part);
+            return Err(e);
+        }
+
+        rename(&part, &path)?;
+
+        Ok(Self {
+            name: name.to_string(),
+            path,
+        })
+    }
+
+    /// Fetches a file with curl, saving it to a path. Gives up if the server can't be reached
+    /// within CONNECT_TIMEOUT seconds or the download takes longer than DOWNLOAD_TIMEOUT
+    fn fetch(url: &str, path: &Path, progress: &mut dyn FnMut(u64, Option<u64>)) -> io::Result<()> {
+        let mut child = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--location",
+                "--connect-timeout",
+                CONNECT_TIMEOUT,
+                "--max-time",
+                DOWNLOAD_TIMEOUT,
+                "--dump-header",
+                "-",
+                url,
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => io::Error::new(
+                    e.kind(),
+                    "Downloading needs curl, which isn't installed or isn't on the PATH",
+                ),
+                _ => io::Error::new(e.kind(), format!("Unable to run curl: {e}")),
+            })?;
+
+        // Collect curl's messages as they come so it can't stall writing to a full pipe
+        let mut stderr = child.stderr.take().expect("curl errors are piped");
+
+        let errors = thread::spawn(move || {
+            let mut errors = String::new();
+            let _ = stderr.read_to_string(&mut errors);
+            errors
+        });
+
+        let saved = save(
+            child.stdout.take().expect("curl output is piped"),
+            path,
+            progress,
+        );
+
+        // Stop curl if the body couldn't be saved, and always wait for it to finish
+        if saved.is_err() {
+            let _ = child.kill();
+        }
+
+        let status = child.wait()?;
+        let errors = errors.join().unwrap_or_default();
+
+        match (saved, status.success()) {
+            (Ok(()), true) => Ok(()),
+            // curl's own message explains a failed download better than the output it left
+            (_, false) if !errors.trim().is_empty() => Err(io::Error::other(format!(
+                "Unable to download {url}: {}",
+                errors.trim()
+            ))),
+            (Err(e), _) => Err(e),
+            (Ok(()), false) => Err(io::Error::other(format!(
+                "Unable to download {url}: curl {status}"
+            ))),
+        }
+    }
+
+    /// Finds the language packs in a directory, in name order
+    fn scan_dir(dir: &Path) -> io::Result<Vec<Self>> {
+        let mut packs = read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter_map(|path| {
+                pack_name(&path).map(|name| Self {
+                    name: name.to_string(),
+                    path: path.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        packs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(packs)
+    }
+}
+
+/// Saves the body of a response from curl's output to a path after reading the headers, calling
+/// a function with the bytes received so far and the total if known
+fn save(
+    output: impl Read,
+    path: &Path,
+    progress: &mut dyn FnMut(u64, Option<u64>),
+) -> io::Result<()> {
+    let mut reader = BufReader::with_capacity(DOWNLOAD_CHUNK, output);
+
+    // The headers come before the body, giving its length
+    let total = read_headers(&mut reader)?;
+
+    let mut file = File::create(path)?;
+    let mut received = 0;
+    let mut buf = vec![0; DOWNLOAD_CHUNK];
+
+    progress(received, total);
+
+    loop {
+        let len = reader.read(&mut buf)?;
+
+        if len == 0 {
+            break;
+        }
+
+        file.write_all(&buf[..len])?;
+        received += len as u64;
+
+        progress(received, total);
+    }
+
+    Ok(())
+}
+
+/// Reads the response headers curl writes before the body, skipping the headers of informational
+/// responses and redirects. Returns the length of the body if given
+fn read_headers(reader: &mut impl BufRead) -> io::Result<Option<u64>> {
+    loop {
+        let mut status = String::new();
+
+        if reader.read_line(&mut status)? == 0 {
+            return Ok(None);
+        }
+
+        let code = status
+            .split_whitespace()
+            .nth(1)
+            .and_then(|code| code.parse::<u16>().ok());
+
+        let mut length = None;
+
+        loop {
+            let mut line = String::new();
+
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().ok();
+                }
+            }
+        }
+
+        match code {
+            Some(100..=199 | 300..=399) => continue,
+            _ => return Ok(length),
+        }
+    }
+}
+
+/// Returns the name of a language pack file, or None if the file isn't a language pack
+fn pack_name(path: &Path) -> Option<&str> {
+    if path.extension()? == PACK_EXTENSION {
+        path.file_stem()?.to_str().filter(|name| !name.is_empty())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_names() {
+        assert_eq!(pack_name(Path::new("packs/english.pack")), Some("english"));
+        assert_eq!(pack_name(Path::new("fr-5.pack")), Some("fr-5"));
+        assert_eq!(pack_name(Path::new("packs/english.zip")), None);
+        assert_eq!(pack_name(Path::new("packs/english")), None);
+        assert_eq!(pack_name(Path::new("packs/.pack")), None);
+        assert_eq!(pack_name(Path::new("packs/english.pack.part")), None);
+    }
+
+    #[test]
+    fn headers() {
+        // A redirect followed by the response with the body
+        let response = "HTTP/1.1 302 Found\r\n\
+            Location: https://example.com/english.pack\r\n\
+            Content-Length: 0\r\n\
+            \r\n\
+            HTTP/2 200\r\n\
+            content-length: 1234\r\n\
+            \r\n\
+            BODY";
+
+        let mut reader = BufReader::new(response.as_bytes());
+
+        assert_eq!(read_headers(&mut reader).unwrap(), Some(1234));
+
+        let mut body = String::new();
+        reader.read_to_string(&mut body).unwrap();
+        assert_eq!(body, "BODY");
+
+        // An interim 100 Continue response, a permanent redirect and then the response
+        let response = "HTTP/1.1 100 Continue\r\n\
+            \r\n\
+            HTTP/1.1 301 Moved Permanently\r\n\
+            Location: https://example.com/english.pack\r\n\
+            \r\n\
+            HTTP/1.1 100 Continue\r\n\
+            \r\n\
+            HTTP/1.1 200 OK\r\n\
+            Content-Type: application/octet-stream\r\n\
+            Content-Length: 42\r\n\
+            \r\n\
+            BODY";
+
+        let mut reader = BufReader::new(response.as_bytes());
+
+        assert_eq!(read_headers(&mut reader).unwrap(), Some(42));
+
+        let mut body = String::new();
+        reader.read_to_string(&mut body).unwrap();
+        assert_eq!(body, "BODY");
+
+        // A redirect's length isn't taken as the body's
+        let response = "HTTP/1.1 302 Found\r\n\
+            Content-Length: 99\r\n\
+            \r\n\
+            HTTP/1.1 200 OK\r\n\
+            \r\n\
+            BODY";
+
+        let mut reader = BufReader::new(response.as_bytes());
+        assert_eq!(read_headers(&mut reader).unwrap(), None);
+
+        // No length given
+        let mut reader = BufReader::new("HTTP/1.1 200 OK\r\n\r\nBODY".as_bytes());
+        assert_eq!(read_headers(&mut reader).unwrap(), None);
+
+        // Nothing received
+        assert_eq!(read_headers(&mut BufReader::new(&b""[..])).unwrap(), None);
+    }
+
+    #[test]
+    fn save_body() {
+        let response = "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nBODY";
+
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("wordle-save-{}.pack.part", std::process::id()));
+
+        let mut calls = Vec::new();
+
+        save(response.as_bytes(), &path, &mut |received, total| {
+            calls.push((received, total))
+        })
+        .unwrap();
+
+        let body = std::fs::read_to_string(&path).unwrap();
+        remove_file(&path).unwrap();
+
+        assert_eq!(body, "BODY");
+        assert_eq!(calls, [(0, Some(4)), (4, Some(4))]);
+
+        // A file which can't be created stops the save
+        let path = dir
+            .join(format!("wordle-missing-{}", std::process::id()))
+            .join("english.pack.part");
+
+        assert!(save(response.as_bytes(), &path, &mut |_, _| ()).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! Locations of the user's files
+//!
+//! Settings go in the configuration directory, files the user builds up such as word list
+//! additions go in the data directory and files which can be recreated go in the cache
+//! directory. These follow the XDG base directories on Linux, Application Support and Caches on
+//! macOS and AppData on Windows
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Application directory name
+const APP_DIR: &str = "wordle-solve";
+
+/// Directory holding the profile directories within the application directory
+const PROFILES_DIR: &str = "profiles";
+
+/// Directory holding the language packs within the data directory
+const PACKS_DIR: &str = "packs";
+
+/// Profile selected for this run
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Selects a profile with its own settings and word list additions, kept apart from the default
+/// files. Can only be set once, before any files are loaded
+pub fn set_profile(name: &str) -> io::Result<()> {
+    if !valid_profile(name) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid profile name {name:?}, use letters, digits, '-' and '_'"),
+        ));
+    }
+
+    PROFILE
+        .set(name.to_string())
+        .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "Profile has already been set"))
+}
+
+/// Returns the selected profile, or None for the default files
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+/// Returns the application configuration directory for the selected profile
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| profile_dir(&dir, profile()))
+}
+
+/// Returns the application data directory for the selected profile
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| profile_dir(&dir, profile()))
+}
+
+/// Returns the directory language packs are installed in for the selected profile
+pub fn packs_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(PACKS_DIR))
+}
+
+/// Returns the application cache directory for the selected profile
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| profile_dir(&dir, profile()))
+}
+
+/// Returns the directory exported files are saved in, the user's pictures directory or their
+/// home directory if there isn't one
+pub fn export_dir() -> Option<PathBuf> {
+    dirs::picture_dir().or_else(dirs::home_dir)
+}
+
+/// Returns the path to a file in the application configuration directory
+pub(crate) fn config_path(file: &str) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(file))
+}
+
+/// Returns the path to a file in the application data directory
+pub(crate) fn data_path(file: &str) -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(file))
+}
+
+/// Returns a profile's directory within a base directory
+fn profile_dir(base: &Path, profile: Option<&str>) -> PathBuf {
+    let dir = base.join(APP_DIR);
+
+    match profile {
+        Some(profile) => dir.join(PROFILES_DIR).join(profile),
+        None => dir,
+    }
+}
+
+/// Checks a profile name is safe to use as a directory name
+fn valid_profile(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles() {
+        let base = Path::new("config");
+
+        assert_eq!(profile_dir(base, None), Path::new("config/wordle-solve"));
+        assert_eq!(
+            profile_dir(base, Some("alice")),
+            Path::new("config/wordle-solve/profiles/alice")
+        );
+
+        assert!(valid_profile("alice"));
+        assert!(valid_profile("kid_2-b"));
+        assert!(!valid_profile(""));
+        assert!(!valid_profile(".."));
+        assert!(!valid_profile("a/b"));
+        assert!(!valid_profile("a b"));
+    }
+}
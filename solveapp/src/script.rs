@@ -0,0 +1,435 @@
+//! Scripts automating the board, written in the rhai scripting language
+//!
+//! Scripts get the app as the `app` variable, with methods to change the board through the
+//! board commands and to query the words fitting it, eg.
+//!
+//! ```text
+//! app.guess("CRANE", "bbygb");
+//! print(app.candidates());
+//!
+//! while !app.solved() {
+//!     app.play(app.best(), "TRACE");
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::fs::read_to_string;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope, INT};
+use solver::{feedback, BoardElem, BoardRow, BoardState};
+
+use crate::{BoardCommand, BoardEffect, SolveApp, TileColour};
+
+/// Result of a function called by a script
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// App shared with a running script
+#[derive(Clone)]
+struct AppHandle(Rc<RefCell<SolveApp>>);
+
+/// Runs rhai scripts against the app
+pub struct Script {
+    /// Scripting engine with the app's methods registered
+    engine: Engine,
+    /// App the scripts change
+    app: AppHandle,
+}
+
+impl Script {
+    /// Creates a script runner for the app
+    pub fn new(app: SolveApp) -> Self {
+        let mut engine = Engine::new();
+
+        engine.register_type_with_name::<AppHandle>("App");
+
+        // Board commands
+        engine.register_fn("add", |app: &mut AppHandle, c: char| {
+            app.apply(BoardCommand::Add(c.to_ascii_uppercase()))
+        });
+        engine.register_fn("remove", |app: &mut AppHandle| {
+            app.apply(BoardCommand::Remove)
+        });
+        engine.register_fn("type_word", AppHandle::type_word);
+        engine.register_fn("guess", AppHandle::guess);
+        engine.register_fn("play", AppHandle::play);
+        engine.register_fn("select_row", |app: &mut AppHandle, row: INT| {
+            app.apply(BoardCommand::SelectRow(index(row)?))
+        });
+        engine.register_fn("toggle_col", |app: &mut AppHandle, col: INT| {
+            app.apply(BoardCommand::ToggleCol(index(col)?))
+        });
+        engine.register_fn("toggle", |app: &mut AppHandle, row: INT, col: INT| {
+            let (row, col) = (index(row)?, index(col)?);
+
+            app.apply(BoardCommand::Toggle { row, col })
+        });
+        engine.register_fn("toggle_back", |app: &mut AppHandle, row: INT, col: INT| {
+            let (row, col) = (index(row)?, index(col)?);
+
+            app.apply(BoardCommand::ToggleBack { row, col })
+        });
+        engine.register_fn(
+            "set_colour",
+            |app: &mut AppHandle, row: INT, col: INT, colour: &str| {
+                let (row, col, colour) = (index(row)?, index(col)?, tile_colour(colour)?);
+
+                app.apply(BoardCommand::SetColour { row, col, colour })
+            },
+        );
+        engine.register_fn("clear_colour", |app: &mut AppHandle, row: INT, col: INT| {
+            let (row, col) = (index(row)?, index(col)?);
+
+            app.apply(BoardCommand::ClearColour { row, col })
+        });
+        engine.register_fn("set_count", |app: &mut AppHandle, c: char, min: INT| {
+            app.set_count(c, min, None)
+        });
+        engine.register_fn(
+            "set_count",
+            |app: &mut AppHandle, c: char, min: INT, max: INT| app.set_count(c, min, Some(max)),
+        );
+        engine.register_fn("clear_counts", |app: &mut AppHandle| {
+            app.apply(BoardCommand::ClearCounts)
+        });
+
+        // Queries
+        engine.register_fn("candidates", AppHandle::candidates);
+        engine.register_fn("best", AppHandle::best);
+        engine.register_fn("board", |app: &mut AppHandle| {
+            BoardState::from(app.0.borrow().board().clone()).to_string()
+        });
+        engine.register_fn("solved", AppHandle::solved);
+        engine.register_fn("letter_count", |app: &mut AppHandle, c: char| {
+            let (min, max) = app.0.borrow().letter_count(c.to_ascii_uppercase());
+
+            let max = max.map_or(Dynamic::UNIT, |max| Dynamic::from(max as INT));
+
+            vec![Dynamic::from(min as INT), max]
+        });
+        engine.register_fn("status", |app: &mut AppHandle| app.0.borrow().status_text());
+        engine.register_fn(
+            "feedback",
+            |guess: &str, answer: &str| -> ScriptResult<String> {
+                let (guess, answer) = words(guess, answer)?;
+
+                Ok(pattern(&feedback(&guess, &answer)))
+            },
+        );
+
+        Self {
+            engine,
+            app: AppHandle(Rc::new(RefCell::new(app))),
+        }
+    }
+
+    /// Runs a script
+    pub fn run(&self, script: &str) -> io::Result<()> {
+        let mut scope = Scope::new();
+        scope.push_constant("app", self.app.clone());
+
+        self.engine
+            .run_with_scope(&mut scope, script)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))
+    }
+
+    /// Runs a script read from a file
+    pub fn run_file(&self, path: &Path) -> io::Result<()> {
+        self.run(&read_to_string(path)?)
+    }
+
+    /// Returns the app with the board left by the scripts
+    pub fn into_app(self) -> SolveApp {
+        drop(self.engine);
+
+        match Rc::try_unwrap(self.app.0) {
+            Ok(app) => app.into_inner(),
+            Err(_) => unreachable!("scripts can't keep the app after they finish"),
+        }
+    }
+}
+
+impl AppHandle {
+    /// Applies a board command, returning true if it changed the board and failing the script
+    /// if the command was rejected
+    fn apply(&mut self, command: BoardCommand) -> ScriptResult<bool> {
+        match self.0.borrow_mut().apply(command) {
+            BoardEffect::Rejected(reason) => Err(reason.into()),
+            effect => Ok(effect.changed()),
+        }
+    }
+
+    /// Types a word at the cursor
+    fn type_word(&mut self, word: &str) -> ScriptResult<()> {
+        for c in word.chars() {
+            self.apply(BoardCommand::Add(c.to_ascii_uppercase()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a row for a guess with its colours as a pattern of 'b' or '.' for gray, 'y' for
+    /// yellow, 'g' for green and '?' for no colour (eg. bbygb)
+    fn guess(&mut self, word: &str, pattern: &str) -> ScriptResult<()> {
+        if !word.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("{word} isn't a word").into());
+        }
+
+        let BoardState(rows) = format!("{word}:{pattern}").parse()?;
+
+        match rows.into_iter().next() {
+            Some(row) => self.add_row(row),
+            None => Err(format!("{word} isn't a word").into()),
+        }
+    }
+
+    /// Adds a row for a guess coloured against an answer
+    fn play(&mut self, word: &str, answer: &str) -> ScriptResult<()> {
+        let (word, answer) = words(word, answer)?;
+
+        self.add_row(feedback(&word, &answer))
+    }
+
+    /// Adds a complete row after the complete rows on the board
+    fn add_row(&mut self, row: BoardRow) -> ScriptResult<()> {
+        let mut rows = self.rows()?;
+        rows.push(row);
+
+        self.apply(BoardCommand::Import(rows))?;
+
+        Ok(())
+    }
+
+    /// Returns the complete rows on the board, failing if a row has only been partly typed
+    fn rows(&self) -> ScriptResult<Vec<BoardRow>> {
+        let app = self.0.borrow();
+
+        let rows: Vec<BoardRow> = app
+            .board()
+            .iter()
+            .take_while(|row| row.iter().all(|elem| !matches!(elem, BoardElem::Empty)))
+            .cloned()
+            .collect();
+
+        if app.board()[rows.len()..]
+            .iter()
+            .flatten()
+            .any(|elem| !matches!(elem, BoardElem::Empty))
+        {
+            return Err("Finish or remove the word being typed first".into());
+        }
+
+        Ok(rows)
+    }
+
+    /// Sets the fewest and most times a letter appears
+    fn set_count(&mut self, c: char, min: INT, max: Option<INT>) -> ScriptResult<bool> {
+        let count = |n: INT| u8::try_from(n).map_err(|_| format!("{n} isn't a letter count"));
+
+        let letter = c.to_ascii_uppercase();
+        let min = count(min)?;
+        let max = max.map(count).transpose()?;
+
+        self.apply(BoardCommand::SetCount { letter, min, max })
+    }
+
+    /// Returns the words fitting the board in the current order, calculating them if needed
+    /// Words are found once a row is complete or a letter count is set
+    fn candidates(&mut self) -> Array {
+        let mut app = self.0.borrow_mut();
+        app.calculate();
+
+        (0..app.words().count().unwrap_or_default())
+            .filter_map(|i| app.get_word(i))
+            .map(Dynamic::from)
+            .collect()
+    }
+
+    /// Returns the best guess, ranking the words if they aren't already, or () if there are no
+    /// words
+    fn best(&mut self) -> Dynamic {
+        let mut app = self.0.borrow_mut();
+
+        if !app.ranking() {
+            app.set_ranking(true);
+        }
+
+        app.calculate();
+
+        app.best_word()
+            .and_then(|elem| app.get_word(elem))
+            .map_or(Dynamic::UNIT, Dynamic::from)
+    }
+
+    /// Returns true if the last complete row is all green
+    fn solved(&mut self) -> bool {
+        self.rows()
+            .ok()
+            .and_then(|rows| rows.last().cloned())
+            .is_some_and(|row| row.iter().all(|elem| matches!(elem, BoardElem::Green(_))))
+    }
+}
+
+/// Converts a script integer to a row or column number
+fn index(n: INT) -> ScriptResult<usize> {
+    usize::try_from(n).map_err(|_| format!("{n} isn't a row or column").into())
+}
+
+/// Converts a guess and an answer to upper case, checking they are words of the same length
+fn words(guess: &str, answer: &str) -> ScriptResult<(String, String)> {
+    if guess.is_empty()
+        || !guess
+            .chars()
+            .chain(answer.chars())
+            .all(|c| c.is_ascii_alphabetic())
+    {
+        return Err(format!("{guess} and {answer} must be words").into());
+    }
+
+    if guess.len() != answer.len() {
+        return Err(format!("{guess} and {answer} are different lengths").into());
+    }
+
+    Ok((guess.to_ascii_uppercase(), answer.to_ascii_uppercase()))
+}
+
+/// Parses a tile colour name
+fn tile_colour(colour: &str) -> ScriptResult<TileColour> {
+    match colour.to_ascii_lowercase().as_str() {
+        "gray" | "grey" => Ok(TileColour::Gray),
+        "yellow" => Ok(TileColour::Yellow),
+        "green" => Ok(TileColour::Green),
+        _ => Err(format!("{colour} isn't a colour, use gray, yellow or green").into()),
+    }
+}
+
+/// Returns the colour pattern of a row (eg. bbygb)
+fn pattern(row: &BoardRow) -> String {
+    let state = BoardState::from(vec![row.clone()]).to_string();
+
+    state
+        .split_once(':')
+        .map(|(_, pattern)| pattern.to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::{Dictionary, WordCase};
+
+    use super::*;
+
+    fn script() -> Script {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank\ntrace",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        Script::new(SolveApp::new(dictionary))
+    }
+
+    #[test]
+    fn commands() {
+        let script = script();
+
+        script
+            .run(
+                r#"
+                app.type_word("crane");
+                app.set_colour(0, 1, "green");
+                app.set_colour(0, 2, "green");
+                app.toggle(0, 3);
+                app.toggle(0, 3);
+                app.toggle(0, 3);
+
+                if app.candidates() != ["BRAKE", "TRACE"] {
+                    throw app.candidates();
+                }
+                "#,
+            )
+            .unwrap();
+
+        let app = script.into_app();
+
+        assert_eq!(
+            BoardState::from(app.board().clone()).to_string(),
+            "CRANE:?ggb?"
+        );
+    }
+
+    #[test]
+    fn queries() {
+        let script = script();
+
+        script
+            .run(
+                r#"
+                app.guess("CRANE", "bggbb");
+
+                if app.board() != "CRANE:bggbb" { throw app.board(); }
+                if app.letter_count('R') != [1, ()] { throw app.letter_count('R'); }
+                if app.letter_count('C') != [0, 0] { throw app.letter_count('C'); }
+                if feedback("crane", "trace") != "yggbg" { throw "feedback"; }
+                if app.solved() { throw "solved"; }
+                "#,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn play() {
+        let script = script();
+
+        script
+            .run(
+                r#"
+                while !app.solved() {
+                    app.play(app.best(), "DRANK");
+                }
+                "#,
+            )
+            .unwrap_err();
+
+        // The board is empty so the first guess must be given
+        let script = self::script();
+
+        script
+            .run(
+                r#"
+                app.play("BRAKE", "DRANK");
+
+                while !app.solved() {
+                    app.play(app.best(), "DRANK");
+                }
+                "#,
+            )
+            .unwrap();
+
+        let app = script.into_app();
+        let state = BoardState::from(app.board().clone()).to_string();
+
+        assert!(state.starts_with("BRAKE:bggyb/"));
+        assert!(state.ends_with("DRANK:ggggg"));
+    }
+
+    #[test]
+    fn errors() {
+        let script = script();
+
+        // Rejected commands fail the script with the reason
+        let e = script.run("app.toggle(0, 9);").unwrap_err();
+        assert!(e.to_string().contains("off the board"), "{e}");
+
+        let e = script.run(r#"app.guess("CRANE", "bgx");"#).unwrap_err();
+        assert!(e.to_string().contains("isn't 5 colours long"), "{e}");
+
+        let e = script
+            .run("app.type_word(\"CR\"); app.play(\"CRANE\", \"TRACE\");")
+            .unwrap_err();
+        assert!(e.to_string().contains("Finish or remove"), "{e}");
+    }
+}
@@ -0,0 +1,182 @@
+//! Persistent user settings
+
+use std::fs::{create_dir_all, read_to_string, write};
+use std::io;
+use std::path::PathBuf;
+
+use crate::paths::config_path;
+use crate::Strategy;
+
+/// Settings file name
+const SETTINGS_FILE: &str = "settings.txt";
+
+/// Address the default language pack is downloaded from
+const DEFAULT_PACK_URL: &str =
+    "https://github.com/andywarduk/wordle/releases/latest/download/english.pack";
+
+/// Colour theme choice
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeMode {
+    /// Follow the system dark or light preference
+    #[default]
+    System,
+    /// Light theme
+    Light,
+    /// Dark theme
+    Dark,
+}
+
+impl std::str::FromStr for ThemeMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "system" => Ok(Self::System),
+            "light" => Ok(Self::Light),
+            "dark" => Ok(Self::Dark),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for ThemeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::System => "system",
+            Self::Light => "light",
+            Self::Dark => "dark",
+        })
+    }
+}
+
+/// User settings saved between runs
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// First run tutorial has been seen
+    pub tutorial_seen: bool,
+    /// Fill columns with known green letters when typing a new row
+    pub lock_greens: bool,
+    /// Mark letters proven absent by earlier rows gray when typed
+    pub mark_absent: bool,
+    /// Leave guesses which aren't in the word list out of the calculation
+    pub reject_unknown: bool,
+    /// Reject colour changes which contradict the rest of the board
+    pub strict: bool,
+    /// Group the found words by their first letter
+    pub group_words: bool,
+    /// Show the words eliminated by the latest edit
+    pub show_eliminated: bool,
+    /// Word ranking strategy
+    pub strategy: Strategy,
+    /// Colour theme for the gui
+    pub theme: ThemeMode,
+    /// Gui zoom level in percent
+    pub zoom: u16,
+    /// Offer to download a language pack on first run has been answered
+    pub pack_offered: bool,
+    /// Address the gui downloads the default language pack from
+    pub pack_url: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            tutorial_seen: false,
+            lock_greens: false,
+            mark_absent: false,
+            reject_unknown: false,
+            strict: false,
+            group_words: false,
+            show_eliminated: false,
+            strategy: Strategy::default(),
+            theme: ThemeMode::default(),
+            zoom: 100,
+            pack_offered: false,
+            pack_url: DEFAULT_PACK_URL.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Loads the settings from the settings file, returning defaults if the file can't be read
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| read_to_string(path).ok())
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    /// Saves the settings to the settings file
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = Self::path() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Unable to determine configuration directory",
+            ));
+        };
+
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+
+        write(path, self.to_string())
+    }
+
+    /// Parses settings file contents. Unknown or invalid lines are ignored
+    fn parse(contents: &str) -> Self {
+        let mut settings = Self::default();
+
+        for line in contents.lines() {
+            if let Some((name, value)) = line.split_once('=') {
+                let value = value.trim();
+
+                match name.trim() {
+                    "tutorial_seen" => Self::parse_value(value, &mut settings.tutorial_seen),
+                    "lock_greens" => Self::parse_value(value, &mut settings.lock_greens),
+                    "mark_absent" => Self::parse_value(value, &mut settings.mark_absent),
+                    "reject_unknown" => Self::parse_value(value, &mut settings.reject_unknown),
+                    "strict" => Self::parse_value(value, &mut settings.strict),
+                    "group_words" => Self::parse_value(value, &mut settings.group_words),
+                    "show_eliminated" => Self::parse_value(value, &mut settings.show_eliminated),
+                    "strategy" => Self::parse_value(value, &mut settings.strategy),
+                    "theme" => Self::parse_value(value, &mut settings.theme),
+                    "zoom" => Self::parse_value(value, &mut settings.zoom),
+                    "pack_offered" => Self::parse_value(value, &mut settings.pack_offered),
+                    "pack_url" => Self::parse_value(value, &mut settings.pack_url),
+                    _ => (),
+                }
+            }
+        }
+
+        settings
+    }
+
+    /// Parses a setting value, leaving the setting unchanged if invalid
+    fn parse_value<T: std::str::FromStr>(value: &str, setting: &mut T) {
+        if let Ok(value) = value.parse() {
+            *setting = value;
+        }
+    }
+
+    /// Returns the path to the settings file
+    fn path() -> Option<PathBuf> {
+        config_path(SETTINGS_FILE)
+    }
+}
+
+impl std::fmt::Display for Settings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "tutorial_seen = {}", self.tutorial_seen)?;
+        writeln!(f, "lock_greens = {}", self.lock_greens)?;
+        writeln!(f, "mark_absent = {}", self.mark_absent)?;
+        writeln!(f, "reject_unknown = {}", self.reject_unknown)?;
+        writeln!(f, "strict = {}", self.strict)?;
+        writeln!(f, "group_words = {}", self.group_words)?;
+        writeln!(f, "show_eliminated = {}", self.show_eliminated)?;
+        writeln!(f, "strategy = {}", self.strategy)?;
+        writeln!(f, "theme = {}", self.theme)?;
+        writeln!(f, "zoom = {}", self.zoom)?;
+        writeln!(f, "pack_offered = {}", self.pack_offered)?;
+        writeln!(f, "pack_url = {}", self.pack_url)
+    }
+}
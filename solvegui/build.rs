@@ -0,0 +1,185 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+use serde_json::Value;
+
+/// Prefixes of the license files copied from each crate
+const LICENSE_FILES: [&str; 4] = ["LICENSE", "LICENCE", "COPYING", "NOTICE"];
+
+fn main() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    // List the third party crates built into the gui with their licenses
+    let (summary, texts) = match licenses() {
+        Ok(licenses) => licenses,
+        Err(e) => {
+            println!("cargo:warning=Unable to collect the license notices: {e}");
+
+            let text = format!("License notices are unavailable in this build: {e}\n");
+
+            (text.clone(), text)
+        }
+    };
+
+    fs::write(out_dir.join("licenses.txt"), summary).unwrap();
+    fs::write(out_dir.join("license_texts.txt"), texts).unwrap();
+
+    println!("cargo:rerun-if-changed=../Cargo.lock");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    // Embed the icon and manifest in the Windows executable
+    #[cfg(windows)]
+    {
+        let mut resource = winres::WindowsResource::new();
+
+        resource
+            .set_icon("assets/wordle_logo.ico")
+            .set_manifest_file("assets/solvegui.manifest");
+
+        resource.compile().unwrap();
+
+        println!("cargo:rerun-if-changed=assets/wordle_logo.ico");
+        println!("cargo:rerun-if-changed=assets/solvegui.manifest");
+    }
+}
+
+/// Returns a line for each crate the gui depends on with its version and license, and the text
+/// of every license file, each followed by the crates it came from
+fn licenses() -> Result<(String, String), String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let target = env::var("TARGET").unwrap();
+
+    let output = Command::new(cargo)
+        .args(["metadata", "--format-version", "1", "--offline"])
+        .args(["--filter-platform", &target])
+        .output()
+        .map_err(|e| format!("Unable to run cargo metadata: {e}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let metadata: Value =
+        serde_json::from_slice(&output.stdout).map_err(|e| format!("Bad cargo metadata: {e}"))?;
+
+    let packages: HashMap<&str, &Value> = metadata["packages"]
+        .as_array()
+        .ok_or("No packages in cargo metadata")?
+        .iter()
+        .filter_map(|package| Some((package["id"].as_str()?, package)))
+        .collect();
+
+    let nodes: HashMap<&str, &Value> = metadata["resolve"]["nodes"]
+        .as_array()
+        .ok_or("No dependency graph in cargo metadata")?
+        .iter()
+        .filter_map(|node| Some((node["id"].as_str()?, node)))
+        .collect();
+
+    let name = env::var("CARGO_PKG_NAME").unwrap();
+
+    let root = packages
+        .values()
+        .find(|package| package["name"] == name.as_str())
+        .and_then(|package| package["id"].as_str())
+        .ok_or("Gui package missing from cargo metadata")?;
+
+    // Walk the normal dependencies from the gui
+    let mut seen = HashSet::new();
+    let mut stack = vec![root];
+
+    while let Some(id) = stack.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+
+        let deps = nodes.get(id).and_then(|node| node["deps"].as_array());
+
+        for dep in deps.into_iter().flatten() {
+            let normal = dep["dep_kinds"]
+                .as_array()
+                .is_some_and(|kinds| kinds.iter().any(|kind| kind["kind"].is_null()));
+
+            if let (true, Some(pkg)) = (normal, dep["pkg"].as_str()) {
+                stack.push(pkg);
+            }
+        }
+    }
+
+    // Leave out the workspace crates
+    let mut crates: Vec<&Value> = seen
+        .into_iter()
+        .filter_map(|id| packages.get(id).copied())
+        .filter(|package| !package["source"].is_null())
+        .collect();
+
+    crates.sort_by_key(|package| (package["name"].as_str(), package["version"].as_str()));
+
+    let mut summary = String::new();
+    let mut texts: HashMap<String, Vec<String>> = HashMap::new();
+
+    for package in crates {
+        let name = format!(
+            "{} {}",
+            package["name"].as_str().unwrap_or_default(),
+            package["version"].as_str().unwrap_or_default()
+        );
+        let license = package["license"].as_str().unwrap_or("see license file");
+
+        summary.push_str(&format!("{name}: {license}\n"));
+
+        // Identical license files are shared between crates so are only included once
+        let dir = package["manifest_path"]
+            .as_str()
+            .and_then(|path| Path::new(path).parent())
+            .ok_or("Package without a manifest path")?;
+
+        for text in license_files(dir) {
+            texts.entry(text).or_default().push(name.clone());
+        }
+    }
+
+    // List the license texts in the order of the first crate using each
+    let mut texts: Vec<(String, Vec<String>)> = texts.into_iter().collect();
+    texts.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+    let texts = texts
+        .into_iter()
+        .map(|(text, names)| format!("==== {}\n\n{}\n", names.join(", "), text.trim_end()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok((summary, texts))
+}
+
+/// Returns the contents of the license files in a crate's directory
+fn license_files(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        LICENSE_FILES
+                            .iter()
+                            .any(|prefix| name.to_ascii_uppercase().starts_with(prefix))
+                    })
+        })
+        .collect();
+
+    files.sort();
+
+    files
+        .into_iter()
+        .filter_map(|path| fs::read(path).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .collect()
+}
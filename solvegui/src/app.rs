@@ -1,14 +1,99 @@
-use dictionary::Dictionary;
+use std::time::Duration;
+use std::{cmp, fmt, thread};
+
+use chrono::{Datelike, Months, NaiveDate, TimeDelta};
+use dictionary::{Dictionary, History, WordCase};
+use iced::futures::channel::mpsc;
 use iced::keyboard::key::Named;
 use iced::keyboard::{self, Key, Modifiers};
-use iced::widget::{button, container, row, text, Column, Lazy, Responsive, Row, Space};
+use iced::widget::{
+    button,
+    center,
+    checkbox,
+    column,
+    container,
+    mouse_area,
+    opaque,
+    pick_list,
+    progress_bar,
+    rich_text,
+    row,
+    scrollable,
+    slider,
+    span,
+    stack,
+    text,
+    text_input,
+    Column,
+    Lazy,
+    Responsive,
+    Row,
+    Space,
+};
 use iced::window::icon::from_rgba;
 use iced::window::{self, Settings as WinSettings};
-use iced::{Color, Element, Length, Size, Subscription, Task};
-use solveapp::{SolveApp, Words, BOARD_COLS, BOARD_ROWS};
+use iced::{
+    clipboard,
+    event,
+    font,
+    mouse,
+    time,
+    Alignment,
+    Border,
+    Color,
+    Element,
+    Event,
+    Font,
+    Length,
+    Size,
+    Subscription,
+    Task,
+    Theme,
+};
+use log::debug;
+use solveapp::board_image::save_board_image;
+use solveapp::packs::{LanguagePack, DEFAULT_PACK};
+use solveapp::{
+    build_info,
+    paths,
+    BoardCommand,
+    BoardEffect,
+    BoardRow,
+    CountBound,
+    ExtraWords,
+    Preview,
+    Settings,
+    SolveApp,
+    ThemeMode,
+    TileColour,
+    Words,
+    BOARD_ROWS,
+};
+
+use crate::LICENSES;
+
+/// Word list the solver starts with and where it came from, so another can be chosen
+pub struct WordList {
+    /// Words which can be the answer
+    pub dictionary: Dictionary,
+    /// Extra words accepted as guesses, from a language pack
+    pub guesses: Option<Dictionary>,
+    /// Word list file used when no language pack is chosen
+    pub file: String,
+    /// Letter case handling when loading word lists
+    pub case: WordCase,
+    /// Language pack loaded, if any
+    pub pack: Option<String>,
+}
 
 /// Run the GUI solver
-pub fn rungui(dictionary: Dictionary) -> iced::Result {
+pub fn rungui(
+    words: WordList,
+    board: Vec<BoardRow>,
+    history: Option<History>,
+    about: Vec<String>,
+    rank: bool,
+) -> iced::Result {
     // Build icon
     let icon = from_rgba(
         include_bytes!("../assets/wordle_logo_192x192.rgba").to_vec(),
@@ -17,30 +102,67 @@ pub fn rungui(dictionary: Dictionary) -> iced::Result {
     )
     .unwrap();
 
-    // Work out min and initial dimensions
-    let board_dim = |btn_count: usize| {
-        ((BUTTON_DIM * btn_count as u16) + (BOARD_SPACING * (btn_count as u16 - 1)) + (PADDING * 2))
-            as f32
-    };
+    let settings = Settings::load();
 
-    let words_w = |word_count: u16| ((WORD_WIDTH * word_count) + (PADDING * 2)) as f32;
+    // Work out min and initial dimensions
+    let cols = words.dictionary.word_len();
+    let zoom = settings.zoom.clamp(MIN_ZOOM, MAX_ZOOM);
 
-    let min_w = board_dim(BOARD_COLS);
-    let min_h = board_dim(BOARD_ROWS);
+    let min = min_size(cols, zoom);
 
-    let w = min_w + words_w(4);
-    let h = min_h * 1.5;
+    let w = min.width + (entry_width(cols, zoom) * 4.0) + (PADDING * 2) as f32;
+    let h = min.height * 2.0;
 
     // Run the app
     iced::application("Wordle Solver", App::update, App::view)
         .subscription(App::subscription)
+        .theme(App::theme)
         .window(WinSettings {
             icon: Some(icon),
             size: Size::new(w, h),
-            min_size: Some(Size::new(min_w, min_h)),
+            min_size: Some(min),
             ..WinSettings::default()
         })
-        .run_with(|| App::new(dictionary))
+        .run_with(move || App::new(words, settings, &board, history, about, rank))
+}
+
+/// Scales a dimension by a zoom level in percent
+fn scaled(dim: u16, zoom: u16) -> f32 {
+    (dim as u32 * zoom as u32) as f32 / 100.0
+}
+
+/// Returns the minimum window size to fit a board with the given number of columns
+fn min_size(cols: usize, zoom: u16) -> Size {
+    let board_dim = |btn_count: usize| {
+        (scaled(BUTTON_DIM, zoom) * btn_count as f32)
+            + (scaled(BOARD_SPACING, zoom) * (btn_count - 1) as f32)
+            + (PADDING * 2) as f32
+    };
+
+    Size::new(board_dim(cols), board_dim(BOARD_ROWS))
+}
+
+/// Returns the width of a word text element for words of the given length
+fn word_width(cols: usize, zoom: u16) -> f32 {
+    scaled(LETTER_WIDTH, zoom) * cols as f32
+}
+
+/// Returns the width of a word list entry, a word with a pin button in front, for words of the
+/// given length
+fn entry_width(cols: usize, zoom: u16) -> f32 {
+    word_width(cols + 1, zoom)
+}
+
+/// Returns the text colour for a word score from 0 (the worst, red) through yellow to 1 (the
+/// best, green)
+fn heat_colour(heat: f32) -> Color {
+    let heat = heat.clamp(0.0, 1.0);
+
+    Color::from_rgb(
+        HEAT_BRIGHTNESS * (2.0 * (1.0 - heat)).min(1.0),
+        HEAT_BRIGHTNESS * (2.0 * heat).min(1.0),
+        0.0,
+    )
 }
 
 /// Dimension of board button
@@ -49,74 +171,1294 @@ const BUTTON_DIM: u16 = 40;
 const BOARD_SPACING: u16 = 8;
 /// Height of each word text element
 const WORD_HEIGHT: u16 = 25;
-/// Width of each word text element
-const WORD_WIDTH: u16 = 90;
+/// Width of each letter of a word text element
+const LETTER_WIDTH: u16 = 18;
 /// Element padding
 const PADDING: u16 = 10;
+/// Dimension of legend colour tile
+const LEGEND_DIM: u16 = 16;
+/// Text size
+const TEXT_SIZE: u16 = 16;
+/// Board letter and heading text size
+const LARGE_TEXT_SIZE: u16 = 20;
+/// Footer text size
+const SMALL_TEXT_SIZE: u16 = 14;
+
+/// Smallest zoom level in percent
+const MIN_ZOOM: u16 = 50;
+/// Largest zoom level in percent
+const MAX_ZOOM: u16 = 200;
+/// Zoom level change for each key press
+const ZOOM_STEP: u16 = 10;
+/// How often to check whether the background ranking has finished
+const RANK_POLL: Duration = Duration::from_millis(50);
+
+/// Tile colours for a theme
+struct Tiles {
+    unknown: Color,
+    gray: Color,
+    yellow: Color,
+    green: Color,
+}
+
+/// Tile colours for the dark theme
+const DARK_TILES: Tiles = Tiles {
+    unknown: Color::from_rgb(0.1, 0.1, 0.1),
+    gray: Color::from_rgb(0.3, 0.3, 0.3),
+    yellow: Color::from_rgb(0.8, 0.8, 0.0),
+    green: Color::from_rgb(0.0, 0.8, 0.0),
+};
+
+/// Tile colours for the light theme
+const LIGHT_TILES: Tiles = Tiles {
+    unknown: Color::from_rgb(0.2, 0.2, 0.2),
+    gray: Color::from_rgb(0.47, 0.49, 0.5),
+    yellow: Color::from_rgb(0.79, 0.71, 0.35),
+    green: Color::from_rgb(0.42, 0.67, 0.39),
+};
+/// Brightness of the word score colours, dark enough to read on the light theme
+const HEAT_BRIGHTNESS: f32 = 0.75;
+/// Number of colour patterns listed in the word preview
+const PREVIEW_GROUPS: usize = 5;
+/// Border colour for rows which aren't dictionary words
+const INVALID: Color = Color::from_rgb(0.9, 0.1, 0.1);
+
+/// Colour of the words eliminated by the last edit
+const ELIMINATED: Color = Color::from_rgb(0.5, 0.5, 0.5);
+
+/// Width of the word search box
+const SEARCH_WIDTH: u16 = 300;
+
+/// Colour of the letters matching the word search
+const SEARCH_MATCH: Color = Color::from_rgb(0.8, 0.3, 0.9);
+
+/// Outline colour of the part of the window operated with the keyboard
+const FOCUS: Color = Color::from_rgb(0.2, 0.5, 1.0);
+/// Width of the keyboard focus outline
+const FOCUS_WIDTH: f32 = 3.0;
+
+/// Widget id of the word search box
+const SEARCH_ID: &str = "search";
+/// Widget id of the grouped word list scroller
+const WORDS_ID: &str = "words";
+/// Widget id of the letter counts scroller
+const COUNTS_ID: &str = "counts";
+
+/// File name the board image is exported to
+const EXPORT_FILE: &str = "wordle-board.png";
+
+/// Number of items in the tile context menu
+const MENU_ITEMS: usize = 6;
+
+/// Calendar column headings
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// First run tutorial steps (title, text)
+const TUTORIAL: [(&str, &str); 3] = [
+    (
+        "Enter a guess",
+        "Type the letters of the word you guessed in the game to fill a row of the board. \
+        Backspace removes the last letter.",
+    ),
+    (
+        "Set the colours",
+        "Click a letter or press a number key to toggle its colour between yellow, green and gray \
+        until the row matches the colours the game gave you. Letters left without a colour turn \
+        gray when you type the next guess.",
+    ),
+    (
+        "Choose the next guess",
+        "The words which fit the board are listed on the right. \
+        Choose one as your next guess and repeat until solved.",
+    ),
+];
+
+/// Word list chosen in the language pack list
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PackChoice {
+    /// Word list file given on the command line or found by default
+    Default,
+    /// Language pack installed in the packs directory
+    Pack(String),
+}
+
+impl fmt::Display for PackChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("Default word list"),
+            Self::Pack(name) => f.write_str(name),
+        }
+    }
+}
+
+/// State of the offer to download the default language pack
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PackOffer {
+    /// Asking whether to download it
+    Asking,
+    /// Downloading, with the bytes received and the total if known
+    Downloading(u64, Option<u64>),
+    /// Download failed
+    Failed(String),
+}
+
+/// Language pack download progress sent from the download thread
+#[derive(Debug, Clone)]
+enum Download {
+    /// Bytes received and the total if known
+    Progress(u64, Option<u64>),
+    /// Download finished
+    Done(Result<LanguagePack, String>),
+}
+
+/// Arrow key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Arrow {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Part of the window operated with the keyboard, moved between with Tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    /// Board tile in a column of the active row
+    Tile(usize),
+    /// Highlighted word in the word list
+    Words,
+    /// Word search box
+    Search,
+    /// Offer to add a guess missing from the word list
+    AddWord,
+    /// Word grouping checkbox
+    Group,
+    /// Past answers button
+    Calendar,
+    /// Letter counts button
+    Counts,
+    /// Letter information button
+    Letters,
+    /// About button
+    About,
+}
 
 #[derive(Debug, Clone)]
 enum Message {
     Quit,
     LetterAdded(char),
     LetterRemoved,
-    Toggle(usize, usize),
+    PaintStart(usize, usize),
+    PaintOver(usize, usize),
+    PaintEnd,
+    TileMenu(usize, usize),
+    SetColour(usize, usize, TileColour),
+    ClearColour(usize, usize),
+    MenuClose,
+    CalendarOpen,
+    CalendarMonth(NaiveDate),
+    CalendarSelect(NaiveDate),
+    CalendarClose,
+    AboutOpen,
+    AboutClose,
+    CountsOpen,
+    CountsClose,
+    CountStep(char, CountBound, bool),
+    CountClear(char),
+    CountsClear,
+    LettersOpen,
+    LettersClose,
+    SelectPack(PackChoice),
+    PackDownload,
+    PackProgress(Download),
+    PackOfferClose,
+    CopyBoard,
+    ExportImage,
+    TogglePin(usize),
+    Preview(usize),
+    PreviewClose,
     ToggleCol(usize),
+    GroupWords(bool),
+    Search(String),
+    SelectRow(bool),
+    JumpRow(usize),
+    TutorialNext,
+    TutorialClose,
+    AddUnknownWord,
+    Zoom(u16),
+    ZoomIn,
+    ZoomOut,
+    ZoomSave,
+    FocusNext,
+    FocusPrev,
+    Arrow(Arrow),
+    Enter,
+    Space(bool),
+    CountKey(char),
+    RankPoll,
 }
 
 struct App {
     app: SolveApp,
+    settings: Settings,
+    tutorial_step: Option<usize>,
+    dark: bool,
+    zoom: u16,
+    /// Row and colour being painted while the mouse button is held
+    paint: Option<(usize, TileColour)>,
+    /// Row and column of the tile the context menu is showing for
+    menu: Option<(usize, usize)>,
+    /// Past answers
+    history: Option<History>,
+    /// First day of the month the calendar is showing
+    calendar: Option<NaiveDate>,
+    /// Day chosen in the calendar to show the answer for
+    calendar_day: Option<NaiveDate>,
+    /// Words left by each colour pattern for the word under the mouse
+    preview: Option<Preview>,
+    /// Solver build and word list details
+    about: Vec<String>,
+    /// Showing the about card
+    about_open: bool,
+    /// Showing the letter counts card
+    counts_open: bool,
+    /// Showing the letter information card
+    letters_open: bool,
+    /// Result of the last board export
+    notice: Option<String>,
+    /// Part of the window operated with the keyboard
+    focus: Option<Focus>,
+    /// Word list index of the word highlighted with the keyboard
+    highlight: Option<usize>,
+    /// Context menu item chosen with the keyboard
+    menu_item: usize,
+    /// Letter chosen with the keyboard in the letter counts card
+    counts_letter: char,
+    /// Language packs installed
+    packs: Vec<LanguagePack>,
+    /// Word list being used
+    pack: PackChoice,
+    /// Word list file used when no language pack is chosen
+    dictionary_file: String,
+    /// Letter case handling when loading word lists
+    case: WordCase,
+    /// Offer to download the default language pack, if showing
+    offer: Option<PackOffer>,
 }
 
 impl App {
-    /// Create new GUI app
-    fn new(dictionary: Dictionary) -> (Self, Task<Message>) {
+    /// Create new GUI app, starting with an imported board
+    fn new(
+        words: WordList,
+        settings: Settings,
+        board: &[BoardRow],
+        history: Option<History>,
+        about: Vec<String>,
+        rank: bool,
+    ) -> (Self, Task<Message>) {
+        // Show the tutorial on first run
+        let tutorial_step = if settings.tutorial_seen {
+            None
+        } else {
+            Some(0)
+        };
+
+        // Offer to download a language pack on first run, or whenever there are no words
+        let packs = LanguagePack::scan();
+        let no_words = words.dictionary.word_count() == 0;
+
+        let offer =
+            (packs.is_empty() && (no_words || !settings.pack_offered)).then_some(PackOffer::Asking);
+
+        let mut app = Self::solve_app(words.dictionary, words.guesses, &settings, rank);
+
+        if let BoardEffect::Rejected(reason) = app.apply(BoardCommand::Import(board.to_vec())) {
+            eprintln!("Unable to import the board: {reason}");
+        } else if !board.is_empty() {
+            app.calculate();
+        }
+
+        // Use the system dark or light preference unless overridden
+        let dark = match settings.theme {
+            ThemeMode::System => dark_light::detect() == dark_light::Mode::Dark,
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+        };
+
+        let zoom = settings.zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+
+        let counts_letter = app.alphabet().first().copied().unwrap_or('A');
+
         (
             Self {
-                app: SolveApp::new(dictionary),
+                app,
+                settings,
+                tutorial_step,
+                dark,
+                zoom,
+                paint: None,
+                menu: None,
+                history,
+                calendar: None,
+                calendar_day: None,
+                preview: None,
+                about,
+                about_open: false,
+                counts_open: false,
+                letters_open: false,
+                notice: None,
+                focus: None,
+                highlight: None,
+                menu_item: 0,
+                counts_letter,
+                packs,
+                pack: words.pack.map_or(PackChoice::Default, PackChoice::Pack),
+                dictionary_file: words.file,
+                case: words.case,
+                offer,
             },
             Task::none(),
         )
     }
 
+    /// Creates the solver for a word list with the extra words added and the settings applied
+    fn solve_app(
+        dictionary: Dictionary,
+        guesses: Option<Dictionary>,
+        settings: &Settings,
+        rank: bool,
+    ) -> SolveApp {
+        let mut app = SolveApp::new(dictionary);
+
+        if let Some(guesses) = guesses {
+            app.set_guesses(guesses);
+        }
+
+        if let Err(e) = app.add_words(&ExtraWords::load()) {
+            eprintln!("Unable to load extra words: {e}");
+        }
+
+        app.set_lock_greens(settings.lock_greens);
+        app.set_mark_absent(settings.mark_absent);
+        app.set_reject_unknown(settings.reject_unknown);
+        app.set_strict(settings.strict);
+        app.set_show_eliminated(settings.show_eliminated);
+        app.set_strategy(settings.strategy);
+        app.set_ranking(rank);
+
+        // Rank words without holding up the window
+        app.set_background(true);
+
+        app
+    }
+
+    /// Downloads the default language pack on another thread, sending the progress back as
+    /// messages
+    fn download_pack(&mut self) -> Task<Message> {
+        let url = self.settings.pack_url.clone();
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            let result = LanguagePack::download(&url, DEFAULT_PACK, |received, total| {
+                let _ = sender.unbounded_send(Download::Progress(received, total));
+            })
+            .map_err(|e| e.to_string());
+
+            let _ = sender.unbounded_send(Download::Done(result));
+        });
+
+        self.offer = Some(PackOffer::Downloading(0, None));
+
+        Task::run(receiver, Message::PackProgress)
+    }
+
+    /// Closes the language pack offer and records it as answered
+    fn close_offer(&mut self) {
+        self.offer = None;
+
+        if !self.settings.pack_offered {
+            self.settings.pack_offered = true;
+
+            if let Err(e) = self.settings.save() {
+                eprintln!("Unable to save settings: {e}");
+            }
+        }
+    }
+
+    /// Switches to another word list, starting a new board sized for its words
+    fn select_pack(&mut self, choice: PackChoice) -> Task<Message> {
+        let loaded = match &choice {
+            PackChoice::Default => {
+                Dictionary::new_from_file(&self.dictionary_file, self.case, false)
+                    .map(|dictionary| (dictionary, None, self.dictionary_file.clone()))
+            }
+            PackChoice::Pack(name) => LanguagePack::find(name).and_then(|pack| {
+                let (answers, guesses) = pack.load(self.case, false)?.into_parts();
+
+                Ok((answers, Some(guesses), pack.file()))
+            }),
+        };
+
+        match loaded {
+            Ok((dictionary, guesses, file)) => {
+                self.about = build_info(&file, &dictionary);
+                self.app = Self::solve_app(dictionary, guesses, &self.settings, self.app.ranking());
+                self.counts_letter = self.app.alphabet().first().copied().unwrap_or('A');
+                self.highlight = None;
+                self.notice = None;
+                self.pack = choice;
+
+                // The board may have more columns than the window fits
+                self.set_zoom(self.zoom)
+            }
+            Err(e) => {
+                self.notice = Some(format!("Unable to load {choice}: {e}"));
+                Task::none()
+            }
+        }
+    }
+
     /// Update the state given a message
     fn update(&mut self, message: Message) -> Task<Message> {
+        // Checking for the word scores happens too often to log
+        if !matches!(message, Message::RankPoll) {
+            debug!("Message {message:?}");
+        }
+
+        // Moving the keyboard focus or checking for the word scores doesn't change the word list
+        let navigating = matches!(
+            message,
+            Message::FocusNext
+                | Message::FocusPrev
+                | Message::Arrow(_)
+                | Message::Enter
+                | Message::Space(_)
+                | Message::RankPoll
+        );
+
+        // Anything other than hovering or pinning may change the word list under the preview
+        if !navigating
+            && !matches!(
+                message,
+                Message::Preview(_) | Message::PreviewClose | Message::TogglePin(_)
+            )
+        {
+            self.preview = None;
+        }
+
+        // The highlighted word is kept while only the words shown or their grouping change
+        if !navigating
+            && !matches!(
+                message,
+                Message::Preview(_)
+                    | Message::PreviewClose
+                    | Message::TogglePin(_)
+                    | Message::Search(_)
+                    | Message::GroupWords(_)
+            )
+        {
+            self.highlight = None;
+        }
+
+        // The export result is shown until the next action
+        if !matches!(
+            message,
+            Message::Preview(_) | Message::PreviewClose | Message::PaintEnd | Message::RankPoll
+        ) {
+            self.notice = None;
+        }
+
         match message {
-            Message::Quit => window::get_latest().and_then(window::close),
+            Message::Quit => {
+                if self.offer.is_some() {
+                    // Close the language pack offer instead of quitting, unless downloading
+                    if !matches!(self.offer, Some(PackOffer::Downloading(..))) {
+                        self.close_offer();
+                    }
+                    Task::none()
+                } else if self.menu.is_some() {
+                    // Close the context menu instead of quitting
+                    self.menu = None;
+                    Task::none()
+                } else if self.calendar.is_some() {
+                    // Close the calendar instead of quitting
+                    self.calendar = None;
+                    Task::none()
+                } else if self.about_open {
+                    // Close the about card instead of quitting
+                    self.about_open = false;
+                    Task::none()
+                } else if self.counts_open {
+                    // Close the letter counts card instead of quitting
+                    self.counts_open = false;
+                    Task::none()
+                } else if self.letters_open {
+                    // Close the letter information card instead of quitting
+                    self.letters_open = false;
+                    Task::none()
+                } else if self.tutorial_step.is_some() {
+                    // Close the tutorial instead of quitting
+                    self.close_tutorial();
+                    Task::none()
+                } else if self.focus.is_some() {
+                    // Stop operating with the keyboard instead of quitting
+                    self.set_focus(None)
+                } else {
+                    window::get_latest().and_then(window::close)
+                }
+            }
+            Message::LetterAdded(c) if self.counts_open => {
+                // Choose the letter to change in the letter counts card
+                self.choose_count_letter(c)
+            }
+            Message::LetterRemoved if self.counts_open => {
+                // Clear the count set for the chosen letter
+                let letter = self.counts_letter;
+
+                if self.apply(BoardCommand::SetCount {
+                    letter,
+                    min: 0,
+                    max: None,
+                }) {
+                    self.app.calculate()
+                }
+                Task::none()
+            }
             Message::LetterAdded(c) => {
                 // Add letter to the board
-                if self.app.add(c) {
+                if self.apply(BoardCommand::Add(c)) {
                     self.app.calculate()
                 }
                 Task::none()
             }
             Message::LetterRemoved => {
                 // Remove last letter from the board
-                if self.app.remove() {
+                if self.apply(BoardCommand::Remove) {
+                    self.app.calculate()
+                }
+                Task::none()
+            }
+            Message::PaintStart(row, col) => {
+                // Toggle a letter at position and paint its new colour across the row while the
+                // mouse button is held. The row is selected for the number keys
+                self.apply(BoardCommand::SelectRow(row));
+
+                if let Some(colour) = self.app.next_colour(row, col) {
+                    if self.apply(BoardCommand::SetColour { row, col, colour }) {
+                        self.app.calculate()
+                    }
+
+                    self.paint = Some((row, colour));
+                }
+                Task::none()
+            }
+            Message::PaintOver(row, col) => {
+                // Paint a letter dragged over on the same row
+                if let Some((paint_row, colour)) = self.paint {
+                    if row == paint_row && self.apply(BoardCommand::SetColour { row, col, colour })
+                    {
+                        self.app.calculate()
+                    }
+                }
+                Task::none()
+            }
+            Message::PaintEnd => {
+                // Mouse button released
+                self.paint = None;
+                Task::none()
+            }
+            Message::TileMenu(row, col) => {
+                // Show the context menu for a tile
+                self.menu = Some((row, col));
+                self.menu_item = 0;
+                Task::none()
+            }
+            Message::SetColour(row, col, colour) => {
+                // Set a tile colour from the context menu
+                self.menu = None;
+
+                if self.apply(BoardCommand::SetColour { row, col, colour }) {
+                    self.app.calculate()
+                }
+                Task::none()
+            }
+            Message::ClearColour(row, col) => {
+                // Clear a tile colour from the context menu
+                self.menu = None;
+
+                if self.apply(BoardCommand::ClearColour { row, col }) {
+                    self.app.calculate()
+                }
+                Task::none()
+            }
+            Message::MenuClose => {
+                // Close the context menu without changing anything
+                self.menu = None;
+                Task::none()
+            }
+            Message::CalendarOpen => {
+                // Show the calendar at the month of the latest answer
+                self.calendar = self
+                    .history
+                    .as_ref()
+                    .and_then(History::range)
+                    .and_then(|(_, last)| last.with_day(1));
+                self.calendar_day = None;
+                Task::none()
+            }
+            Message::CalendarMonth(month) => {
+                // Move the calendar to another month
+                self.calendar = Some(month);
+                self.calendar_day = None;
+                Task::none()
+            }
+            Message::CalendarSelect(date) => {
+                // Show the answer for a day
+                self.calendar_day = Some(date);
+                Task::none()
+            }
+            Message::CalendarClose => {
+                // Close the calendar
+                self.calendar = None;
+                Task::none()
+            }
+            Message::AboutOpen => {
+                // Show the about card
+                self.about_open = true;
+                Task::none()
+            }
+            Message::AboutClose => {
+                // Close the about card
+                self.about_open = false;
+                Task::none()
+            }
+            Message::CountsOpen => {
+                // Show the letter counts card
+                self.counts_open = true;
+                Task::none()
+            }
+            Message::CountsClose => {
+                // Close the letter counts card
+                self.counts_open = false;
+                Task::none()
+            }
+            Message::LettersOpen => {
+                // Show the letter information card
+                self.letters_open = true;
+                Task::none()
+            }
+            Message::LettersClose => {
+                // Close the letter information card
+                self.letters_open = false;
+                Task::none()
+            }
+            Message::PackDownload => {
+                // Download the default language pack
+                self.download_pack()
+            }
+            Message::PackProgress(Download::Progress(received, total)) => {
+                // More of the language pack received
+                self.offer = Some(PackOffer::Downloading(received, total));
+                Task::none()
+            }
+            Message::PackProgress(Download::Done(Ok(pack))) => {
+                // Language pack downloaded - switch to it
+                self.close_offer();
+                self.packs = LanguagePack::scan();
+                self.select_pack(PackChoice::Pack(pack.name))
+            }
+            Message::PackProgress(Download::Done(Err(e))) => {
+                // Language pack download failed
+                self.offer = Some(PackOffer::Failed(e));
+                Task::none()
+            }
+            Message::PackOfferClose => {
+                // Close the language pack offer
+                self.close_offer();
+                Task::none()
+            }
+            Message::SelectPack(choice) => {
+                // Switch to another language pack
+                if choice != self.pack {
+                    self.select_pack(choice)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::CountStep(letter, bound, up) => {
+                // Move the fewest or most times a letter appears
+                if self.apply(BoardCommand::StepCount { letter, bound, up }) {
                     self.app.calculate()
                 }
                 Task::none()
             }
-            Message::Toggle(row, col) => {
-                // Toggle a letter at position
-                if self.app.toggle(row, col) {
+            Message::CountClear(letter) => {
+                // Clear the count set for a letter
+                if self.apply(BoardCommand::SetCount {
+                    letter,
+                    min: 0,
+                    max: None,
+                }) {
                     self.app.calculate()
                 }
-                Task::none()
+                Task::none()
+            }
+            Message::CountsClear => {
+                // Clear all of the counts set
+                if self.apply(BoardCommand::ClearCounts) {
+                    self.app.calculate()
+                }
+                Task::none()
+            }
+            Message::CopyBoard => {
+                // Copy the board in ANSI colours for pasting into a terminal
+                self.menu = None;
+                self.notice = Some("Board copied for pasting into a terminal".to_string());
+                clipboard::write(self.app.board_ansi())
+            }
+            Message::ExportImage => {
+                // Save the board as an image
+                self.menu = None;
+
+                let path = paths::export_dir().map(|dir| dir.join(EXPORT_FILE));
+
+                self.notice = Some(match path {
+                    Some(path) => match save_board_image(&path, self.app.board()) {
+                        Ok(()) => format!("Board saved to {}", path.display()),
+                        Err(e) => format!("Unable to save the board image: {e}"),
+                    },
+                    None => "No directory to save the board image in".to_string(),
+                });
+
+                Task::none()
+            }
+            Message::TogglePin(elem) => {
+                // Pin a word to the shortlist or unpin it
+                self.app.toggle_pin(elem);
+                Task::none()
+            }
+            Message::Preview(elem) => {
+                // Show the words left by each colour pattern for the word under the mouse
+                self.preview = self.app.preview(elem);
+                Task::none()
+            }
+            Message::PreviewClose => {
+                // The mouse has left the word
+                self.preview = None;
+                Task::none()
+            }
+            Message::ToggleCol(col) => {
+                // Toggle the letter in the column on the active row
+                if self.apply(BoardCommand::ToggleCol(col)) {
+                    self.app.calculate()
+                }
+                Task::none()
+            }
+            Message::GroupWords(group) => {
+                // Group the words by first letter or show them in columns, and save the choice
+                self.settings.group_words = group;
+
+                if let Err(e) = self.settings.save() {
+                    eprintln!("Unable to save settings: {e}");
+                }
+                Task::none()
+            }
+            Message::Search(search) => {
+                // Filter the words shown
+                self.app.set_search(&search);
+                Task::none()
+            }
+            Message::SelectRow(up) => {
+                // Select the row above or below the active row
+                if let Some(row) = self.app.active_row() {
+                    let row = if up { row.saturating_sub(1) } else { row + 1 };
+
+                    self.apply(BoardCommand::SelectRow(row));
+                }
+                Task::none()
+            }
+            Message::JumpRow(row) => {
+                // Select a row from the history to change its colours
+                self.apply(BoardCommand::SelectRow(row));
+                Task::none()
+            }
+            Message::TutorialNext => {
+                // Move to the next tutorial step
+                match self.tutorial_step {
+                    Some(step) if step + 1 < TUTORIAL.len() => self.tutorial_step = Some(step + 1),
+                    _ => self.close_tutorial(),
+                }
+                Task::none()
+            }
+            Message::TutorialClose => {
+                // Close the tutorial
+                self.close_tutorial();
+                Task::none()
+            }
+            Message::AddUnknownWord => {
+                // Add the unknown word to the extra words
+                match self.app.learn_unknown_word() {
+                    Ok(Some(_)) => self.app.calculate(),
+                    Ok(None) => (),
+                    Err(e) => eprintln!("Unable to add word: {e}"),
+                }
+                Task::none()
+            }
+            Message::Zoom(zoom) => {
+                // Zoom slider moved
+                self.set_zoom(zoom)
+            }
+            Message::ZoomIn => {
+                // Zoom in and save
+                let task = self.set_zoom(self.zoom.saturating_add(ZOOM_STEP));
+                self.save_zoom();
+                task
+            }
+            Message::ZoomOut => {
+                // Zoom out and save
+                let task = self.set_zoom(self.zoom.saturating_sub(ZOOM_STEP));
+                self.save_zoom();
+                task
+            }
+            Message::ZoomSave => {
+                // Zoom slider released
+                self.save_zoom();
+                Task::none()
+            }
+            Message::FocusNext => {
+                // Tab pressed - move the keyboard focus forwards
+                self.move_focus(true)
+            }
+            Message::FocusPrev => {
+                // Shift tab pressed - move the keyboard focus backwards
+                self.move_focus(false)
+            }
+            Message::Arrow(arrow) => {
+                // Arrow pressed - move within the card showing or the focused part of the window
+                self.arrow(arrow)
+            }
+            Message::Enter => {
+                // Enter pressed - open the tile menu or press the focused item
+                match (self.focus, self.app.active_row()) {
+                    (Some(Focus::Tile(col)), Some(row))
+                        if self.modal_closed() && self.app.next_colour(row, col).is_some() =>
+                    {
+                        self.update(Message::TileMenu(row, col))
+                    }
+                    _ => self.press(false),
+                }
+            }
+            Message::Space(back) => {
+                // Space pressed - toggle the focused tile or press the focused item
+                self.press(back)
+            }
+            Message::RankPoll => {
+                // Collect the word scores if the ranking has finished
+                if self.app.poll_ranking() {
+                    // The words have been sorted so the highlighted and previewed words have moved
+                    self.highlight = None;
+                    self.preview = None;
+                }
+                Task::none()
+            }
+            Message::CountKey(c) => {
+                // Change the count of the chosen letter in the letter counts card
+                let step = match c {
+                    '+' | '=' => Some((CountBound::Min, true)),
+                    '-' => Some((CountBound::Min, false)),
+                    ']' => Some((CountBound::Max, true)),
+                    '[' => Some((CountBound::Max, false)),
+                    _ => None,
+                };
+
+                if let (true, Some((bound, up))) = (self.counts_open, step) {
+                    let letter = self.counts_letter;
+
+                    if self.apply(BoardCommand::StepCount { letter, bound, up }) {
+                        self.app.calculate()
+                    }
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Applies a command to the board, returning true if it changed the board or selected row
+    fn apply(&mut self, command: BoardCommand) -> bool {
+        self.app.apply(command).changed()
+    }
+
+    /// Returns true if no card or menu is showing over the window
+    fn modal_closed(&self) -> bool {
+        self.menu.is_none()
+            && self.calendar.is_none()
+            && !self.about_open
+            && !self.counts_open
+            && !self.letters_open
+            && self.tutorial_step.is_none()
+            && self.offer.is_none()
+    }
+
+    /// Returns the parts of the window which can be operated with the keyboard in Tab order
+    fn focus_order(&self) -> Vec<Focus> {
+        let words = !self.app.shown_words().is_empty();
+        let history = self
+            .history
+            .as_ref()
+            .is_some_and(|history| !history.is_empty());
+
+        [
+            (Focus::Tile(0), true),
+            (Focus::Words, words),
+            (Focus::Search, true),
+            (Focus::AddWord, self.app.unknown_word().is_some()),
+            (Focus::Group, self.app.words().count().is_some()),
+            (Focus::Calendar, history),
+            (Focus::Counts, true),
+            (Focus::Letters, true),
+            (Focus::About, true),
+        ]
+        .into_iter()
+        .filter_map(|(focus, available)| available.then_some(focus))
+        .collect()
+    }
+
+    /// Moves the keyboard focus to the next or previous part of the window
+    fn move_focus(&mut self, forward: bool) -> Task<Message> {
+        if !self.modal_closed() {
+            return Task::none();
+        }
+
+        let order = self.focus_order();
+        let len = order.len();
+
+        // Any board tile is the board's place in the order
+        let place = |focus: Focus| match focus {
+            Focus::Tile(_) => Focus::Tile(0),
+            focus => focus,
+        };
+
+        let pos = self
+            .focus
+            .and_then(|focus| order.iter().position(|f| *f == place(focus)));
+
+        let next = match pos {
+            Some(pos) if forward => (pos + 1) % len,
+            Some(pos) => (pos + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+
+        self.set_focus(Some(order[next]))
+    }
+
+    /// Sets the keyboard focus, highlighting the first word when the word list gains it and
+    /// focusing or unfocusing the search box
+    fn set_focus(&mut self, focus: Option<Focus>) -> Task<Message> {
+        let was_search = self.focus == Some(Focus::Search);
+
+        if self.focus == Some(Focus::Words) {
+            self.preview = None;
+        }
+
+        self.focus = focus;
+
+        if focus == Some(Focus::Words) {
+            let shown = self.app.shown_words();
+
+            if !self.highlight.is_some_and(|elem| shown.contains(&elem)) {
+                self.highlight = shown.first().copied();
+            }
+
+            self.preview = self.highlight.and_then(|elem| self.app.preview(elem));
+        }
+
+        if focus == Some(Focus::Search) {
+            text_input::focus(text_input::Id::new(SEARCH_ID))
+        } else if was_search {
+            // Focusing an id no widget has unfocuses the search box
+            text_input::focus(text_input::Id::unique())
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Handles an arrow key, moving through the menu, calendar or letter counts if showing,
+    /// otherwise moving along the board or through the words
+    fn arrow(&mut self, arrow: Arrow) -> Task<Message> {
+        if self.menu.is_some() {
+            // Choose a menu item
+            self.menu_item = match arrow {
+                Arrow::Up | Arrow::Left => self.menu_item.saturating_sub(1),
+                Arrow::Down | Arrow::Right => cmp::min(self.menu_item + 1, MENU_ITEMS - 1),
+            };
+        } else if self.calendar.is_some() {
+            // Choose a day with an answer
+            let days = match arrow {
+                Arrow::Up => -7,
+                Arrow::Down => 7,
+                Arrow::Left => -1,
+                Arrow::Right => 1,
+            };
+
+            let day = self
+                .calendar_day
+                .or(self.calendar)
+                .and_then(|day| day.checked_add_signed(TimeDelta::days(days)))
+                .filter(|day| {
+                    self.history
+                        .as_ref()
+                        .is_some_and(|history| history.answer(*day).is_some())
+                });
+
+            if let Some(day) = day {
+                self.calendar = day.with_day(1);
+                self.calendar_day = Some(day);
+            }
+        } else if self.counts_open {
+            // Choose a letter
+            let alphabet = self.app.alphabet();
+            let pos = alphabet
+                .iter()
+                .position(|c| *c == self.counts_letter)
+                .unwrap_or(0);
+
+            let pos = match arrow {
+                Arrow::Up | Arrow::Left => pos.saturating_sub(1),
+                Arrow::Down | Arrow::Right => cmp::min(pos + 1, alphabet.len().saturating_sub(1)),
+            };
+
+            if let Some(letter) = alphabet.get(pos) {
+                return self.choose_count_letter(*letter);
+            }
+        } else if self.modal_closed() {
+            match self.focus {
+                Some(Focus::Tile(col)) => match arrow {
+                    // Move along the active row, or select another row
+                    Arrow::Left => self.focus = Some(Focus::Tile(col.saturating_sub(1))),
+                    Arrow::Right => {
+                        self.focus = Some(Focus::Tile(cmp::min(col + 1, self.app.cols() - 1)))
+                    }
+                    Arrow::Up | Arrow::Down => {
+                        return self.update(Message::SelectRow(arrow == Arrow::Up))
+                    }
+                },
+                Some(Focus::Words) => return self.move_highlight(arrow),
+                Some(_) => (),
+                None => self.focus = Some(Focus::Tile(0)),
+            }
+        }
+
+        Task::none()
+    }
+
+    /// Chooses the letter to change in the letter counts card, scrolling it into view. Letters
+    /// not used by the word list are ignored
+    fn choose_count_letter(&mut self, letter: char) -> Task<Message> {
+        let alphabet = self.app.alphabet();
+
+        let Some(pos) = alphabet.iter().position(|c| *c == letter) else {
+            return Task::none();
+        };
+
+        self.counts_letter = letter;
+
+        let y = pos as f32 / (alphabet.len().max(2) - 1) as f32;
+
+        scrollable::snap_to(
+            scrollable::Id::new(COUNTS_ID),
+            scrollable::RelativeOffset { x: 0.0, y },
+        )
+    }
+
+    /// Moves the word highlight up or down through the words shown, previewing the word and
+    /// scrolling grouped words to keep it in view
+    fn move_highlight(&mut self, arrow: Arrow) -> Task<Message> {
+        let shown = self.app.shown_words();
+
+        if shown.is_empty() {
+            return Task::none();
+        }
+
+        // Start at the first word
+        let pos = match self
+            .highlight
+            .and_then(|elem| shown.iter().position(|shown| *shown == elem))
+        {
+            Some(pos) => match arrow {
+                Arrow::Up | Arrow::Left => pos.saturating_sub(1),
+                Arrow::Down | Arrow::Right => cmp::min(pos + 1, shown.len() - 1),
+            },
+            None => 0,
+        };
+
+        self.highlight = Some(shown[pos]);
+        self.preview = self.app.preview(shown[pos]);
+
+        if self.settings.group_words && shown.len() > 1 {
+            let y = pos as f32 / (shown.len() - 1) as f32;
+
+            scrollable::snap_to(
+                scrollable::Id::new(WORDS_ID),
+                scrollable::RelativeOffset { x: 0.0, y },
+            )
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Presses the focused menu item, card button or part of the window with enter or space,
+    /// toggling a focused tile backwards if back is set
+    fn press(&mut self, back: bool) -> Task<Message> {
+        if let Some((row, col)) = self.menu {
+            return self.update(Self::menu_items(row, col)[self.menu_item].1.clone());
+        }
+
+        if self.calendar.is_some() {
+            return self.update(Message::CalendarClose);
+        }
+
+        if self.about_open {
+            return self.update(Message::AboutClose);
+        }
+
+        if self.counts_open {
+            return self.update(Message::CountsClose);
+        }
+
+        if self.letters_open {
+            return self.update(Message::LettersClose);
+        }
+
+        if self.tutorial_step.is_some() {
+            return self.update(Message::TutorialNext);
+        }
+
+        let message = match self.focus {
+            Some(Focus::Tile(col)) => {
+                // Toggle the tile on the active row
+                if let Some(row) = self.app.active_row() {
+                    let command = if back {
+                        BoardCommand::ToggleBack { row, col }
+                    } else {
+                        BoardCommand::Toggle { row, col }
+                    };
+
+                    if self.apply(command) {
+                        self.app.calculate();
+                        self.highlight = None;
+                    }
+                }
+                None
+            }
+            Some(Focus::Words) => {
+                // Pin or unpin the highlighted word, keeping its preview
+                if let Some(elem) = self.highlight {
+                    self.app.toggle_pin(elem);
+                    self.preview = self.app.preview(elem);
+                }
+                None
+            }
+            Some(Focus::AddWord) => Some(Message::AddUnknownWord),
+            Some(Focus::Group) => Some(Message::GroupWords(!self.settings.group_words)),
+            Some(Focus::Calendar) => Some(Message::CalendarOpen),
+            Some(Focus::Counts) => Some(Message::CountsOpen),
+            Some(Focus::Letters) => Some(Message::LettersOpen),
+            Some(Focus::About) => Some(Message::AboutOpen),
+            Some(Focus::Search) | None => None,
+        };
+
+        match message {
+            Some(message) => self.update(message),
+            None => Task::none(),
+        }
+    }
+
+    /// Returns the context menu items for a tile, with the message each sends
+    fn menu_items(row: usize, col: usize) -> [(&'static str, Message); MENU_ITEMS] {
+        [
+            ("Set Green", Message::SetColour(row, col, TileColour::Green)),
+            (
+                "Set Yellow",
+                Message::SetColour(row, col, TileColour::Yellow),
+            ),
+            ("Set Gray", Message::SetColour(row, col, TileColour::Gray)),
+            ("Clear colour", Message::ClearColour(row, col)),
+            ("Copy board for terminal", Message::CopyBoard),
+            ("Export board image", Message::ExportImage),
+        ]
+    }
+
+    /// Changes the zoom level, growing the window if it's smaller than the new minimum size
+    fn set_zoom(&mut self, zoom: u16) -> Task<Message> {
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+
+        let min = min_size(self.app.cols(), self.zoom);
+
+        window::get_latest().and_then(move |id| {
+            window::get_size(id).then(move |size| {
+                if size.width < min.width || size.height < min.height {
+                    let size = Size::new(size.width.max(min.width), size.height.max(min.height));
+
+                    window::resize(id, size)
+                } else {
+                    Task::none()
+                }
+            })
+        })
+    }
+
+    /// Saves the zoom level in the settings
+    fn save_zoom(&mut self) {
+        if self.settings.zoom != self.zoom {
+            self.settings.zoom = self.zoom;
+
+            if let Err(e) = self.settings.save() {
+                eprintln!("Unable to save settings: {e}");
             }
-            Message::ToggleCol(col) => {
-                // Toggle last letter in the column
-                if self.app.toggle_col(col) {
-                    self.app.calculate()
-                }
-                Task::none()
+        }
+    }
+
+    /// Scales a dimension by the zoom level
+    fn scaled(&self, dim: u16) -> f32 {
+        scaled(dim, self.zoom)
+    }
+
+    /// Closes the tutorial and records it as seen
+    fn close_tutorial(&mut self) {
+        self.tutorial_step = None;
+
+        if !self.settings.tutorial_seen {
+            self.settings.tutorial_seen = true;
+
+            if let Err(e) = self.settings.save() {
+                eprintln!("Unable to save settings: {e}");
             }
         }
     }
 
+    /// Returns the theme to draw with
+    fn theme(&self) -> Theme {
+        if self.dark {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
+    }
+
+    /// Returns the tile colours for the theme
+    fn tiles(dark: bool) -> &'static Tiles {
+        if dark {
+            &DARK_TILES
+        } else {
+            &LIGHT_TILES
+        }
+    }
+
     // Add subscriptions
     fn subscription(&self) -> Subscription<Message> {
         // Subscribe to keyboard events
-        keyboard::on_key_press(|key, modifiers| {
+        let keys = keyboard::on_key_press(|key, modifiers| {
             let mut res = None;
 
             // Check no modifiers
@@ -127,6 +1469,15 @@ impl App {
                         // Delete / backspace
                         res = Some(Message::LetterRemoved)
                     }
+                    Key::Named(Named::PageUp) => res = Some(Message::SelectRow(true)),
+                    Key::Named(Named::PageDown) => res = Some(Message::SelectRow(false)),
+                    Key::Named(Named::Tab) => res = Some(Message::FocusNext),
+                    Key::Named(Named::ArrowUp) => res = Some(Message::Arrow(Arrow::Up)),
+                    Key::Named(Named::ArrowDown) => res = Some(Message::Arrow(Arrow::Down)),
+                    Key::Named(Named::ArrowLeft) => res = Some(Message::Arrow(Arrow::Left)),
+                    Key::Named(Named::ArrowRight) => res = Some(Message::Arrow(Arrow::Right)),
+                    Key::Named(Named::Enter) => res = Some(Message::Enter),
+                    Key::Named(Named::Space) => res = Some(Message::Space(false)),
                     Key::Character(c) => {
                         if let Some(c) = c.chars().next() {
                             if c.is_ascii_uppercase() {
@@ -138,60 +1489,282 @@ impl App {
                             } else if ('1'..='9').contains(&c) {
                                 // Number
                                 res = Some(Message::ToggleCol((c as u8 - b'1') as usize));
+                            } else if matches!(c, '=' | '-' | '[' | ']') {
+                                // Letter count change
+                                res = Some(Message::CountKey(c));
                             }
                         }
                     }
                     _ => (),
                 }
+            } else if modifiers.shift() && !modifiers.command() && !modifiers.alt() {
+                // Shift tab moves the focus backwards and shift space toggles backwards
+                match key.as_ref() {
+                    Key::Named(Named::Tab) => res = Some(Message::FocusPrev),
+                    Key::Named(Named::Space) => res = Some(Message::Space(true)),
+                    Key::Character("+") => res = Some(Message::CountKey('+')),
+                    _ => (),
+                }
+            } else if modifiers.command() && !modifiers.alt() {
+                // Ctrl / command plus and minus zoom
+                match key.as_ref() {
+                    Key::Character("+" | "=") => res = Some(Message::ZoomIn),
+                    Key::Character("-") => res = Some(Message::ZoomOut),
+                    _ => (),
+                }
             }
 
             res
-        })
+        });
+
+        // Subscribe to left mouse button releases anywhere to stop painting
+        let release = event::listen_with(|event, _status, _window| match event {
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                Some(Message::PaintEnd)
+            }
+            _ => None,
+        });
+
+        // Check for the word scores while ranking in the background
+        let ranking = if self.app.ranking_pending() {
+            time::every(RANK_POLL).map(|_| Message::RankPoll)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([keys, release, ranking])
     }
 
     // Create view from state
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         // Draw the button grid
         let btn_grid = self.draw_board();
 
         // Draw the words grid
         let words = self.draw_words();
 
-        // Create word count text
+        // Create word count text, with the choice of grouping the words once found and why the
+        // top suggestion is best if ranking
         let words_txt: Element<Message> = match self.app.words().count() {
-            Some(word_count) => text!("Words found: {word_count}"),
+            Some(word_count) => {
+                let found = row![
+                    text!("Words found: {word_count}").size(self.scaled(TEXT_SIZE)),
+                    self.focus_ring(
+                        Focus::Group,
+                        checkbox("Group by first letter", self.settings.group_words)
+                            .on_toggle(Message::GroupWords)
+                            .size(self.scaled(TEXT_SIZE))
+                            .text_size(self.scaled(TEXT_SIZE)),
+                    ),
+                ]
+                .spacing(self.scaled(PADDING));
+
+                match self.app.explain_best() {
+                    Some(explanation) => {
+                        column![found, text(explanation).size(self.scaled(SMALL_TEXT_SIZE))]
+                            .spacing(self.scaled(BOARD_SPACING))
+                            .into()
+                    }
+                    None => found.into(),
+                }
+            }
             None => text(
                 "\
                 Type letters to fill the board\n\n\
                 Backspace to clear the last position\n\n\
-                Toggle letters with the mouse or\npress 1-5 to toggle the column\
+                Toggle letters with the mouse, dragging\nacross a row to set the same colour, or\n\
+                press a number key to toggle the column\n\n\
+                Tab moves between the board, words\nand buttons, operated with the arrow\n\
+                keys, Enter and Space\
                 ",
+            )
+            .size(self.scaled(TEXT_SIZE))
+            .into(),
+        };
+
+        // Offer to add a guess missing from the word list, or explain why a colour change was
+        // rejected in strict mode
+        let add_word: Element<Message> = match (self.app.rejected(), self.app.unknown_word()) {
+            (Some(reason), _) => text(reason)
+                .color(INVALID)
+                .size(self.scaled(TEXT_SIZE))
+                .into(),
+            (None, Some(word)) => self.focus_ring(
+                Focus::AddWord,
+                button(text!("Add {word} to word list").size(self.scaled(TEXT_SIZE)))
+                    .on_press(Message::AddUnknownWord),
             ),
-        }
-        .into();
+            (None, None) => match &self.notice {
+                Some(notice) => text(notice).size(self.scaled(TEXT_SIZE)).into(),
+                None => Space::new(Length::Shrink, Length::Shrink).into(),
+            },
+        };
+
+        // List the pinned words
+        let shortlist = self.app.shortlist();
+
+        let shortlist: Element<Message> = if shortlist.is_empty() {
+            Space::new(Length::Shrink, Length::Shrink).into()
+        } else {
+            text!("Shortlist: {}", shortlist.join(" "))
+                .size(self.scaled(TEXT_SIZE))
+                .into()
+        };
+
+        // Offer to browse the past answers if loaded
+        let calendar: Element<Message> = match &self.history {
+            Some(history) if !history.is_empty() => self.focus_ring(
+                Focus::Calendar,
+                button(text("Past answers").size(self.scaled(TEXT_SIZE)))
+                    .on_press(Message::CalendarOpen),
+            ),
+            _ => Space::new(Length::Shrink, Length::Shrink).into(),
+        };
+
+        // Set letter counts by hand
+        let counts = self.focus_ring(
+            Focus::Counts,
+            button(text("Letter counts").size(self.scaled(TEXT_SIZE)))
+                .on_press(Message::CountsOpen),
+        );
+
+        // Show the information each letter would give if probed
+        let letters = self.focus_ring(
+            Focus::Letters,
+            button(text("Letter info").size(self.scaled(TEXT_SIZE))).on_press(Message::LettersOpen),
+        );
+
+        // Show the build and word list details
+        let about = self.focus_ring(
+            Focus::About,
+            button(text("About").size(self.scaled(TEXT_SIZE))).on_press(Message::AboutOpen),
+        );
+
+        // Choose the language pack if any are installed
+        let packs: Element<Message> = if self.packs.is_empty() {
+            Space::new(Length::Shrink, Length::Shrink).into()
+        } else {
+            let choices = [PackChoice::Default]
+                .into_iter()
+                .chain(
+                    self.packs
+                        .iter()
+                        .map(|pack| PackChoice::Pack(pack.name.clone())),
+                )
+                .collect::<Vec<_>>();
+
+            pick_list(choices, Some(self.pack.clone()), Message::SelectPack)
+                .text_size(self.scaled(TEXT_SIZE))
+                .into()
+        };
 
         // Draw the board container
         let board_box = container(Column::with_children([
             btn_grid,
             Space::new(Length::Shrink, 16).into(),
+            self.draw_legend(),
+            Space::new(Length::Shrink, 16).into(),
             words_txt,
+            Space::new(Length::Shrink, 16).into(),
+            self.draw_history(),
+            Space::new(Length::Shrink, 16).into(),
+            add_word,
+            Space::new(Length::Shrink, 16).into(),
+            shortlist,
+            Space::new(Length::Shrink, 16).into(),
+            self.draw_preview(),
+            Space::new(Length::Shrink, 16).into(),
+            row![calendar, counts, letters, about, packs]
+                .spacing(self.scaled(PADDING))
+                .into(),
+            Space::new(Length::Shrink, 16).into(),
+            self.draw_zoom(),
         ]))
         .height(Length::Fill)
         .padding(PADDING);
 
+        // Search box filtering the words shown
+        let search = self.app.search().unwrap_or_default();
+
+        let search = text_input("Search words, ? matches any letter", search)
+            .id(text_input::Id::new(SEARCH_ID))
+            .on_input(Message::Search)
+            .size(self.scaled(TEXT_SIZE))
+            .width(self.scaled(SEARCH_WIDTH));
+
         // Draw the words container
-        let words_box = container(words)
+        let words_box = container(column![search, words].spacing(self.scaled(BOARD_SPACING)))
             .height(Length::Fill)
             .width(Length::Fill)
             .padding(PADDING);
 
-        // Create row with buttons grid and words
-        let res: Element<Message> = row!(board_box, words_box).into();
+        // Create footer with the solver statistics
+        let footer = container(text(self.app.status_text()).size(self.scaled(SMALL_TEXT_SIZE)))
+            .padding([0, PADDING]);
+
+        // Create row with buttons grid and words above the footer
+        let mut res: Element<Message> = column![row!(board_box, words_box), footer].into();
+
+        // Draw the tutorial over the top if showing
+        if let Some(step) = self.tutorial_step {
+            res = stack![res, self.draw_tutorial(step)].into();
+        }
+
+        // Draw the language pack offer over the top if showing
+        if let Some(offer) = &self.offer {
+            res = stack![res, self.draw_pack_offer(offer)].into();
+        }
+
+        // Draw the calendar over the top if showing
+        if let Some(month) = self.calendar {
+            res = stack![res, self.draw_calendar(month)].into();
+        }
+
+        // Draw the about card over the top if showing
+        if self.about_open {
+            res = stack![res, self.draw_about()].into();
+        }
+
+        // Draw the letter counts card over the top if showing
+        if self.counts_open {
+            res = stack![res, self.draw_counts()].into();
+        }
+
+        // Draw the letter information card over the top if showing
+        if self.letters_open {
+            res = stack![res, self.draw_letters()].into();
+        }
+
+        // Draw the tile context menu over the top if showing
+        if let Some((row, col)) = self.menu {
+            res = stack![res, self.draw_menu(row, col)].into();
+        }
 
         // to debug layout res.explain(Color::WHITE)
         res
     }
 
+    // Draw an outline around an element if it has the keyboard focus
+    fn focus_ring<'a>(
+        &self,
+        focus: Focus,
+        content: impl Into<Element<'a, Message>>,
+    ) -> Element<'a, Message> {
+        let focused = self.focus == Some(focus);
+
+        container(content)
+            .padding(FOCUS_WIDTH)
+            .style(move |_theme| container::Style {
+                border: Border {
+                    color: FOCUS,
+                    width: if focused { FOCUS_WIDTH } else { 0.0 },
+                    radius: 4.0.into(),
+                },
+                ..container::Style::default()
+            })
+            .into()
+    }
+
     // Return true if no key modifiers present
     fn no_modifiers(modifiers: Modifiers) -> bool {
         !modifiers.alt()
@@ -202,60 +1775,794 @@ impl App {
     }
 
     // Draw the wordle board
-    fn draw_board(&self) -> Element<Message> {
-        Lazy::new(self.app.board(), |board| {
-            Column::with_children(board.iter().enumerate().map(|(rn, row)| {
-                Row::with_children(row.iter().enumerate().map(|(cn, boardelem)| {
+    fn draw_board(&self) -> Element<'_, Message> {
+        // Rows which aren't dictionary words
+        let invalid: Vec<bool> = (0..BOARD_ROWS)
+            .map(|row| self.app.row_valid(row) == Some(false))
+            .collect();
+
+        // Column of the active row's tile with the keyboard focus
+        let focus = match self.focus {
+            Some(Focus::Tile(col)) => Some(col),
+            _ => None,
+        };
+
+        let dep = (
+            self.app.board(),
+            self.app.auto(),
+            invalid,
+            self.app.active_row(),
+            focus,
+            self.dark,
+            self.zoom,
+        );
+
+        Lazy::new(dep, |(board, auto, invalid, active, focus, dark, zoom)| {
+            let tiles = Self::tiles(*dark);
+            let zoom = *zoom;
+
+            // Number the columns, with a lock on columns with a green letter. The space on the
+            // left lines the numbers up with the letters after the active row marker
+            let marker_space = Space::with_width(scaled(LEGEND_DIM, zoom));
+
+            let header = Row::with_children(std::iter::once(marker_space.into()).chain(
+                (0..self.app.cols()).map(|cn| {
+                    let (label, colour) = if self.app.col_locked(cn) {
+                        (format!("{} 🔒", cn + 1), Some(tiles.green))
+                    } else {
+                        (format!("{}", cn + 1), None)
+                    };
+
+                    text(label)
+                        .color_maybe(colour)
+                        .size(scaled(SMALL_TEXT_SIZE, zoom))
+                        .width(scaled(BUTTON_DIM, zoom))
+                        .center()
+                        .into()
+                }),
+            ))
+            .spacing(scaled(BOARD_SPACING, zoom));
+
+            let rows = board.iter().enumerate().map(|(rn, row)| {
+                // The row the number keys act on is marked with an arrow
+                let marker = text(if *active == Some(rn) { "▶" } else { "" })
+                    .size(scaled(SMALL_TEXT_SIZE, zoom))
+                    .width(scaled(LEGEND_DIM, zoom));
+
+                let cells = row.iter().enumerate().map(|(cn, boardelem)| {
+                    // Letters filled from known greens are drawn faded
+                    let alpha = if auto[rn][cn] { 0.6 } else { 1.0 };
+
                     // Calculate enebled, character and colour from board element
+                    // Letters without a colour are drawn as an outline
+                    let outline = matches!(boardelem, solveapp::BoardElem::Unknown(_));
+
                     let (enabled, button_char, colour) = match boardelem {
                         solveapp::BoardElem::Empty => (false, ' ', None),
-                        solveapp::BoardElem::Gray(c) => {
-                            (true, *c, Some(Color::from_rgb(0.3, 0.3, 0.3)))
-                        }
-                        solveapp::BoardElem::Yellow(c) => {
-                            (true, *c, Some(Color::from_rgb(0.8, 0.8, 0.0)))
-                        }
-                        solveapp::BoardElem::Green(c) => {
-                            (true, *c, Some(Color::from_rgb(0.0, 0.8, 0.0)))
-                        }
+                        solveapp::BoardElem::Unknown(c) => (true, *c, Some(tiles.unknown)),
+                        solveapp::BoardElem::Gray(c) => (true, *c, Some(tiles.gray)),
+                        solveapp::BoardElem::Yellow(c) => (true, *c, Some(tiles.yellow)),
+                        solveapp::BoardElem::Green(c) => (true, *c, Some(tiles.green)),
                     };
 
                     // Create button text (white)
                     let text = text(button_char.to_string())
                         .center()
-                        .size(20)
-                        .style(|_theme| text::Style {
-                            color: Some(Color::from_rgb(1.0, 1.0, 1.0)),
+                        .size(scaled(LARGE_TEXT_SIZE, zoom))
+                        .style(move |_theme| text::Style {
+                            color: Some(Color::from_rgba(1.0, 1.0, 1.0, alpha)),
                             // ..text::Style::default()
                         });
 
-                    // Create button with text
-                    let mut button = button(text).width(BUTTON_DIM).height(BUTTON_DIM);
+                    // Create button with text. Presses are handled by the mouse area around it
+                    let mut button = button(text)
+                        .width(scaled(BUTTON_DIM, zoom))
+                        .height(scaled(BUTTON_DIM, zoom));
 
-                    // Add click event to toggle
-                    if enabled {
-                        button = button.on_press_with(move || Message::Toggle(rn, cn));
-                    }
+                    // Set button colour, with a red border if the row isn't a word. The tile with
+                    // the keyboard focus is outlined
+                    let focused = *active == Some(rn) && *focus == Some(cn);
+
+                    if focused {
+                        let border = Border {
+                            color: FOCUS,
+                            width: FOCUS_WIDTH,
+                            ..Border::default()
+                        };
+
+                        button = button.style(move |theme, status| button::Style {
+                            border,
+                            ..match colour {
+                                Some(colour) => button::Style::default().with_background(colour),
+                                None => button::primary(theme, status),
+                            }
+                        });
+                    } else if let Some(colour) = colour {
+                        let border = if invalid[rn] {
+                            Border {
+                                color: INVALID,
+                                width: 3.0,
+                                ..Border::default()
+                            }
+                        } else if outline {
+                            Border {
+                                color: tiles.gray,
+                                width: 2.0,
+                                ..Border::default()
+                            }
+                        } else {
+                            Border::default()
+                        };
 
-                    // Set button colour
-                    if let Some(colour) = colour {
-                        button = button.style(move |_theme, _status| {
-                            button::Style::default().with_background(colour)
+                        button = button.style(move |_theme, _status| button::Style {
+                            border,
+                            ..button::Style::default().with_background(colour)
                         });
                     }
 
-                    button.into()
-                }))
-                .spacing(BOARD_SPACING)
-                .into()
-            }))
-            .spacing(BOARD_SPACING)
+                    // Pressing toggles the letter, dragging paints the row and right clicking
+                    // shows the context menu
+                    if enabled {
+                        mouse_area(button)
+                            .on_press(Message::PaintStart(rn, cn))
+                            .on_enter(Message::PaintOver(rn, cn))
+                            .on_right_press(Message::TileMenu(rn, cn))
+                            .interaction(mouse::Interaction::Pointer)
+                            .into()
+                    } else {
+                        button.into()
+                    }
+                });
+
+                Row::with_children(std::iter::once(marker.into()).chain(cells))
+                    .spacing(scaled(BOARD_SPACING, zoom))
+                    .align_y(Alignment::Center)
+                    .into()
+            });
+
+            Column::with_children(std::iter::once(header.into()).chain(rows))
+                .spacing(scaled(BOARD_SPACING, zoom))
         })
         .into()
     }
 
+    // Draw the colour legend
+    fn draw_legend(&self) -> Element<'_, Message> {
+        let tiles = Self::tiles(self.dark);
+
+        let text_size = self.scaled(TEXT_SIZE);
+
+        let legend_row = |colour: Color, desc: &'static str| {
+            row![
+                container(Space::new(self.scaled(LEGEND_DIM), self.scaled(LEGEND_DIM)))
+                    .style(move |_theme| container::Style::default().background(colour)),
+                text(desc).size(text_size),
+            ]
+            .spacing(self.scaled(BOARD_SPACING))
+            .align_y(Alignment::Center)
+            .into()
+        };
+
+        Column::with_children([
+            legend_row(tiles.gray, "Letter not in the word"),
+            legend_row(tiles.yellow, "Letter in the word, wrong place"),
+            legend_row(tiles.green, "Letter in the word, right place"),
+            text(
+                "Click a letter to change its colour, drag across a row or right click for a menu",
+            )
+            .size(text_size)
+            .into(),
+        ])
+        .spacing(4)
+        .into()
+    }
+
+    // Draw the candidates before and after each completed row, selecting the row when clicked
+    fn draw_history(&self) -> Element<'_, Message> {
+        let history = self.app.row_history();
+
+        if history.is_empty() {
+            return Space::new(Length::Shrink, Length::Shrink).into();
+        }
+
+        let active = self.app.active_row();
+
+        Column::with_children(history.into_iter().map(|row| {
+            let label = text!(
+                "Row {} {}: {} → {}",
+                row.row + 1,
+                row.word,
+                row.before,
+                row.after
+            )
+            .size(self.scaled(SMALL_TEXT_SIZE));
+
+            // The row the colour toggles act on is highlighted
+            let style = if active == Some(row.row) {
+                button::primary
+            } else {
+                button::text
+            };
+
+            button(label)
+                .on_press(Message::JumpRow(row.row))
+                .padding(2)
+                .style(style)
+                .into()
+        }))
+        .spacing(2)
+        .into()
+    }
+
+    // Draw the words left by the largest colour pattern groups for the word under the mouse
+    fn draw_preview(&self) -> Element<'_, Message> {
+        let Some(preview) = &self.preview else {
+            return Space::new(Length::Shrink, Length::Shrink).into();
+        };
+
+        let tiles = Self::tiles(self.dark);
+
+        let text_size = self.scaled(TEXT_SIZE);
+
+        let group_row = |board_row: &BoardRow, words: usize| {
+            let mut cells: Vec<Element<Message>> = board_row
+                .iter()
+                .map(|elem| {
+                    let (c, colour) = match elem {
+                        solveapp::BoardElem::Gray(c) => (*c, tiles.gray),
+                        solveapp::BoardElem::Yellow(c) => (*c, tiles.yellow),
+                        solveapp::BoardElem::Green(c) => (*c, tiles.green),
+                        solveapp::BoardElem::Empty => (' ', tiles.unknown),
+                        solveapp::BoardElem::Unknown(c) => (*c, tiles.unknown),
+                    };
+
+                    container(
+                        text(c)
+                            .size(self.scaled(SMALL_TEXT_SIZE))
+                            .color(Color::WHITE),
+                    )
+                    .center(self.scaled(WORD_HEIGHT))
+                    .style(move |_theme| container::Style::default().background(colour))
+                    .into()
+                })
+                .collect();
+
+            cells.push(text!("{words} words").size(text_size).into());
+
+            Row::with_children(cells)
+                .spacing(2)
+                .align_y(Alignment::Center)
+                .into()
+        };
+
+        let mut lines: Vec<Element<Message>> = vec![text!(
+            "{}: {} patterns, at worst {} words left",
+            preview.word,
+            preview.groups.len(),
+            preview.worst()
+        )
+        .size(text_size)
+        .into()];
+
+        lines.extend(
+            preview
+                .groups
+                .iter()
+                .take(PREVIEW_GROUPS)
+                .map(|(board_row, words)| group_row(board_row, *words)),
+        );
+
+        Column::with_children(lines).spacing(4).into()
+    }
+
+    // Draw the zoom slider
+    fn draw_zoom(&self) -> Element<'_, Message> {
+        row![
+            text("Zoom").size(self.scaled(TEXT_SIZE)),
+            slider(MIN_ZOOM..=MAX_ZOOM, self.zoom, Message::Zoom)
+                .step(ZOOM_STEP)
+                .on_release(Message::ZoomSave)
+                .width(self.scaled(BUTTON_DIM) * 3.0),
+            text!("{}%", self.zoom).size(self.scaled(TEXT_SIZE)),
+        ]
+        .spacing(self.scaled(BOARD_SPACING))
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    // Draw the tutorial overlay
+    fn draw_tutorial(&self, step: usize) -> Element<'_, Message> {
+        let (title, body) = TUTORIAL[step];
+
+        let next_label = if step + 1 < TUTORIAL.len() {
+            "Next"
+        } else {
+            "Done"
+        };
+
+        // Create the tutorial card
+        let card = container(
+            column![
+                text!("{title} ({}/{})", step + 1, TUTORIAL.len())
+                    .size(self.scaled(LARGE_TEXT_SIZE)),
+                text(body).size(self.scaled(TEXT_SIZE)),
+                row![
+                    button(text("Skip").size(self.scaled(TEXT_SIZE)))
+                        .on_press(Message::TutorialClose),
+                    Space::with_width(Length::Fill),
+                    button(text(next_label).size(self.scaled(TEXT_SIZE)))
+                        .on_press(Message::TutorialNext),
+                ],
+            ]
+            .spacing(16),
+        )
+        .max_width(self.scaled(400))
+        .padding(20)
+        .style(container::rounded_box);
+
+        // Centre the card over a translucent background, blocking input to the board
+        opaque(center(card).style(|_theme| {
+            container::Style::default().background(Color {
+                a: 0.7,
+                ..Color::BLACK
+            })
+        }))
+    }
+
+    // Draw the offer to download the default language pack, with the download progress
+    fn draw_pack_offer<'a>(&'a self, offer: &'a PackOffer) -> Element<'a, Message> {
+        let text_size = self.scaled(TEXT_SIZE);
+
+        let dir = paths::packs_dir().map_or("the data directory".to_string(), |dir| {
+            dir.display().to_string()
+        });
+
+        let body: Element<Message> = match offer {
+            PackOffer::Asking => text!(
+                "Download the English language pack, with the answers and the words accepted as \
+                guesses, from {} into {dir}?",
+                self.settings.pack_url
+            )
+            .size(text_size)
+            .into(),
+            PackOffer::Downloading(received, total) => {
+                let (status, done) = match total {
+                    Some(total) => (
+                        format!("Downloading {} of {} KB", received / 1024, total / 1024),
+                        *received as f32 / cmp::max(*total, 1) as f32,
+                    ),
+                    None => (format!("Downloading {} KB", received / 1024), 0.0),
+                };
+
+                column![text(status).size(text_size), progress_bar(0.0..=1.0, done)]
+                    .spacing(8)
+                    .into()
+            }
+            PackOffer::Failed(e) => text(e).color(INVALID).size(text_size).into(),
+        };
+
+        // The buttons are disabled while downloading
+        let idle = !matches!(offer, PackOffer::Downloading(..));
+
+        let action = if matches!(offer, PackOffer::Failed(_)) {
+            "Retry"
+        } else {
+            "Download"
+        };
+
+        let card = container(
+            column![
+                text("Download a word list").size(self.scaled(LARGE_TEXT_SIZE)),
+                body,
+                row![
+                    button(text("Not now").size(text_size))
+                        .on_press_maybe(idle.then_some(Message::PackOfferClose)),
+                    Space::with_width(Length::Fill),
+                    button(text(action).size(text_size))
+                        .on_press_maybe(idle.then_some(Message::PackDownload)),
+                ],
+            ]
+            .spacing(16),
+        )
+        .max_width(self.scaled(400))
+        .padding(20)
+        .style(container::rounded_box);
+
+        // Centre the card over a translucent background, blocking input to the board
+        opaque(center(card).style(|_theme| {
+            container::Style::default().background(Color {
+                a: 0.7,
+                ..Color::BLACK
+            })
+        }))
+    }
+
+    // Draw the about card with the build and word list details to quote in bug reports
+    fn draw_about(&self) -> Element<'_, Message> {
+        let text_size = self.scaled(TEXT_SIZE);
+
+        let card = container(
+            column![
+                text("Wordle Solver").size(self.scaled(LARGE_TEXT_SIZE)),
+                Column::with_children(
+                    self.about
+                        .iter()
+                        .map(|line| text(line).size(text_size).into())
+                )
+                .spacing(4),
+                text("Third party licenses").size(text_size),
+                scrollable(text(LICENSES).size(self.scaled(SMALL_TEXT_SIZE)))
+                    .width(Length::Fill)
+                    .height(self.scaled(200)),
+                text("Run solvegui --licenses for the full license texts")
+                    .size(self.scaled(SMALL_TEXT_SIZE)),
+                row![
+                    Space::with_width(Length::Fill),
+                    button(text("Close").size(text_size)).on_press(Message::AboutClose),
+                ],
+            ]
+            .spacing(16),
+        )
+        .max_width(self.scaled(600))
+        .padding(20)
+        .style(container::rounded_box);
+
+        // Centre the card over a translucent background, blocking input to the board
+        opaque(center(card).style(|_theme| {
+            container::Style::default().background(Color {
+                a: 0.7,
+                ..Color::BLACK
+            })
+        }))
+    }
+
+    // Draw the card showing the information each letter not on the board yet would give if
+    // probed, with the share of the words containing it and the share with it in each column
+    fn draw_letters(&self) -> Element<'_, Message> {
+        let text_size = self.scaled(TEXT_SIZE);
+        let value_width = self.scaled(LARGE_TEXT_SIZE * 3);
+
+        let value = |value: String| text(value).size(text_size).width(value_width).center();
+        let percent = |share: f32| value(format!("{:.0}%", share * 100.0));
+
+        let mut header = vec![
+            value("Letter".to_string()),
+            value("Bits".to_string()),
+            value("Words".to_string()),
+        ];
+        header.extend((1..=self.app.cols()).map(|col| value(format!("Col {col}"))));
+
+        let mut rows: Vec<Element<Message>> =
+            vec![Row::with_children(header.into_iter().map(Element::from)).into()];
+
+        rows.extend(self.app.letter_information().into_iter().map(|info| {
+            let mut cells = vec![
+                value(info.letter.to_string()),
+                value(format!("{:.2}", info.bits)),
+                percent(info.present),
+            ];
+
+            cells.extend(info.columns.iter().map(|share| percent(*share)));
+
+            Row::with_children(cells.into_iter().map(Element::from)).into()
+        }));
+
+        let card = container(
+            column![
+                text("Letter information").size(self.scaled(LARGE_TEXT_SIZE)),
+                text(
+                    "The information in bits each letter not on the board yet would give if you \
+                    probed it, most first, with the share of the words containing it and the \
+                    share with it in each column. Letters in about half of the words and spread \
+                    over the columns tell you the most"
+                )
+                .size(text_size),
+                scrollable(Column::with_children(rows).spacing(4)).height(self.scaled(300)),
+                row![
+                    Space::with_width(Length::Fill),
+                    button(text("Close").size(text_size)).on_press(Message::LettersClose),
+                ],
+            ]
+            .spacing(16),
+        )
+        .max_width(self.scaled(600))
+        .padding(20)
+        .style(container::rounded_box);
+
+        // Centre the card over a translucent background, blocking input to the board
+        opaque(center(card).style(|_theme| {
+            container::Style::default().background(Color {
+                a: 0.7,
+                ..Color::BLACK
+            })
+        }))
+    }
+
+    // Draw the card for setting the fewest and most times letters appear
+    fn draw_counts(&self) -> Element<'_, Message> {
+        let text_size = self.scaled(TEXT_SIZE);
+        let value_width = self.scaled(LARGE_TEXT_SIZE * 2);
+
+        let step = |label: &'static str, letter: char, bound: CountBound, up: bool| {
+            button(text(label).size(text_size)).on_press(Message::CountStep(letter, bound, up))
+        };
+
+        // A row for each letter, with the counts set by hand in bold and a button to clear them
+        let letters = Column::with_children(self.app.alphabet().into_iter().map(|letter| {
+            let (min, max) = self.app.letter_count(letter);
+            let set = self.app.count_set(letter).is_some();
+
+            let font = if set {
+                Font {
+                    weight: font::Weight::Bold,
+                    ..Font::DEFAULT
+                }
+            } else {
+                Font::DEFAULT
+            };
+
+            let value = |value: String| {
+                text(value)
+                    .size(text_size)
+                    .font(font)
+                    .width(value_width)
+                    .center()
+            };
+
+            // The letter chosen with the keyboard is outlined
+            let chosen = letter == self.counts_letter;
+
+            let letter_row = row![
+                value(letter.to_string()),
+                step("-", letter, CountBound::Min, false),
+                value(min.to_string()),
+                step("+", letter, CountBound::Min, true),
+                Space::with_width(self.scaled(PADDING)),
+                step("-", letter, CountBound::Max, false),
+                value(max.map_or("any".to_string(), |max| max.to_string())),
+                step("+", letter, CountBound::Max, true),
+                Space::with_width(self.scaled(PADDING)),
+                button(text("Clear").size(text_size))
+                    .on_press_maybe(set.then_some(Message::CountClear(letter))),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center);
+
+            container(letter_row)
+                .padding(FOCUS_WIDTH)
+                .style(move |_theme| container::Style {
+                    border: Border {
+                        color: FOCUS,
+                        width: if chosen { FOCUS_WIDTH } else { 0.0 },
+                        ..Border::default()
+                    },
+                    ..container::Style::default()
+                })
+                .into()
+        }))
+        .spacing(4);
+
+        let card = container(
+            column![
+                text("Letter counts").size(self.scaled(LARGE_TEXT_SIZE)),
+                text(
+                    "The fewest and most times each letter appears in the words. Change them to \
+                    use knowledge the board can't show. Counts can only be narrowed, and those \
+                    you set are in bold. Type a letter or use the arrow keys to choose one, then \
+                    press + and - to change the fewest, ] and [ to change the most and Delete \
+                    to clear it"
+                )
+                .size(text_size),
+                scrollable(letters)
+                    .id(scrollable::Id::new(COUNTS_ID))
+                    .height(self.scaled(300)),
+                row![
+                    Space::with_width(Length::Fill),
+                    button(text("Clear all").size(text_size)).on_press(Message::CountsClear),
+                    button(text("Close").size(text_size)).on_press(Message::CountsClose),
+                ]
+                .spacing(self.scaled(PADDING)),
+            ]
+            .spacing(16),
+        )
+        .max_width(self.scaled(600))
+        .padding(20)
+        .style(container::rounded_box);
+
+        // Centre the card over a translucent background, blocking input to the board
+        opaque(center(card).style(|_theme| {
+            container::Style::default().background(Color {
+                a: 0.7,
+                ..Color::BLACK
+            })
+        }))
+    }
+
+    // Draw the context menu for a board tile
+    fn draw_menu(&self, row: usize, col: usize) -> Element<'_, Message> {
+        let text_size = self.scaled(TEXT_SIZE);
+
+        let letter = match self.app.board()[row][col] {
+            solveapp::BoardElem::Empty => ' ',
+            solveapp::BoardElem::Unknown(c)
+            | solveapp::BoardElem::Gray(c)
+            | solveapp::BoardElem::Yellow(c)
+            | solveapp::BoardElem::Green(c) => c,
+        };
+
+        // Menu items, with the item chosen with the keyboard outlined
+        let items = Self::menu_items(row, col);
+
+        let items = items.into_iter().enumerate().map(|(i, (label, message))| {
+            let border = if i == self.menu_item {
+                Border {
+                    color: FOCUS,
+                    width: FOCUS_WIDTH,
+                    ..Border::default()
+                }
+            } else {
+                Border::default()
+            };
+
+            button(text(label).size(text_size))
+                .width(Length::Fill)
+                .on_press(message)
+                .style(move |theme, status| button::Style {
+                    border,
+                    ..button::primary(theme, status)
+                })
+                .into()
+        });
+
+        // Create the menu card
+        let card = container(
+            Column::with_children(
+                std::iter::once(text!("Letter {letter}").size(text_size).into()).chain(items),
+            )
+            .spacing(8),
+        )
+        .max_width(self.scaled(200))
+        .padding(12)
+        .style(container::rounded_box);
+
+        // Centre the card over a translucent background which closes the menu when clicked
+        opaque(
+            mouse_area(center(card).style(|_theme| {
+                container::Style::default().background(Color {
+                    a: 0.4,
+                    ..Color::BLACK
+                })
+            }))
+            .on_press(Message::MenuClose)
+            .on_right_press(Message::MenuClose),
+        )
+    }
+
+    // Draw the calendar of past answers for a month
+    fn draw_calendar(&self, month: NaiveDate) -> Element<'_, Message> {
+        let text_size = self.scaled(TEXT_SIZE);
+        let day_dim = self.scaled(BUTTON_DIM);
+
+        let history = self.history.as_ref();
+        let (first, last) = history.and_then(History::range).unwrap_or((month, month));
+
+        // Month navigation, stopping at the months with answers
+        let nav = |label: &'static str, to: Option<NaiveDate>| {
+            let to = to.filter(|to| {
+                (to.year(), to.month()) >= (first.year(), first.month())
+                    && (to.year(), to.month()) <= (last.year(), last.month())
+            });
+
+            button(text(label).size(text_size)).on_press_maybe(to.map(Message::CalendarMonth))
+        };
+
+        let header = row![
+            nav("<", month.checked_sub_months(Months::new(1))),
+            text(month.format("%B %Y").to_string())
+                .size(self.scaled(LARGE_TEXT_SIZE))
+                .width(Length::Fill)
+                .center(),
+            nav(">", month.checked_add_months(Months::new(1))),
+        ]
+        .align_y(Alignment::Center);
+
+        // Day buttons, enabled for the days with an answer
+        let answers: Vec<NaiveDate> = history
+            .map(|history| {
+                history
+                    .month(month.year(), month.month())
+                    .map(|(date, _)| date)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let blanks = month.weekday().num_days_from_monday() as usize;
+
+        let mut cells: Vec<Element<Message>> = (0..blanks)
+            .map(|_| Space::new(day_dim, day_dim).into())
+            .collect();
+
+        cells.extend(
+            month
+                .iter_days()
+                .take_while(|day| day.month() == month.month())
+                .map(|day| {
+                    // The chosen day is outlined
+                    let border = if self.calendar_day == Some(day) {
+                        Border {
+                            color: FOCUS,
+                            width: FOCUS_WIDTH,
+                            ..Border::default()
+                        }
+                    } else {
+                        Border::default()
+                    };
+
+                    button(text(day.day()).size(text_size).center())
+                        .width(day_dim)
+                        .height(day_dim)
+                        .on_press_maybe(
+                            answers
+                                .contains(&day)
+                                .then_some(Message::CalendarSelect(day)),
+                        )
+                        .style(move |theme, status| button::Style {
+                            border,
+                            ..button::primary(theme, status)
+                        })
+                        .into()
+                }),
+        );
+
+        let mut grid = vec![Row::with_children(
+            WEEKDAYS.map(|day| text(day).size(text_size).width(day_dim).center().into()),
+        )
+        .spacing(4)
+        .into()];
+
+        while !cells.is_empty() {
+            let week: Vec<Element<Message>> = cells.drain(..cells.len().min(7)).collect();
+
+            grid.push(Row::with_children(week).spacing(4).into());
+        }
+
+        // Answer for the chosen day
+        let answer = match self.calendar_day {
+            Some(day) => match history.and_then(|history| history.answer(day)) {
+                Some(answer) => format!("{}: {answer}", day.format("%A %e %B %Y")),
+                None => String::new(),
+            },
+            None => "Choose a day to show its answer".to_string(),
+        };
+
+        // Create the calendar card
+        let card = container(
+            column![
+                header,
+                Column::with_children(grid).spacing(4),
+                text(answer).size(text_size),
+                row![
+                    Space::with_width(Length::Fill),
+                    button(text("Close").size(text_size)).on_press(Message::CalendarClose),
+                ],
+            ]
+            .spacing(16),
+        )
+        .max_width(day_dim * 7.0 + 24.0 + 40.0)
+        .padding(20)
+        .style(container::rounded_box);
+
+        // Centre the card over a translucent background, blocking input to the board
+        opaque(center(card).style(|_theme| {
+            container::Style::default().background(Color {
+                a: 0.7,
+                ..Color::BLACK
+            })
+        }))
+    }
+
     // Draw the found words
-    fn draw_words(&self) -> Element<Message> {
+    fn draw_words(&self) -> Element<'_, Message> {
         // Create responsive container
         Responsive::new(|size| {
             // Dependency structure
@@ -263,16 +2570,29 @@ impl App {
             struct WordsDep<'a> {
                 size: Size<usize>,
                 words: &'a Words,
+                shortlist: Vec<String>,
+                group: bool,
+                search: Option<&'a str>,
+                eliminated: Vec<String>,
+                highlight: Option<usize>,
+                zoom: u16,
             }
 
             // How many rows and columns?
-            let cols_avail = (size.width / WORD_WIDTH as f32).floor() as usize;
-            let rows_avail = (size.height / WORD_HEIGHT as f32).floor() as usize;
+            let cols_avail =
+                (size.width / entry_width(self.app.cols(), self.zoom)).floor() as usize;
+            let rows_avail = (size.height / self.scaled(WORD_HEIGHT)).floor() as usize;
 
             // Set dependency structure
             let dep = WordsDep {
                 size: Size::new(cols_avail, rows_avail),
                 words: self.app.words(),
+                shortlist: self.app.shortlist(),
+                group: self.settings.group_words,
+                search: self.app.search(),
+                eliminated: self.app.eliminated(),
+                highlight: self.highlight.filter(|_| self.focus == Some(Focus::Words)),
+                zoom: self.zoom,
             };
 
             // Create lazy content
@@ -280,30 +2600,128 @@ impl App {
                 // Get size
                 let size = dep.size;
 
-                // Get words
+                // Get words, and the words shown which match the search
                 let words = dep.words;
+                let shown = self.app.shown_words();
+
+                let text_size = scaled(TEXT_SIZE, dep.zoom);
+                let height = scaled(WORD_HEIGHT, dep.zoom);
+
+                // Create a word list entry
+                let entry = |j: usize| -> Element<Message> {
+                    // Create the star button to pin the word
+                    let star = if self.app.is_pinned(j) { "★" } else { "☆" };
+
+                    let pin = button(text(star).size(text_size))
+                        .on_press(Message::TogglePin(j))
+                        .padding(0)
+                        .height(height)
+                        .width(scaled(LETTER_WIDTH, dep.zoom))
+                        .style(button::text);
+
+                    // Create text element with the found word, coloured by its score if ranking
+                    // and with the letters matching the search picked out
+                    let word = self.app.get_word(j).unwrap();
+                    let colour = self.app.get_heat(j).map(heat_colour);
+
+                    let spans = match self.app.search_match(j) {
+                        Some(range) => vec![
+                            span(word[..range.start].to_string()).color_maybe(colour),
+                            span(word[range.clone()].to_string())
+                                .color(SEARCH_MATCH)
+                                .underline(true),
+                            span(word[range.end..].to_string()).color_maybe(colour),
+                        ],
+                        None => vec![span(word).color_maybe(colour)],
+                    };
+
+                    let word = rich_text(spans).size(text_size).height(height);
+
+                    // Preview the word while the mouse is over it
+                    let word = mouse_area(word)
+                        .on_enter(Message::Preview(j))
+                        .on_exit(Message::PreviewClose);
+
+                    // Outline the word highlighted with the keyboard
+                    let highlight = dep.highlight == Some(j);
+
+                    container(row![pin, word])
+                        .width(entry_width(self.app.cols(), dep.zoom))
+                        .style(move |_theme| container::Style {
+                            border: Border {
+                                color: FOCUS,
+                                width: if highlight { FOCUS_WIDTH } else { 0.0 },
+                                ..Border::default()
+                            },
+                            ..container::Style::default()
+                        })
+                        .into()
+                };
+
+                // Create an entry for a word eliminated by the last edit, crossed out
+                let eliminated_entry = |word: &String| -> Element<Message> {
+                    let word = rich_text([span(word.clone()).strikethrough(true)])
+                        .color(ELIMINATED)
+                        .size(text_size)
+                        .height(height);
+
+                    row![Space::with_width(scaled(LETTER_WIDTH, dep.zoom)), word]
+                        .width(entry_width(self.app.cols(), dep.zoom))
+                        .into()
+                };
+
+                let eliminated = &dep.eliminated;
 
                 // Get word count
+                let word_count = shown.len();
+                let entry_count = word_count + eliminated.len();
+
                 let content: Option<Element<Message>> = match words.count() {
-                    Some(word_count) if word_count > 0 => {
+                    Some(_) if entry_count > 0 && dep.group => {
+                        // Create a heading with the word count for each first letter, followed by
+                        // its words wrapped to the width available
+                        let groups = Column::with_children(self.app.word_groups().into_iter().map(
+                            |(c, group)| {
+                                column![
+                                    text!("{c} ({})", group.len()).size(text_size),
+                                    Row::with_children(group.into_iter().map(&entry)).wrap(),
+                                ]
+                                .into()
+                            },
+                        ))
+                        .push_maybe((!eliminated.is_empty()).then(|| {
+                            column![
+                                text!("Eliminated ({})", eliminated.len()).size(text_size),
+                                Row::with_children(eliminated.iter().map(eliminated_entry)).wrap(),
+                            ]
+                        }))
+                        .spacing(scaled(BOARD_SPACING, dep.zoom));
+
+                        Some(
+                            scrollable(groups)
+                                .id(scrollable::Id::new(WORDS_ID))
+                                .width(Length::Fill)
+                                .into(),
+                        )
+                    }
+                    Some(_) if entry_count > 0 => {
                         // Enough space to render some words?
                         if size.width > 0 && size.height > 0 {
                             // How many columns to draw?
-                            let draw_cols = (((word_count - 1) / size.height) + 1).min(size.width);
+                            let draw_cols = (((entry_count - 1) / size.height) + 1).min(size.width);
 
                             // Create row layout containing columns
                             let row = Row::with_children((0..draw_cols).map(|i| {
                                 // Calculate start word for this column
                                 let start = i * size.height;
 
-                                // Create the word column
+                                // Create the word column, with the eliminated words after the words
                                 Column::with_children(
-                                    (start..word_count.min(start + size.height)).map(|j| {
-                                        // Create text element with the found word
-                                        text(self.app.get_word(j).unwrap())
-                                            .height(WORD_HEIGHT)
-                                            .width(WORD_WIDTH)
-                                            .into()
+                                    (start..entry_count.min(start + size.height)).map(|k| {
+                                        match shown.get(k) {
+                                            Some(j) => entry(*j),
+                                            None => eliminated_entry(&eliminated[k - word_count]),
+                                        }
                                     }),
                                 )
                                 .into()
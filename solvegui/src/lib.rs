@@ -0,0 +1,270 @@
+//! Graphical Wordle solver
+
+use std::error::Error;
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use app::{rungui, WordList};
+use clap::Parser;
+use dictionary::{bundled_sha256, verify_sha256, Dictionary, History, WordCase, BUNDLED_DICT};
+use solveapp::board_image::board_from_image;
+use solveapp::packs::{LanguagePack, DEFAULT_PACK};
+use solveapp::{build_info, long_version, paths, BoardRow, BOARD_ROWS, VERSION};
+
+mod app;
+
+/// Third party crates built into the gui, with their versions and licenses
+const LICENSES: &str = include_str!(concat!(env!("OUT_DIR"), "/licenses.txt"));
+
+/// License files of the third party crates built into the gui
+const LICENSE_TEXTS: &str = include_str!(concat!(env!("OUT_DIR"), "/license_texts.txt"));
+
+/// Wordle solver
+#[derive(Parser, Default)]
+#[clap(author, version = VERSION, long_version = long_version(), about)]
+struct Args {
+    /// Word list file
+    #[clap(
+        short = 'd',
+        long = "dictionary",
+        default_value_t = default_dict().into(),
+    )]
+    dictionary_file: String,
+
+    /// Expected SHA-256 hash of the word list file, checked before loading
+    /// The bundled word list is always checked
+    #[clap(long = "dict-sha256")]
+    dict_sha256: Option<String>,
+
+    /// Log file
+    #[clap(short = 'l', long = "log-file")]
+    log_file: Option<String>,
+
+    /// Accept upper and mixed case words in the word list
+    #[clap(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Import the board colours from a PNG screenshot of a game
+    #[clap(long = "import-image", requires = "words")]
+    import_image: Option<PathBuf>,
+
+    /// Words guessed in the screenshot, comma separated
+    #[clap(long = "words", value_delimiter = ',', requires = "import_image")]
+    words: Vec<String>,
+
+    /// Rank words by expected information, colouring them from red for the worst to green for
+    /// the best
+    #[clap(short = 'r', long = "rank")]
+    rank: bool,
+
+    /// File of past answers with a date and answer on each line (eg. 2024-03-01 CRANE), shown
+    /// in a calendar
+    #[clap(long = "history")]
+    history: Option<String>,
+
+    /// Keep settings and word list additions in a separate profile
+    #[clap(long = "profile")]
+    profile: Option<String>,
+
+    /// Use a language pack installed in the packs directory instead of the word list file
+    #[clap(long = "pack")]
+    pack: Option<String>,
+
+    /// List the language packs installed and exit
+    #[clap(long = "list-packs")]
+    list_packs: bool,
+
+    /// Print the license files of the third party crates built in and exit
+    #[clap(long = "licenses")]
+    licenses: bool,
+}
+
+/// Runs with command line arguments, the first being the program name
+pub fn run<I, T>(args: I) -> Result<(), Box<dyn Error>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    // Parse command line arguments
+    let args = Args::parse_from(args);
+
+    // Print the third party license files
+    if args.licenses {
+        io::stdout().write_all(LICENSE_TEXTS.as_bytes())?;
+        return Ok(());
+    }
+
+    // Set up logging
+    solveapp::init_logging(args.log_file.as_deref())?;
+
+    // Select the profile before any settings are loaded
+    if let Some(profile) = &args.profile {
+        paths::set_profile(profile)?;
+    }
+
+    let case = if args.ignore_case {
+        WordCase::Normalise
+    } else {
+        WordCase::Lower
+    };
+
+    // List the language packs
+    if args.list_packs {
+        list_packs(case);
+        return Ok(());
+    }
+
+    // Use the language pack chosen, or an installed pack if there's no word list file
+    let pack = match &args.pack {
+        Some(name) => Some(LanguagePack::find(name)?),
+        None if args.dictionary_file.is_empty() => {
+            let packs = LanguagePack::scan();
+
+            packs
+                .iter()
+                .find(|pack| pack.name == DEFAULT_PACK)
+                .or(packs.first())
+                .cloned()
+        }
+        None => None,
+    };
+
+    // Load words from the language pack or the word list file
+    let (dictionary, guesses, file) = match &pack {
+        Some(pack) => {
+            let (answers, guesses) = pack.load(case, false)?.into_parts();
+
+            (answers, Some(guesses), pack.file())
+        }
+        None if args.dictionary_file.is_empty() => {
+            // Start without any words, offering to download a language pack
+            eprintln!(
+                "No dictionary file given and none of the default dictionaries could be found."
+            );
+            eprintln!("Default dictionaries are:");
+
+            for d in DICTS {
+                eprintln!("  {d}");
+            }
+
+            (
+                Dictionary::new_from_string("", case, false)?,
+                None,
+                String::new(),
+            )
+        }
+        None => {
+            // Check the word list hasn't been corrupted or changed
+            let sha256 = args
+                .dict_sha256
+                .as_deref()
+                .or_else(|| bundled_sha256(&args.dictionary_file));
+
+            if let Some(sha256) = sha256 {
+                verify_sha256(&args.dictionary_file, sha256, false)?;
+            }
+
+            let dictionary = Dictionary::new_from_file(&args.dictionary_file, case, false)?;
+
+            (dictionary, None, args.dictionary_file.clone())
+        }
+    };
+
+    // Import the board from a screenshot
+    let board = match &args.import_image {
+        Some(path) => import_board(path, &args.words, &dictionary)?,
+        None => Vec::new(),
+    };
+
+    // Load past answers
+    let history = match &args.history {
+        Some(file) => Some(History::new_from_file(file)?),
+        None => None,
+    };
+
+    // Describe the build and word list for the about card
+    let about = build_info(&file, &dictionary);
+
+    // Run the gui
+    let words = WordList {
+        dictionary,
+        guesses,
+        file: args.dictionary_file,
+        case,
+        pack: pack.map(|pack| pack.name),
+    };
+
+    rungui(words, board, history, about, args.rank)?;
+
+    Ok(())
+}
+
+/// Prints the language packs installed with their descriptions
+fn list_packs(case: WordCase) {
+    let dir = paths::packs_dir().map_or("unknown".to_string(), |dir| dir.display().to_string());
+    let packs = LanguagePack::scan();
+
+    if packs.is_empty() {
+        println!("No language packs installed in {dir}");
+    } else {
+        println!("Language packs in {dir}:");
+
+        for pack in packs {
+            println!("  {}", pack.describe(case));
+        }
+    }
+}
+
+/// Reads the board from a screenshot, checking the words fit the board
+fn import_board(
+    path: &Path,
+    words: &[String],
+    dictionary: &Dictionary,
+) -> Result<Vec<BoardRow>, Box<dyn Error>> {
+    if words.len() > BOARD_ROWS {
+        return Err(format!("At most {BOARD_ROWS} words can be imported").into());
+    }
+
+    if words.iter().any(|word| word.len() != dictionary.word_len()) {
+        return Err(format!("Words must have {} letters", dictionary.word_len()).into());
+    }
+
+    Ok(board_from_image(path, words)?)
+}
+
+const DICTS: [&str; 3] = [
+    "words.txt",
+    "words.txt.gz",
+    "/etc/dictionaries-common/words",
+];
+
+/// Directories relative to the executable holding the bundled word list when installed by
+/// package.sh, for Windows, the macOS app bundle and Linux respectively
+const PACKAGED_DICT_DIRS: [&str; 3] = [".", "../Resources", "../share/solvegui"];
+
+fn default_dict() -> String {
+    packaged_dict()
+        .or_else(|| DICTS.iter().find_map(|d| dict_valid(d)))
+        .unwrap_or_default()
+}
+
+/// Finds the bundled word list installed alongside the executable
+fn packaged_dict() -> Option<String> {
+    let exe = std::env::current_exe().ok()?;
+    let dir = exe.parent()?;
+
+    PACKAGED_DICT_DIRS
+        .iter()
+        .map(|sub| dir.join(sub).join(BUNDLED_DICT))
+        .find(|path| path.is_file())
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+fn dict_valid(dict: &str) -> Option<String> {
+    if Path::new(dict).is_file() {
+        Some(dict.into())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,35 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some("SOLVER_FFI_H".into()),
+        autogen_warning: Some(
+            "/* Generated by cbindgen from solver-ffi/src/lib.rs. Do not edit */".into(),
+        ),
+        cpp_compat: true,
+        usize_is_size_t: true,
+        enumeration: cbindgen::EnumConfig {
+            prefix_with_name: true,
+            ..Default::default()
+        },
+        export: cbindgen::ExportConfig {
+            // Not used in any signatures so must be listed
+            include: vec!["WordleState".into()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file(crate_dir.join("include").join("solver_ffi.h"));
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+}
@@ -0,0 +1,135 @@
+#ifndef SOLVER_FFI_H
+#define SOLVER_FFI_H
+
+/* Generated by cbindgen from solver-ffi/src/lib.rs. Do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Board cell state
+ */
+enum WordleState
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  /**
+   * Empty board space
+   */
+  WordleState_Empty = 0,
+  /**
+   * Letter not in the solution
+   */
+  WordleState_Gray = 1,
+  /**
+   * Letter in the solution but in the wrong place
+   */
+  WordleState_Yellow = 2,
+  /**
+   * Letter in the solution and in the correct place
+   */
+  WordleState_Green = 3,
+  /**
+   * Letter without a colour, giving no information about the solution
+   */
+  WordleState_Unknown = 4,
+};
+#ifndef __cplusplus
+typedef uint8_t WordleState;
+#endif // __cplusplus
+
+/**
+ * Dictionary handle
+ */
+typedef struct WordleDictionary WordleDictionary;
+
+/**
+ * Words found by the solver
+ */
+typedef struct WordleWords WordleWords;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a dictionary from a buffer of words, one per line
+ * Returns null if the buffer can't be loaded
+ *
+ * # Safety
+ *
+ * `data` must point to `len` readable bytes
+ */
+struct WordleDictionary *wordle_dictionary_new(const uint8_t *data, size_t len, bool ignore_case);
+
+/**
+ * Returns the length of the words in a dictionary, which is the number of board columns
+ *
+ * # Safety
+ *
+ * `dictionary` must have been returned by `wordle_dictionary_new` and not freed
+ */
+size_t wordle_dictionary_word_len(const struct WordleDictionary *dictionary);
+
+/**
+ * Frees a dictionary. Null is ignored
+ *
+ * # Safety
+ *
+ * `dictionary` must be null or have been returned by `wordle_dictionary_new` and not freed
+ */
+void wordle_dictionary_free(struct WordleDictionary *dictionary);
+
+/**
+ * Finds the words matching a board
+ * The board is given as `rows` rows of letters and states, each as long as the dictionary words.
+ * Letters may be upper or lower case and are ignored for empty cells
+ * Returns null if a letter or state is invalid
+ *
+ * # Safety
+ *
+ * `dictionary` must have been returned by `wordle_dictionary_new` and not freed. `letters` and
+ * `states` must each point to rows * word length readable bytes
+ */
+struct WordleWords *wordle_solve(const struct WordleDictionary *dictionary,
+                                 const uint8_t *letters,
+                                 const uint8_t *states,
+                                 size_t rows);
+
+/**
+ * Returns the number of words found
+ *
+ * # Safety
+ *
+ * `words` must have been returned by `wordle_solve` and not freed
+ */
+size_t wordle_words_count(const struct WordleWords *words);
+
+/**
+ * Returns a found word as an upper case nul terminated string, or null if the index is out of
+ * range. The string is freed with the words
+ *
+ * # Safety
+ *
+ * `words` must have been returned by `wordle_solve` and not freed
+ */
+const char *wordle_words_get(const struct WordleWords *words, size_t index);
+
+/**
+ * Frees the words found by the solver. Null is ignored
+ *
+ * # Safety
+ *
+ * `words` must be null or have been returned by `wordle_solve` and not freed
+ */
+void wordle_words_free(struct WordleWords *words);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SOLVER_FFI_H */
@@ -0,0 +1,262 @@
+#![warn(missing_docs)]
+
+//! C interface to the wordle solver
+//!
+//! The header for these functions is generated in include/solver_ffi.h when the crate is built
+
+use std::ffi::{c_char, CString};
+use std::{ptr, slice};
+
+use dictionary::{Dictionary, WordCase};
+use solver::{find_words, BoardElem, BoardRow, SolverArgs, Standard};
+
+/// Dictionary handle
+pub struct WordleDictionary(Dictionary);
+
+/// Words found by the solver
+pub struct WordleWords(Vec<CString>);
+
+/// Board cell state
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordleState {
+    /// Empty board space
+    Empty = 0,
+    /// Letter not in the solution
+    Gray = 1,
+    /// Letter in the solution but in the wrong place
+    Yellow = 2,
+    /// Letter in the solution and in the correct place
+    Green = 3,
+    /// Letter without a colour, giving no information about the solution
+    Unknown = 4,
+}
+
+/// Creates a dictionary from a buffer of words, one per line
+/// Returns null if the buffer can't be loaded
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn wordle_dictionary_new(
+    data: *const u8,
+    len: usize,
+    ignore_case: bool,
+) -> *mut WordleDictionary {
+    if data.is_null() {
+        return ptr::null_mut();
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(data, len) };
+
+    let case = if ignore_case {
+        WordCase::Normalise
+    } else {
+        WordCase::Lower
+    };
+
+    match Dictionary::new_from_bytes(bytes, case, false) {
+        Ok(dictionary) => Box::into_raw(Box::new(WordleDictionary(dictionary))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Returns the length of the words in a dictionary, which is the number of board columns
+///
+/// # Safety
+///
+/// `dictionary` must have been returned by `wordle_dictionary_new` and not freed
+#[no_mangle]
+pub unsafe extern "C" fn wordle_dictionary_word_len(dictionary: *const WordleDictionary) -> usize {
+    unsafe { &(*dictionary).0 }.word_len()
+}
+
+/// Frees a dictionary. Null is ignored
+///
+/// # Safety
+///
+/// `dictionary` must be null or have been returned by `wordle_dictionary_new` and not freed
+#[no_mangle]
+pub unsafe extern "C" fn wordle_dictionary_free(dictionary: *mut WordleDictionary) {
+    if !dictionary.is_null() {
+        drop(unsafe { Box::from_raw(dictionary) });
+    }
+}
+
+/// Finds the words matching a board
+/// The board is given as `rows` rows of letters and states, each as long as the dictionary words.
+/// Letters may be upper or lower case and are ignored for empty cells
+/// Returns null if a letter or state is invalid
+///
+/// # Safety
+///
+/// `dictionary` must have been returned by `wordle_dictionary_new` and not freed. `letters` and
+/// `states` must each point to rows * word length readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solve(
+    dictionary: *const WordleDictionary,
+    letters: *const u8,
+    states: *const u8,
+    rows: usize,
+) -> *mut WordleWords {
+    let dictionary = unsafe { &(*dictionary).0 };
+    let cols = dictionary.word_len();
+
+    let (letters, states) = if rows == 0 {
+        (&[][..], &[][..])
+    } else if letters.is_null() || states.is_null() {
+        return ptr::null_mut();
+    } else {
+        unsafe {
+            (
+                slice::from_raw_parts(letters, rows * cols),
+                slice::from_raw_parts(states, rows * cols),
+            )
+        }
+    };
+
+    let Some(board) = board(letters, states, cols) else {
+        return ptr::null_mut();
+    };
+
+    let words = find_words(SolverArgs {
+        board: &board,
+        dictionary,
+        rules: &Standard,
+        debug: false,
+        cancel: None,
+    })
+    .words
+    .iter()
+    .map(|elem| {
+        CString::new(dictionary.get_word(*elem as usize)).expect("Word contains a nul character")
+    })
+    .collect();
+
+    Box::into_raw(Box::new(WordleWords(words)))
+}
+
+/// Returns the number of words found
+///
+/// # Safety
+///
+/// `words` must have been returned by `wordle_solve` and not freed
+#[no_mangle]
+pub unsafe extern "C" fn wordle_words_count(words: *const WordleWords) -> usize {
+    unsafe { &(*words).0 }.len()
+}
+
+/// Returns a found word as an upper case nul terminated string, or null if the index is out of
+/// range. The string is freed with the words
+///
+/// # Safety
+///
+/// `words` must have been returned by `wordle_solve` and not freed
+#[no_mangle]
+pub unsafe extern "C" fn wordle_words_get(
+    words: *const WordleWords,
+    index: usize,
+) -> *const c_char {
+    match unsafe { &(*words).0 }.get(index) {
+        Some(word) => word.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Frees the words found by the solver. Null is ignored
+///
+/// # Safety
+///
+/// `words` must be null or have been returned by `wordle_solve` and not freed
+#[no_mangle]
+pub unsafe extern "C" fn wordle_words_free(words: *mut WordleWords) {
+    if !words.is_null() {
+        drop(unsafe { Box::from_raw(words) });
+    }
+}
+
+/// Builds the board from the letter and state arrays
+fn board(letters: &[u8], states: &[u8], cols: usize) -> Option<Vec<BoardRow>> {
+    letters
+        .chunks(cols)
+        .zip(states.chunks(cols))
+        .map(|(letters, states)| {
+            letters
+                .iter()
+                .zip(states.iter())
+                .map(|(letter, state)| {
+                    let c = letter.to_ascii_uppercase() as char;
+
+                    match *state {
+                        s if s == WordleState::Empty as u8 => Some(BoardElem::Empty),
+                        _ if !c.is_ascii_uppercase() => None,
+                        s if s == WordleState::Gray as u8 => Some(BoardElem::Gray(c)),
+                        s if s == WordleState::Yellow as u8 => Some(BoardElem::Yellow(c)),
+                        s if s == WordleState::Green as u8 => Some(BoardElem::Green(c)),
+                        s if s == WordleState::Unknown as u8 => Some(BoardElem::Unknown(c)),
+                        _ => None,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    const WORDS: &str = "crane\nprank\nstair\ntrace\n";
+
+    fn words(result: *const WordleWords) -> Vec<String> {
+        unsafe {
+            (0..wordle_words_count(result))
+                .map(|i| {
+                    CStr::from_ptr(wordle_words_get(result, i))
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn solve() {
+        unsafe {
+            let dictionary = wordle_dictionary_new(WORDS.as_ptr(), WORDS.len(), false);
+            assert!(!dictionary.is_null());
+            assert_eq!(wordle_dictionary_word_len(dictionary), 5);
+
+            // Empty board finds every word
+            let result = wordle_solve(dictionary, ptr::null(), ptr::null(), 0);
+            assert_eq!(words(result), ["CRANE", "PRANK", "STAIR", "TRACE"]);
+            assert!(wordle_words_get(result, 4).is_null());
+            wordle_words_free(result);
+
+            // CRANE played against PRANK
+            let states = [1, 3, 3, 3, 1];
+            let result = wordle_solve(dictionary, b"crane".as_ptr(), states.as_ptr(), 1);
+            assert_eq!(words(result), ["PRANK"]);
+            wordle_words_free(result);
+
+            // Letters without a colour are ignored
+            let states = [4, 3, 3, 3, 4];
+            let result = wordle_solve(dictionary, b"CRANE".as_ptr(), states.as_ptr(), 1);
+            assert_eq!(words(result), ["CRANE", "PRANK"]);
+            wordle_words_free(result);
+
+            // Invalid state
+            let states = [1, 3, 3, 3, 5];
+            assert!(wordle_solve(dictionary, b"CRANE".as_ptr(), states.as_ptr(), 1).is_null());
+
+            // Invalid letter
+            let states = [1, 3, 3, 3, 1];
+            assert!(wordle_solve(dictionary, b"CRAN!".as_ptr(), states.as_ptr(), 1).is_null());
+
+            wordle_dictionary_free(dictionary);
+        }
+    }
+}
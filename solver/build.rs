@@ -0,0 +1,69 @@
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    // Embed the commit the solver is built from
+    let hash = git(&["rev-parse", "--short", "HEAD"]).unwrap_or_else(|| "unknown".into());
+
+    println!("cargo:rustc-env=SOLVER_GIT_HASH={hash}");
+
+    // Embed the build date, taken from SOURCE_DATE_EPOCH for reproducible builds
+    let secs = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        });
+
+    println!("cargo:rustc-env=SOLVER_BUILD_DATE={}", date(secs));
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Rebuild when a new commit is checked out or made on the current branch
+    let head = git(&["symbolic-ref", "-q", "HEAD"]);
+
+    for file in ["HEAD"].into_iter().chain(head.as_deref()) {
+        if let Some(path) = git(&["rev-parse", "--git-path", file]) {
+            if Path::new(&path).exists() {
+                println!("cargo:rerun-if-changed={path}");
+            }
+        }
+    }
+}
+
+/// Runs git, returning the first line of its output if successful
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8(output.stdout).ok()?;
+
+    stdout.lines().next().map(|line| line.trim().to_string())
+}
+
+/// Converts seconds since the Unix epoch to a YYYY-MM-DD date
+fn date(secs: u64) -> String {
+    // Days since 1 March 0000, so leap days fall at the end of each year
+    let days = secs / 86400 + 719_468;
+
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+
+    // Months are counted from March
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,119 @@
+//! Compact text form of a board
+
+use crate::{BoardElem, BoardRow};
+
+/// Board rows with a compact text form of GUESS:PATTERN pairs separated by '/', where the pattern
+/// has 'g' for a green letter, 'y' for yellow, 'b' for gray and '?' for a letter without a colour
+/// (eg. CRANE:bbygb/STAIR:gbbby). Empty spaces in a partly typed row are '_' in both the guess and
+/// the pattern (eg. CRANE:bbygb/ST___:??___)
+///
+/// When parsing, '.' is also accepted for gray and pairs can be separated by whitespace. Rows
+/// without any letters are left out
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BoardState(pub Vec<BoardRow>);
+
+impl BoardState {
+    /// Returns the letter and pattern character for a board element
+    fn elem_chars(elem: &BoardElem) -> (char, char) {
+        match elem {
+            BoardElem::Empty => ('_', '_'),
+            BoardElem::Unknown(c) => (*c, '?'),
+            BoardElem::Gray(c) => (*c, 'b'),
+            BoardElem::Yellow(c) => (*c, 'y'),
+            BoardElem::Green(c) => (*c, 'g'),
+        }
+    }
+
+    /// Parses a GUESS:PATTERN pair
+    fn parse_row(pair: &str) -> Result<BoardRow, String> {
+        let Some((guess, pattern)) = pair.split_once(':') else {
+            return Err(format!("{pair} isn't a GUESS:PATTERN pair"));
+        };
+
+        if guess.is_empty() || !guess.chars().all(|c| c.is_ascii_alphabetic() || c == '_') {
+            return Err(format!("{guess} isn't a word"));
+        }
+
+        if pattern.chars().count() != guess.len() {
+            return Err(format!("{pattern} isn't {} colours long", guess.len()));
+        }
+
+        guess
+            .chars()
+            .map(|c| c.to_ascii_uppercase())
+            .zip(pattern.chars())
+            .map(|(c, colour)| match (c, colour.to_ascii_lowercase()) {
+                ('_', '_') => Ok(BoardElem::Empty),
+                ('_', _) | (_, '_') => {
+                    Err("Empty spaces need '_' in the guess and the pattern".to_string())
+                }
+                (_, 'b' | '.') => Ok(BoardElem::Gray(c)),
+                (_, 'y') => Ok(BoardElem::Yellow(c)),
+                (_, 'g') => Ok(BoardElem::Green(c)),
+                (_, '?') => Ok(BoardElem::Unknown(c)),
+                _ => Err(format!("{colour} isn't a colour, use 'b', 'y', 'g' or '?'")),
+            })
+            .collect()
+    }
+}
+
+impl std::str::FromStr for BoardState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .split(|c: char| c == '/' || c.is_whitespace())
+            .filter(|pair| !pair.is_empty())
+            .map(Self::parse_row)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if rows.windows(2).any(|pair| pair[0].len() != pair[1].len()) {
+            return Err("Guesses must all be the same length".to_string());
+        }
+
+        Ok(Self(rows))
+    }
+}
+
+impl std::fmt::Display for BoardState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = self
+            .0
+            .iter()
+            .filter(|row| row.iter().any(|elem| !matches!(elem, BoardElem::Empty)));
+
+        for (i, row) in rows.enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+
+            let (guess, pattern): (String, String) = row.iter().map(Self::elem_chars).unzip();
+
+            write!(f, "{guess}:{pattern}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Vec<BoardRow>> for BoardState {
+    fn from(rows: Vec<BoardRow>) -> Self {
+        Self(rows)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BoardState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BoardState {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,31 @@
+//! Cooperative cancellation of long searches
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Token used to abort a search from another thread
+/// Clones share the same flag, so a clone can be kept by whoever may need to cancel the search
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Creates a token which hasn't been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks searches using the token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Clears the cancellation so the token can be used for another search
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    /// Returns true if the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,149 @@
+//! Candidate word sets
+
+use dictionary::{Dictionary, LetterNext};
+
+/// Set of candidate words from a dictionary
+/// Stored as a bitset over the dictionary words so set operations and counting are fast
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Candidates {
+    /// Tree element of the first dictionary word
+    first: usize,
+    /// Bit set for each word in the set
+    bits: Vec<u64>,
+}
+
+impl Candidates {
+    /// Creates an empty set for a dictionary
+    pub fn new(dictionary: &Dictionary) -> Self {
+        let range = dictionary.word_range();
+
+        Self {
+            first: range.start,
+            bits: vec![0; range.len().div_ceil(64)],
+        }
+    }
+
+    /// Creates a set containing every word in a dictionary
+    pub fn all(dictionary: &Dictionary) -> Self {
+        let mut result = Self::new(dictionary);
+
+        for elem in dictionary.word_elems() {
+            result.insert(elem as LetterNext);
+        }
+
+        result
+    }
+
+    /// Creates a set from a list of dictionary elements
+    pub fn from_words(dictionary: &Dictionary, words: &[LetterNext]) -> Self {
+        let mut result = Self::new(dictionary);
+
+        for elem in words {
+            result.insert(*elem);
+        }
+
+        result
+    }
+
+    /// Adds a word to the set
+    pub fn insert(&mut self, elem: LetterNext) {
+        let (word, bit) = self.position(elem);
+
+        self.bits[word] |= bit;
+    }
+
+    /// Removes a word from the set
+    pub fn remove(&mut self, elem: LetterNext) {
+        let (word, bit) = self.position(elem);
+
+        self.bits[word] &= !bit;
+    }
+
+    /// Tests if a word is in the set
+    pub fn contains(&self, elem: LetterNext) -> bool {
+        if (elem as usize) < self.first {
+            return false;
+        }
+
+        let (word, bit) = self.position(elem);
+
+        self.bits.get(word).is_some_and(|bits| bits & bit != 0)
+    }
+
+    /// Returns the number of words in the set
+    pub fn count(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns true if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|bits| *bits == 0)
+    }
+
+    /// Returns the words in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Returns the words in either set
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Returns the words in this set but not the other
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Iterates the dictionary elements of the words in the set in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = LetterNext> + '_ {
+        self.bits.iter().enumerate().flat_map(move |(word, bits)| {
+            let mut bits = *bits;
+
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    None
+                } else {
+                    let bit = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+
+                    Some((self.first + (word * 64) + bit) as LetterNext)
+                }
+            })
+        })
+    }
+
+    /// Returns the dictionary elements of the words in the set in alphabetical order
+    pub fn to_words(&self) -> Vec<LetterNext> {
+        self.iter().collect()
+    }
+
+    /// Combines two sets a word at a time
+    fn combine(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(
+            self.first, other.first,
+            "Candidates from different dictionaries"
+        );
+
+        Self {
+            first: self.first,
+            bits: self
+                .bits
+                .iter()
+                .zip(other.bits.iter())
+                .map(|(a, b)| op(*a, *b))
+                .collect(),
+        }
+    }
+
+    /// Returns the bits word index and bit mask for a dictionary element
+    #[inline]
+    fn position(&self, elem: LetterNext) -> (usize, u64) {
+        let index = elem as usize - self.first;
+
+        (index / 64, 1 << (index % 64))
+    }
+}
@@ -0,0 +1,467 @@
+//! Constraints derived from the board
+
+use std::cmp;
+use std::collections::HashMap;
+
+use dictionary::{Dictionary, LetterNext, MAX_WORD_LEN};
+use log::{debug, log_enabled, Level};
+
+use crate::{BoardElem, BoardRow};
+
+/// Letter count constraint, with the fewest and most times the letter can appear
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Contains {
+    /// Fewest times the letter appears
+    pub(crate) min: u8,
+    /// Most times the letter appears, or None if there is no limit
+    pub(crate) max: Option<u8>,
+}
+
+impl Contains {
+    /// Letter appears at least n times
+    pub(crate) const fn at_least(n: u8) -> Self {
+        Self { min: n, max: None }
+    }
+
+    /// Letter appears exactly n times
+    pub(crate) const fn exactly(n: u8) -> Self {
+        Self {
+            min: n,
+            max: Some(n),
+        }
+    }
+
+    /// Combines the bounds from two rows, taking the highest minimum and the lowest maximum
+    fn merge(self, other: Self) -> Self {
+        Self {
+            min: cmp::max(self.min, other.min),
+            max: match (self.max, other.max) {
+                (Some(a), Some(b)) => Some(cmp::min(a, b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+
+    /// Tests if a letter count is within the bounds
+    fn allows(&self, count: u8) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+}
+
+/// A board which can't be given by any answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Contradiction {
+    /// A letter is yellow in a column where it is green on another row, so it is both in and not
+    /// in that place
+    YellowGreen {
+        /// Letter
+        letter: char,
+        /// Column number, from 0
+        col: usize,
+    },
+    /// Two different letters are green in the same column
+    TwoGreens {
+        /// The letters, in board order
+        letters: [char; 2],
+        /// Column number, from 0
+        col: usize,
+    },
+    /// A letter is gray in a column where it is green on another row, so it is both in and not
+    /// in that place
+    GrayGreen {
+        /// Letter
+        letter: char,
+        /// Column number, from 0
+        col: usize,
+    },
+    /// One row shows more of a letter than another row's gray letter, or a letter count set by
+    /// hand, allows
+    Count {
+        /// Letter
+        letter: char,
+        /// Fewest times the letter is shown to appear
+        min: u8,
+        /// Most times the letter can appear
+        max: u8,
+    },
+}
+
+impl std::fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::YellowGreen { letter, col } => write!(
+                f,
+                "{letter} is yellow in column {} where it is green",
+                col + 1
+            ),
+            Self::TwoGreens { letters, col } => write!(
+                f,
+                "{} and {} are both green in column {}",
+                letters[0],
+                letters[1],
+                col + 1
+            ),
+            Self::GrayGreen { letter, col } => write!(
+                f,
+                "{letter} is gray in column {} where it is green",
+                col + 1
+            ),
+            Self::Count { letter, min, max } => write!(
+                f,
+                "{letter} is in the word at least {min} times but can appear at most {max} times"
+            ),
+        }
+    }
+}
+
+/// Constraints on the solution derived from the board
+#[derive(Clone, Debug)]
+pub struct Constraints {
+    /// Correct letters
+    pub(crate) correct: Vec<Option<u8>>,
+    /// Incorrect letters for each column
+    pub(crate) incorrect: Vec<[bool; 26]>,
+    /// Letter counts
+    pub(crate) contains: HashMap<u8, Contains>,
+    /// Unused letters
+    pub(crate) unused: [bool; 26],
+    /// Contradiction between the board rows which the other constraints can't show
+    pub(crate) conflict: Option<Contradiction>,
+}
+
+impl Constraints {
+    /// Builds the constraints from a board with rows of the given length
+    pub fn new(board: &[BoardRow], cols: usize) -> Self {
+        // Correct letters
+        let mut correct = vec![None; cols];
+
+        // Incorrect letters
+        let mut incorrect = vec![[false; 26]; cols];
+        let mut contains = HashMap::new();
+
+        // Unused letters
+        let mut unused = [false; 26];
+
+        // Letter count bounds merged from every row
+        let mut bounds: HashMap<u8, Contains> = HashMap::new();
+
+        // First contradiction between the rows which the constraints can't show
+        let mut conflict = None;
+
+        // Iterate each row
+        for row in board {
+            // Yellow and green letters in the row, and letters gray in the row
+            let mut rowcontains: HashMap<u8, u8> = HashMap::new();
+            let mut rowgray = [false; 26];
+
+            // Iterate each letter in the row
+            for (elem, col) in row.iter().enumerate() {
+                match col {
+                    BoardElem::Gray(c) => rowgray[Dictionary::uchar_to_usize(*c)] = true,
+                    BoardElem::Yellow(c) => {
+                        incorrect[elem][Dictionary::uchar_to_usize(*c)] = true;
+                        *rowcontains.entry(Dictionary::uchar_to_u8(*c)).or_default() += 1;
+                    }
+                    BoardElem::Green(c) => {
+                        let letter = Dictionary::uchar_to_u8(*c);
+
+                        if let Some(other) = correct[elem].filter(|other| *other != letter) {
+                            conflict.get_or_insert(Contradiction::TwoGreens {
+                                letters: [(other + b'A') as char, *c],
+                                col: elem,
+                            });
+                        }
+
+                        correct[elem] = Some(letter);
+                        *rowcontains.entry(Dictionary::uchar_to_u8(*c)).or_default() += 1;
+                    }
+                    _ => (),
+                }
+            }
+
+            // The row shows at least as many of each letter as it has yellow and green, and
+            // exactly that many if the letter is also gray
+            let letters = (0..26u8).filter(|l| rowgray[*l as usize] || rowcontains.contains_key(l));
+
+            for letter in letters {
+                let count = rowcontains.get(&letter).copied().unwrap_or_default();
+
+                let row_bounds = if rowgray[letter as usize] {
+                    Contains::exactly(count)
+                } else {
+                    Contains::at_least(count)
+                };
+
+                bounds
+                    .entry(letter)
+                    .and_modify(|bounds| *bounds = bounds.merge(row_bounds))
+                    .or_insert(row_bounds);
+            }
+        }
+
+        // A gray letter in a column where it is green on another row
+        if conflict.is_none() {
+            conflict = board.iter().find_map(|row| {
+                row.iter().enumerate().find_map(|(col, elem)| match elem {
+                    BoardElem::Gray(c) if correct[col] == Some(Dictionary::uchar_to_u8(*c)) => {
+                        Some(Contradiction::GrayGreen { letter: *c, col })
+                    }
+                    _ => None,
+                })
+            });
+        }
+
+        // Letters which can't appear are unused, the rest have a letter count constraint
+        for (letter, bounds) in bounds {
+            if bounds == Contains::exactly(0) {
+                unused[letter as usize] = true;
+            } else {
+                contains.insert(letter, bounds);
+            }
+        }
+
+        let constraints = Self {
+            correct,
+            incorrect,
+            contains,
+            unused,
+            conflict,
+        };
+
+        if log_enabled!(Level::Debug) {
+            constraints.debug();
+        }
+
+        constraints
+    }
+
+    /// Checks the constraints can be met by some word
+    /// Two different green letters in a column, a letter which is yellow or gray in a column
+    /// where it is green, or a letter shown more times than a gray letter allows, is a
+    /// contradiction. Constraints with a yellow or count contradiction match no words
+    pub fn validate(&self) -> Result<(), Contradiction> {
+        if let Some(conflict) = self.conflict {
+            return Err(conflict);
+        }
+
+        for (col, (correct, incorrect)) in self.correct.iter().zip(&self.incorrect).enumerate() {
+            if let Some(letter) = correct {
+                if incorrect[*letter as usize] {
+                    return Err(Contradiction::YellowGreen {
+                        letter: (letter + b'A') as char,
+                        col,
+                    });
+                }
+            }
+        }
+
+        let mut counts: Vec<_> = self.contains.iter().collect();
+        counts.sort_by_key(|(letter, _)| **letter);
+
+        for (letter, contains) in counts {
+            if let Some(max) = contains.max.filter(|max| *max < contains.min) {
+                return Err(Contradiction::Count {
+                    letter: (letter + b'A') as char,
+                    min: contains.min,
+                    max,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the fewest and most times an upper case letter can appear, with no most if there
+    /// is no limit
+    pub fn count(&self, letter: char) -> (u8, Option<u8>) {
+        let letter = Dictionary::uchar_to_u8(letter);
+
+        if self.unused[letter as usize] {
+            return (0, Some(0));
+        }
+
+        match self.contains.get(&letter) {
+            Some(contains) => (contains.min, contains.max),
+            None => (0, None),
+        }
+    }
+
+    /// Restricts the number of times an upper case letter can appear, eg. from knowledge the
+    /// board can't show. The bounds are combined with the existing ones, keeping the tighter of
+    /// each, so a count can only be narrowed
+    pub fn restrict_count(&mut self, letter: char, min: u8, max: Option<u8>) {
+        let letter = Dictionary::uchar_to_u8(letter);
+
+        let bounds = if self.unused[letter as usize] {
+            Contains::exactly(0)
+        } else {
+            self.contains
+                .get(&letter)
+                .copied()
+                .unwrap_or(Contains::at_least(0))
+        };
+
+        let bounds = bounds.merge(Contains { min, max });
+
+        if bounds == Contains::exactly(0) {
+            self.unused[letter as usize] = true;
+            self.contains.remove(&letter);
+        } else {
+            self.unused[letter as usize] = false;
+            self.contains.insert(letter, bounds);
+        }
+    }
+
+    /// Returns true if every word matching these constraints also matches the other constraints,
+    /// ie. these constraints are the same as or tighter than the other
+    pub fn implies(&self, other: &Self) -> bool {
+        // Correct letters must be kept
+        let correct = self
+            .correct
+            .iter()
+            .zip(other.correct.iter())
+            .all(|(new, old)| old.is_none() || new == old);
+
+        // Incorrect and unused letters must be kept
+        let incorrect = self
+            .incorrect
+            .iter()
+            .zip(other.incorrect.iter())
+            .all(|(new, old)| Self::superset(new, old));
+
+        let unused = Self::superset(&self.unused, &other.unused);
+
+        // Letter counts must be the same or tighter
+        let contains = other.contains.iter().all(|(letter, old)| {
+            self.contains.get(letter).is_some_and(|new| {
+                new.min >= old.min
+                    && old
+                        .max
+                        .is_none_or(|old| new.max.is_some_and(|new| new <= old))
+            })
+        });
+
+        correct && incorrect && unused && contains
+    }
+
+    /// Returns the letters which may appear in a column, indexed by letter number
+    pub fn allowed_letters(&self, col: usize) -> [bool; 26] {
+        let mut allowed = [false; 26];
+
+        match self.correct[col] {
+            Some(letter) => allowed[letter as usize] = !self.incorrect[col][letter as usize],
+            None => {
+                for (letter, allowed) in allowed.iter_mut().enumerate() {
+                    *allowed = !self.unused[letter] && !self.incorrect[col][letter];
+                }
+
+                // Letters with a maximum count all placed in other columns can't appear here
+                for (letter, contains) in &self.contains {
+                    if let Some(max) = contains.max {
+                        let placed = self
+                            .correct
+                            .iter()
+                            .filter(|correct| **correct == Some(*letter))
+                            .count();
+
+                        if placed >= max as usize {
+                            allowed[*letter as usize] = false;
+                        }
+                    }
+                }
+            }
+        }
+
+        allowed
+    }
+
+    /// Tests if a dictionary word matches the constraints
+    pub fn matches(&self, dictionary: &Dictionary, elem: LetterNext) -> bool {
+        self.letters_valid(dictionary, elem) && self.contains_valid(dictionary, elem as usize)
+    }
+
+    /// Tests if each letter of a dictionary word is allowed in its position
+    pub(crate) fn letters_valid(&self, dictionary: &Dictionary, elem: LetterNext) -> bool {
+        let mut word = [0; MAX_WORD_LEN];
+        let len = dictionary.get_word_into(elem as usize, &mut word);
+
+        word[..len].iter().enumerate().all(|(col, c)| {
+            let letter = c - b'A';
+
+            match self.correct[col] {
+                Some(correct) => correct == letter && !self.incorrect[col][letter as usize],
+                None => !self.unused[letter as usize] && !self.incorrect[col][letter as usize],
+            }
+        })
+    }
+
+    /// Tests if an upper case word uses all of the hints, keeping the correct letters in place and
+    /// containing at least as many of each letter as has been found
+    pub fn uses_hints(&self, word: &str) -> bool {
+        let letters: Vec<u8> = word.chars().map(Dictionary::uchar_to_u8).collect();
+
+        let correct = self
+            .correct
+            .iter()
+            .zip(letters.iter())
+            .all(|(correct, letter)| correct.is_none_or(|correct| correct == *letter));
+
+        let contains = self.contains.iter().all(|(letter, contains)| {
+            letters.iter().filter(|l| *l == letter).count() >= contains.min as usize
+        });
+
+        correct && contains
+    }
+
+    /// Tests if a dictionary word satisfies the letter count constraints
+    pub(crate) fn contains_valid(&self, dictionary: &Dictionary, elem: usize) -> bool {
+        self.contains
+            .iter()
+            .all(|(c, contains)| contains.allows(dictionary.letter_count(elem, *c)))
+    }
+
+    /// Returns true if every set letter in old is set in new
+    fn superset(new: &[bool; 26], old: &[bool; 26]) -> bool {
+        new.iter().zip(old.iter()).all(|(new, old)| *new || !*old)
+    }
+
+    /// Logs the constraints
+    #[cold]
+    fn debug(&self) {
+        let letter = |l: u8| (l + b'A') as char;
+
+        let letters = |set: &[bool; 26]| {
+            set.iter()
+                .enumerate()
+                .filter(|(_, set)| **set)
+                .map(|(l, _)| letter(l as u8))
+                .collect::<String>()
+        };
+
+        let correct = self
+            .correct
+            .iter()
+            .map(|c| c.map(letter).unwrap_or('.'))
+            .collect::<String>();
+
+        let incorrect = self
+            .incorrect
+            .iter()
+            .map(letters)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut contains = self
+            .contains
+            .iter()
+            .map(|(l, c)| format!("{}:{:?}", letter(*l), c))
+            .collect::<Vec<_>>();
+        contains.sort();
+
+        debug!(
+            "Constraints: correct {correct}, incorrect {incorrect}, contains [{}], unused {}",
+            contains.join(", "),
+            letters(&self.unused)
+        );
+    }
+}
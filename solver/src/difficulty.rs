@@ -0,0 +1,103 @@
+//! Answer difficulty rating
+
+use crate::{play_answer, Distribution, WordLetters};
+
+/// Mean guesses below which an answer is rated easy
+const EASY_GUESSES: f32 = 3.5;
+
+/// Mean guesses at or above which an answer is rated hard
+const HARD_GUESSES: f32 = 4.5;
+
+/// How hard a word is to find as the answer
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Difficulty {
+    /// Mean fraction of the answers without each letter of the word, from 0 (letters in every
+    /// answer) to 1 (letters in no other answer)
+    pub rarity: f32,
+    /// Number of letters used more than once
+    pub repeats: usize,
+    /// Mean guesses for the default strategy to solve the word after each opener, counting failed
+    /// games as one more guess than the board rows
+    pub guesses: f32,
+}
+
+impl Difficulty {
+    /// Returns a description of the difficulty from the mean guesses
+    pub fn rating(&self) -> &'static str {
+        if self.guesses < EASY_GUESSES {
+            "easy"
+        } else if self.guesses < HARD_GUESSES {
+            "average"
+        } else {
+            "hard"
+        }
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:.1} avg guesses, letter rarity {:.2}, {} repeated letter{}",
+            self.rating(),
+            self.guesses,
+            self.rarity,
+            self.repeats,
+            if self.repeats == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// Rates an answer from a set of equally likely answers by how rare its letters are, how many
+/// letters it repeats and how many guesses the default strategy takes to solve it after each of
+/// the openers
+pub fn difficulty(answers: &[WordLetters], answer: usize, openers: &[WordLetters]) -> Difficulty {
+    let word = &answers[answer];
+
+    // Count the answers containing each letter
+    let mut counts = [0usize; 26];
+
+    for letters in answers {
+        let mut seen = [false; 26];
+
+        for l in letters {
+            seen[*l as usize] = true;
+        }
+
+        for (count, seen) in counts.iter_mut().zip(seen) {
+            *count += seen as usize;
+        }
+    }
+
+    // Find the distinct letters of the word
+    let mut used = [0usize; 26];
+
+    for l in word {
+        used[*l as usize] += 1;
+    }
+
+    let distinct: Vec<usize> = (0..26).filter(|l| used[*l] > 0).collect();
+
+    let rarity = distinct
+        .iter()
+        .map(|l| 1.0 - counts[*l] as f32 / answers.len() as f32)
+        .sum::<f32>()
+        / distinct.len().max(1) as f32;
+
+    let repeats = distinct.iter().filter(|l| used[**l] > 1).count();
+
+    // Play the answer after each opener
+    let set: Vec<usize> = (0..answers.len()).collect();
+
+    let mut dist = Distribution::default();
+
+    for opener in openers {
+        dist.merge(&play_answer(answers, &set, opener, answer));
+    }
+
+    Difficulty {
+        rarity,
+        repeats,
+        guesses: dist.expected(),
+    }
+}
@@ -2,22 +2,77 @@
 
 //! Wordle helper
 
-use std::cmp;
-use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use dictionary::{Dictionary, LetterNext, NEXT_NONE};
 
-/// Number of columns on the board
-pub const BOARD_COLS: usize = 5;
+mod board_state;
+mod cancel;
+mod candidates;
+mod constraints;
+mod difficulty;
+mod random;
+mod rank;
+mod rules;
+mod simulate;
+mod strategy;
+mod version;
+
+pub use board_state::BoardState;
+pub use cancel::CancelToken;
+pub use candidates::Candidates;
+pub use constraints::{Constraints, Contradiction};
+pub use difficulty::{difficulty, Difficulty};
+pub use random::Random;
+pub use rank::{
+    letter_counts,
+    letter_information,
+    pattern,
+    patterns,
+    rank_words,
+    rank_words_cancellable,
+    score_guess,
+    word_letters,
+    LetterInformation,
+    Pattern,
+    WordLetters,
+};
+pub use rules::{game_rules, Absurdle, GameRules, HardMode, Quordle, Standard, GAME_RULES};
+pub use simulate::{
+    adversary,
+    best_guess,
+    partition,
+    partition_sizes,
+    play_answer,
+    play_answer_by,
+    play_guess,
+    play_partitions,
+    play_set,
+    solved_pattern,
+    Distribution,
+};
+pub use strategy::{
+    expected_guesses,
+    rank_letters_strategy,
+    rank_words_strategy,
+    ScoreKind,
+    Strategy,
+    EXPECTED_SEARCH_LIMIT,
+    HYBRID_THRESHOLD,
+};
+pub use version::{build_info, long_version, VERSION};
 
 /// Number of rows on the board
 pub const BOARD_ROWS: usize = 6;
 
 /// Board element
-#[derive(Clone, Copy, Debug, Hash)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoardElem {
     /// Empty board space
     Empty,
+    /// Letter entered without a colour yet, giving no information about the solution
+    Unknown(char),
     /// Gray board space (letter not in solution)
     Gray(char),
     /// Yellow board space (letter in solution but in the wrong place)
@@ -26,129 +81,264 @@ pub enum BoardElem {
     Green(char),
 }
 
+/// Board row, with a column for each letter of the dictionary's words
+pub type BoardRow = Vec<BoardElem>;
+
+/// Creates an empty board for words of the given length
+pub fn empty_board(cols: usize) -> Vec<BoardRow> {
+    vec![vec![BoardElem::Empty; cols]; BOARD_ROWS]
+}
+
 /// Arguments for the wordle helper
 pub struct SolverArgs<'a> {
-    /// Current board
-    pub board: &'a [[BoardElem; BOARD_COLS]; BOARD_ROWS],
+    /// Current board. Each row must be as long as the dictionary's words
+    pub board: &'a [BoardRow],
     /// Dictionary to use
     pub dictionary: &'a Dictionary,
+    /// Rules of the game being played
+    pub rules: &'a dyn GameRules,
     /// Debug output
     pub debug: bool,
+    /// Token to abort the search with, if it may need to be cancelled
+    pub cancel: Option<&'a CancelToken>,
+}
+
+impl SolverArgs<'_> {
+    /// Returns true if the search has been cancelled
+    fn cancelled(&self) -> bool {
+        self.cancel.is_some_and(CancelToken::is_cancelled)
+    }
+}
+
+/// Solver statistics
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SolveStats {
+    /// Number of dictionary tree nodes visited
+    pub nodes: usize,
+    /// Time taken to find the words
+    pub elapsed: Duration,
+    /// Number of words checked against the letter count constraints
+    pub contains_checks: usize,
+    /// Time spent checking the letter count constraints
+    pub contains_time: Duration,
+}
+
+impl SolveStats {
+    /// Returns the time spent walking the dictionary tree or checking letter positions, ie. the
+    /// time not spent checking the letter count constraints
+    pub fn walk_time(&self) -> Duration {
+        self.elapsed.saturating_sub(self.contains_time)
+    }
+
+    /// Times a letter count constraint check
+    fn time_contains(&mut self, check: impl FnOnce() -> bool) -> bool {
+        let start = Instant::now();
+        let valid = check();
+
+        self.contains_checks += 1;
+        self.contains_time += start.elapsed();
+
+        valid
+    }
+}
+
+/// Words found by the solver
+pub struct SolveResult {
+    /// Dictionary elements of the words found, in alphabetical order
+    pub words: Vec<LetterNext>,
+    /// Set of the words found
+    pub candidates: Candidates,
+    /// Solver statistics
+    pub stats: SolveStats,
+    /// True if the search was cancelled before it finished, so the words are incomplete
+    pub cancelled: bool,
 }
 
 struct SolverRec<'a> {
     args: SolverArgs<'a>,
-    correct: [Option<u8>; BOARD_COLS],
-    incorrect: [[bool; 26]; BOARD_COLS],
-    contains: HashMap<u8, Contains>,
-    unused: [bool; 26],
+    constraints: &'a Constraints,
 }
 
-enum Contains {
-    AtLeast(u8),
-    Exactly(u8),
+/// Calculates the board row for a guess played against a known answer
+/// Both words must be upper case and the same length
+pub fn feedback(guess: &str, answer: &str) -> BoardRow {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+
+    let mut result = vec![BoardElem::Empty; guess.len()];
+
+    // Count of each answer letter not matched by a green
+    let mut remaining = [0u8; 26];
+
+    // Mark green letters
+    for (col, elem) in result.iter_mut().enumerate() {
+        if guess[col] == answer[col] {
+            *elem = BoardElem::Green(guess[col]);
+        } else {
+            remaining[Dictionary::uchar_to_usize(answer[col])] += 1;
+        }
+    }
+
+    // Mark yellow and gray letters
+    for (col, elem) in result.iter_mut().enumerate() {
+        if let BoardElem::Empty = elem {
+            let count = &mut remaining[Dictionary::uchar_to_usize(guess[col])];
+
+            if *count > 0 {
+                *count -= 1;
+                *elem = BoardElem::Yellow(guess[col]);
+            } else {
+                *elem = BoardElem::Gray(guess[col]);
+            }
+        }
+    }
+
+    result
 }
 
 /// Find words in the provides dictionary using the provided letters
-pub fn find_words(args: SolverArgs) -> Vec<LetterNext> {
-    let mut result = Vec::new();
+/// The words are always returned in alphabetical order
+pub fn find_words(args: SolverArgs) -> SolveResult {
+    // Build constraints from the board
+    let constraints = args
+        .rules
+        .constraints(args.board, args.dictionary.word_len());
 
-    // Correct letters
-    let mut correct = [None; BOARD_COLS];
+    find_words_with(args, &constraints)
+}
 
-    // Incorrect letters
-    let mut incorrect = [[false; 26]; BOARD_COLS];
-    let mut contains = HashMap::new();
+/// Find words in the dictionary matching the provided constraints, eg. those built from the
+/// board with extra letter counts. The words are always returned in alphabetical order
+pub fn find_words_with(args: SolverArgs, constraints: &Constraints) -> SolveResult {
+    find_words_into(args, constraints, Vec::new())
+}
 
-    // Unused letters
-    let mut unused = [false; 26];
+/// Find words matching the provided constraints like find_words_with, collecting them in a word
+/// list which is cleared first. Passing the words from the previous search reuses their
+/// allocation, avoiding regrowing the list each time the board changes
+pub fn find_words_into(
+    args: SolverArgs,
+    constraints: &Constraints,
+    mut words: Vec<LetterNext>,
+) -> SolveResult {
+    let start = Instant::now();
 
-    // Lambda to add a letter to the row contains list
-    let add_rowcontains = |rowcontains: &mut HashMap<u8, u8>, c| {
-        rowcontains
-            .entry(Dictionary::uchar_to_u8(c))
-            .and_modify(|n| *n += 1)
-            .or_insert(1);
+    words.clear();
+
+    let mut result = SolveResult {
+        words,
+        candidates: Candidates::new(args.dictionary),
+        stats: SolveStats::default(),
+        cancelled: false,
     };
 
-    // Iterate each row
-    for row in args.board {
-        let mut rowcontains = HashMap::new();
-
-        // Iterate each letter in the row
-        for (elem, col) in row.iter().enumerate() {
-            match col {
-                BoardElem::Gray(c) => unused[Dictionary::uchar_to_usize(*c)] = true,
-                BoardElem::Yellow(c) => {
-                    incorrect[elem][Dictionary::uchar_to_usize(*c)] = true;
-                    add_rowcontains(&mut rowcontains, *c);
-                }
-                BoardElem::Green(c) => {
-                    correct[elem] = Some(Dictionary::uchar_to_u8(*c));
-                    add_rowcontains(&mut rowcontains, *c);
-                }
-                _ => (),
-            }
-        }
+    // Start search recursion
+    let rec = SolverRec { args, constraints };
 
-        // Build contains from rowcontains
-        for (letter, count) in rowcontains.into_iter() {
-            contains
-                .entry(letter)
-                .and_modify(|e| {
-                    *e = match *e {
-                        Contains::AtLeast(n) => Contains::AtLeast(cmp::max(n, count)),
-                        Contains::Exactly(_) => panic!("Attempt to update Contains::Exactly"),
-                    }
-                })
-                .or_insert(Contains::AtLeast(count));
-        }
+    find_words_rec(&rec, 0, 0, &mut result);
+
+    // Guarantee alphabetical order. The tree walk already produces this so the sort is cheap
+    result.words.sort_unstable();
+
+    for elem in &result.words {
+        result.candidates.insert(*elem);
     }
 
-    // Letter can be in contains and unused if guessed multiple times and the word contains fewer
-    unused
-        .iter_mut()
-        .enumerate()
-        .filter(|(_, unused)| **unused)
-        .for_each(|(i, unused)| {
-            if let Some(contains) = contains.get_mut(&(i as u8)) {
-                // Set unused to false
-                *unused = false;
-
-                // Convert Contains AtLeast to Exactly
-                *contains = match *contains {
-                    Contains::AtLeast(n) => Contains::Exactly(n),
-                    Contains::Exactly(_) => panic!("Already Contains::Exactly"),
-                }
-            }
-        });
+    result.stats.elapsed = start.elapsed();
 
-    // Start search recursion
-    let rec = SolverRec {
-        args,
-        correct,
-        incorrect,
-        contains,
-        unused,
+    result
+}
+
+/// Filters a set of previously found words using the provided constraints
+/// The constraints must imply the constraints the words were found with (see
+/// Constraints::implies), otherwise words will be missed. The words are returned in alphabetical
+/// order
+pub fn filter_words(
+    args: SolverArgs,
+    constraints: &Constraints,
+    candidates: &Candidates,
+) -> SolveResult {
+    filter_words_into(args, constraints, candidates, Vec::new())
+}
+
+/// Filters a set of previously found words like filter_words, collecting them in a word list
+/// which is cleared first and sized to hold every candidate, so it is allocated at most once
+pub fn filter_words_into(
+    args: SolverArgs,
+    constraints: &Constraints,
+    candidates: &Candidates,
+    mut words: Vec<LetterNext>,
+) -> SolveResult {
+    let start = Instant::now();
+
+    words.clear();
+    words.reserve(candidates.count());
+
+    let mut result = SolveResult {
+        words,
+        candidates: Candidates::new(args.dictionary),
+        stats: SolveStats::default(),
+        cancelled: false,
     };
 
-    find_words_rec(&rec, 0, 0, &mut result);
+    for elem in candidates.iter() {
+        if args.cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        result.stats.nodes += 1;
+
+        if constraints.letters_valid(args.dictionary, elem)
+            && result
+                .stats
+                .time_contains(|| constraints.contains_valid(args.dictionary, elem as usize))
+        {
+            result.words.push(elem);
+            result.candidates.insert(elem);
+        }
+    }
+
+    result.stats.elapsed = start.elapsed();
 
     result
 }
 
-fn find_words_rec(
-    rec: &SolverRec,
-    letter_elem: usize,
-    dict_elem: usize,
-    result: &mut Vec<LetterNext>,
-) {
-    // Got a letter in this position?
-    if let Some(letter) = rec.correct[letter_elem] {
-        find_words_rec_letter(rec, letter_elem, dict_elem, letter, result);
+/// Groups words by their first letter, returning the letters in alphabetical order with the
+/// positions of their words in the list. Words keep their list order within each group
+pub fn letter_groups(dictionary: &Dictionary, words: &[LetterNext]) -> Vec<(char, Vec<usize>)> {
+    let mut groups: [Vec<usize>; 26] = Default::default();
+
+    for (i, elem) in words.iter().enumerate() {
+        if let Some(c) = dictionary.get_word(*elem as usize).chars().next() {
+            groups[Dictionary::uchar_to_usize(c)].push(i);
+        }
+    }
+
+    ('A'..='Z')
+        .zip(groups)
+        .filter(|(_, group)| !group.is_empty())
+        .collect()
+}
+
+fn find_words_rec(rec: &SolverRec, letter_elem: usize, dict_elem: usize, result: &mut SolveResult) {
+    // Stop searching if cancelled
+    if rec.args.cancelled() {
+        result.cancelled = true;
+        return;
+    }
+
+    // Got a letter in this position? A correct letter which is also incorrect here is a
+    // contradiction, which no word matches
+    if let Some(letter) = rec.constraints.correct[letter_elem] {
+        if !rec.constraints.incorrect[letter_elem][letter as usize] {
+            find_words_rec_letter(rec, letter_elem, dict_elem, letter, result);
+        }
     } else {
         for letter in 0u8..26u8 {
-            if !rec.unused[letter as usize] && !rec.incorrect[letter_elem][letter as usize] {
+            if !rec.constraints.unused[letter as usize]
+                && !rec.constraints.incorrect[letter_elem][letter as usize]
+            {
                 find_words_rec_letter(rec, letter_elem, dict_elem, letter, result);
             }
         }
@@ -160,7 +350,7 @@ fn find_words_rec_letter(
     letter_elem: usize,
     dict_elem: usize,
     letter: u8,
-    result: &mut Vec<LetterNext>,
+    result: &mut SolveResult,
 ) {
     // Walk the dictionary
     let dict_elem = rec
@@ -174,29 +364,18 @@ fn find_words_rec_letter(
 
     // Recurse to next letter
     if dict_elem != NEXT_NONE {
-        if letter_elem == BOARD_COLS - 1 {
+        result.stats.nodes += 1;
+
+        if letter_elem == rec.constraints.correct.len() - 1 {
             // Check we have all unplaced letters in the word
-            let mut valid = true;
-
-            for (c, contains) in &rec.contains {
-                let (count, exact) = match contains {
-                    Contains::AtLeast(n) => (n, false),
-                    Contains::Exactly(n) => (n, true),
-                };
-
-                if !rec
-                    .args
-                    .dictionary
-                    .word_contains(dict_elem as usize, *c, *count, exact)
-                {
-                    valid = false;
-                    break;
-                }
-            }
+            let valid = result.stats.time_contains(|| {
+                rec.constraints
+                    .contains_valid(rec.args.dictionary, dict_elem as usize)
+            });
 
             if valid {
                 // Add to results
-                result.push(dict_elem);
+                result.words.push(dict_elem);
             }
         } else {
             find_words_rec(rec, letter_elem + 1, dict_elem as usize, result);
@@ -211,3 +390,1234 @@ fn debug_lookup(dictionary: &Dictionary, dict_elem: LetterNext) {
 
     println!("{:indent$}{} ({:?})", "", string, dict_elem);
 }
+
+#[cfg(test)]
+mod tests {
+    use dictionary::WordCase;
+
+    use super::*;
+    use crate::constraints::Contains;
+
+    fn feedback_str(guess: &str, answer: &str) -> String {
+        feedback(guess, answer)
+            .iter()
+            .map(|elem| match elem {
+                BoardElem::Empty | BoardElem::Unknown(_) => ' ',
+                BoardElem::Gray(_) => '.',
+                BoardElem::Yellow(_) => 'y',
+                BoardElem::Green(_) => 'G',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn feedback_simple() {
+        assert_eq!(feedback_str("CRANE", "CRANE"), "GGGGG");
+        assert_eq!(feedback_str("CRANE", "TOILS"), ".....");
+        assert_eq!(feedback_str("CRANE", "NACRE"), "yyyyG");
+    }
+
+    #[test]
+    fn feedback_repeated() {
+        // Only one E in the answer, green takes priority
+        assert_eq!(feedback_str("EERIE", "THREE"), "y.G.G");
+        assert_eq!(feedback_str("SPEED", "ABIDE"), "..y.y");
+        assert_eq!(feedback_str("SPEED", "ERASE"), "y.yy.");
+        // Two L's guessed, one in the answer
+        assert_eq!(feedback_str("LLAMA", "HELLO"), "yy...");
+        assert_eq!(feedback_str("HALLO", "LEVER"), "..y..");
+    }
+
+    #[test]
+    fn pattern_matches_feedback() {
+        let words = [
+            "CRANE", "EERIE", "THREE", "SPEED", "ABIDE", "LLAMA", "HELLO",
+        ];
+
+        let to_letters = |word: &str| {
+            word.chars()
+                .map(Dictionary::uchar_to_u8)
+                .collect::<Vec<_>>()
+        };
+
+        for guess in words {
+            for answer in words {
+                let expected = feedback(guess, answer).iter().rev().fold(0, |acc, elem| {
+                    (acc * 3)
+                        + match elem {
+                            BoardElem::Green(_) => 2,
+                            BoardElem::Yellow(_) => 1,
+                            _ => 0,
+                        }
+                });
+
+                assert_eq!(pattern(&to_letters(guess), &to_letters(answer)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn pattern_long_words() {
+        // The longest word needs a base 3 digit for every column
+        let len = dictionary::MAX_WORD_LEN;
+
+        let a = vec![0; len];
+        let b = vec![1; len];
+
+        assert_eq!(pattern(&a, &a), solved_pattern(&a));
+        assert_eq!(solved_pattern(&a), Pattern::pow(3, len as u32) - 1);
+        assert_eq!(pattern(&a, &b), 0);
+
+        // AAA...AB against BAA...AA has a yellow in the first and last columns
+        let mut guess = a.clone();
+        guess[len - 1] = 1;
+        let mut answer = a.clone();
+        answer[0] = 1;
+
+        let greens = (1..len - 1)
+            .map(|col| 2 * Pattern::pow(3, col as u32))
+            .sum::<Pattern>();
+
+        assert_eq!(
+            pattern(&guess, &answer),
+            1 + greens + Pattern::pow(3, len as u32 - 1)
+        );
+    }
+
+    #[test]
+    fn rank_long_words() {
+        // Words with more columns than a usize pattern number can hold
+        let a = "a".repeat(40);
+        let words = format!("{a}b\n{a}c");
+
+        let dictionary = Dictionary::new_from_string(&words, WordCase::Lower, false).unwrap();
+        assert_eq!(dictionary.word_len(), 41);
+
+        let elems: Vec<LetterNext> = dictionary
+            .word_elems()
+            .map(|elem| elem as LetterNext)
+            .collect();
+
+        // Either word splits the two apart
+        assert_eq!(rank_words(&dictionary, &elems), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn rank() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        // Empty board finds all of the words
+        let board = empty_board(5);
+
+        let words = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        })
+        .words;
+
+        // BRAKE splits the words 3/1/1, the others split them 2/1/1/1
+        let scores = rank_words(&dictionary, &words);
+
+        assert_eq!(scores.len(), 5);
+        assert!((scores[0] - 1.3710).abs() < 0.0001);
+
+        for score in &scores[1..] {
+            assert!((score - 1.9219).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn letter_information() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let words: Vec<LetterNext> = dictionary
+            .word_elems()
+            .map(|elem| elem as LetterNext)
+            .collect();
+
+        let info = super::letter_information(&dictionary, &words);
+
+        assert_eq!(info.len(), 26);
+
+        // A is in the same column of every word, so gives nothing away
+        assert_eq!(info[0].letter, 'A');
+        assert_eq!(info[0].present, 1.0);
+        assert_eq!(info[0].columns, [0.0, 0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(info[0].bits, 0.0);
+
+        // K splits the words 3/1/1 by column, as BRAKE does
+        let k = &info[10];
+        assert_eq!(k.letter, 'K');
+        assert!((k.present - 0.8).abs() < 0.0001);
+        assert!((k.columns[3] - 0.2).abs() < 0.0001);
+        assert!((k.columns[4] - 0.6).abs() < 0.0001);
+        assert!((k.bits - 1.3710).abs() < 0.0001);
+
+        // C and E are each in two words
+        assert!((info[2].bits - 0.9710).abs() < 0.0001);
+        assert!((info[4].bits - 0.9710).abs() < 0.0001);
+
+        // Z is in none
+        assert_eq!(info[25].present, 0.0);
+        assert_eq!(info[25].bits, 0.0);
+    }
+
+    #[test]
+    fn letter_information_long_words() {
+        // Columns past 32 need a wider mask
+        let a = "a".repeat(39);
+        let words = format!("{a}b\n{a}c");
+
+        let dictionary = Dictionary::new_from_string(&words, WordCase::Lower, false).unwrap();
+
+        let words: Vec<LetterNext> = dictionary
+            .word_elems()
+            .map(|elem| elem as LetterNext)
+            .collect();
+
+        let info = super::letter_information(&dictionary, &words);
+
+        // B is in the last column of one of the words
+        let b = &info[1];
+        assert_eq!(b.columns.len(), 40);
+        assert_eq!(b.columns[39], 0.5);
+        assert_eq!(b.bits, 1.0);
+    }
+
+    #[test]
+    fn cancel() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let board = empty_board(5);
+        let cancel = CancelToken::new();
+
+        let args = || SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: Some(&cancel),
+        };
+
+        // Search runs to completion when not cancelled
+        let result = find_words(args());
+
+        assert!(!result.cancelled);
+        assert_eq!(result.words.len(), 5);
+
+        // Cancelled searches stop straight away
+        cancel.clone().cancel();
+
+        let cancelled = find_words(args());
+
+        assert!(cancelled.cancelled);
+        assert!(cancelled.words.is_empty());
+
+        let constraints = Standard.constraints(&board, 5);
+        let filtered = filter_words(args(), &constraints, &result.candidates);
+
+        assert!(filtered.cancelled);
+        assert!(filtered.words.is_empty());
+
+        assert_eq!(
+            rank_words_cancellable(&dictionary, &result.words, &cancel),
+            None
+        );
+
+        // Reset token can be used again
+        cancel.reset();
+
+        assert!(!find_words(args()).cancelled);
+        assert_eq!(
+            rank_words_cancellable(&dictionary, &result.words, &cancel).map(|scores| scores.len()),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn feedback_find() {
+        let dictionary = Dictionary::new_from_string(
+            "crane\ncrank\ndrank\nprank\nslate",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut board = empty_board(5);
+        board[0] = feedback("CRANE", "PRANK");
+
+        let words = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        })
+        .words;
+
+        let words: Vec<String> = words
+            .into_iter()
+            .map(|elem| dictionary.get_word(elem as usize))
+            .collect();
+
+        assert_eq!(words, vec!["DRANK", "PRANK"]);
+    }
+
+    #[test]
+    fn find_order() {
+        // Words out of order in the dictionary are found in alphabetical order
+        let dictionary = Dictionary::new_from_string(
+            "prank\ncrank\nslate\ndrank\nbrake\ncrane",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let board = empty_board(5);
+
+        let words: Vec<String> = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        })
+        .words
+        .into_iter()
+        .map(|elem| dictionary.get_word(elem as usize))
+        .collect();
+
+        assert_eq!(
+            words,
+            ["BRAKE", "CRANE", "CRANK", "DRANK", "PRANK", "SLATE"]
+        );
+    }
+
+    #[test]
+    fn find_six() {
+        // Six letter words are solved on a six column board
+        let dictionary = Dictionary::new_from_string(
+            "planet\nplanes\nplaice\nrocket\nslate",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut board = empty_board(dictionary.word_len());
+        board[0] = feedback("PLAICE", "PLANET");
+
+        let words: Vec<String> = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        })
+        .words
+        .into_iter()
+        .map(|elem| dictionary.get_word(elem as usize))
+        .collect();
+
+        assert_eq!(words, ["PLANES", "PLANET"]);
+    }
+
+    #[test]
+    fn repeated_letters() {
+        // The letter count constraints are checked with the dictionary's letter_count
+        let dictionary =
+            Dictionary::new_from_string("abide\namble\ndebar\ndelve", WordCase::Lower, false)
+                .unwrap();
+
+        // SPEED against ABIDE gives one yellow and one gray E, so the answer has exactly one E,
+        // and a yellow D so it has at least one D
+        let mut board = empty_board(5);
+        board[0] = feedback("SPEED", "ABIDE");
+
+        let constraints = Constraints::new(&board, 5);
+        let e = Dictionary::uchar_to_u8('E');
+        let d = Dictionary::uchar_to_u8('D');
+
+        assert_eq!(constraints.contains.get(&e), Some(&Contains::exactly(1)));
+        assert_eq!(constraints.contains.get(&d), Some(&Contains::at_least(1)));
+
+        let words: Vec<String> = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        })
+        .words
+        .into_iter()
+        .map(|elem| dictionary.get_word(elem as usize))
+        .collect();
+
+        // DELVE has two Es and AMBLE has no D
+        assert_eq!(words, ["ABIDE", "DEBAR"]);
+    }
+
+    #[test]
+    fn cross_row_counts() {
+        // Board, letter, expected count bounds (None if the letter is unused) and contradiction
+        let cases: [(&str, char, Option<Contains>, Option<Contradiction>); 8] = [
+            // One yellow and one gray E in a row is exactly one
+            ("SPEED:bbybb", 'E', Some(Contains::exactly(1)), None),
+            // Green and yellow E in a row is at least two
+            ("SPEED:bbgyb", 'E', Some(Contains::at_least(2)), None),
+            // Gray E only is none
+            ("CRATE:bbbbb", 'E', None, None),
+            // Two yellow Es, then one gray with two placed, is exactly two
+            (
+                "EAVES:ybbyb/GEESE:bgbbg",
+                'E',
+                Some(Contains::exactly(2)),
+                None,
+            ),
+            // At least one E, then at least two
+            (
+                "CRATE:bbbby/EAVES:ybbyb",
+                'E',
+                Some(Contains::at_least(2)),
+                None,
+            ),
+            // Exactly one E, then at least one
+            (
+                "SPEED:bbybb/CRATE:bbbby",
+                'E',
+                Some(Contains::exactly(1)),
+                None,
+            ),
+            // Two yellow Es, then exactly one
+            (
+                "EAVES:ybbyb/THEME:bbgbb",
+                'E',
+                Some(Contains {
+                    min: 2,
+                    max: Some(1),
+                }),
+                Some(Contradiction::Count {
+                    letter: 'E',
+                    min: 2,
+                    max: 1,
+                }),
+            ),
+            // No E, then one yellow E
+            (
+                "CRATE:bbbbb/SPEED:bbybb",
+                'E',
+                Some(Contains {
+                    min: 1,
+                    max: Some(0),
+                }),
+                Some(Contradiction::Count {
+                    letter: 'E',
+                    min: 1,
+                    max: 0,
+                }),
+            ),
+        ];
+
+        for (board, letter, contains, contradiction) in cases {
+            let board: BoardState = board.parse().unwrap();
+            let constraints = Constraints::new(&board.0, 5);
+            let l = Dictionary::uchar_to_u8(letter);
+
+            assert_eq!(constraints.contains.get(&l), contains.as_ref(), "{board}");
+            assert_eq!(
+                constraints.unused[l as usize],
+                contains.is_none(),
+                "{board}"
+            );
+            assert_eq!(constraints.validate().err(), contradiction, "{board}");
+        }
+
+        // Words are checked against both bounds
+        let dictionary = Dictionary::new_from_string(
+            "fence\nhence\nlevee\nmetal\nspree",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let board: BoardState = "EAVES:ybbyb/GEESE:bgbbg".parse().unwrap();
+
+        let words: Vec<String> = find_words(SolverArgs {
+            board: &board.0,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        })
+        .words
+        .into_iter()
+        .map(|elem| dictionary.get_word(elem as usize))
+        .collect();
+
+        // LEVEE has three Es and METAL one
+        assert_eq!(words, ["FENCE", "HENCE"]);
+    }
+
+    #[test]
+    fn restrict_count() {
+        let dictionary = Dictionary::new_from_string(
+            "fence\nhence\nlevee\nmetal\nspree",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let board: BoardState = "THYME:bbbbg".parse().unwrap();
+        let mut constraints = Constraints::new(&board.0, 5);
+
+        assert_eq!(constraints.count('E'), (1, None));
+        assert_eq!(constraints.count('T'), (0, Some(0)));
+        assert_eq!(constraints.count('N'), (0, None));
+
+        // Counts can only be narrowed
+        constraints.restrict_count('E', 0, Some(2));
+        assert_eq!(constraints.count('E'), (1, Some(2)));
+
+        constraints.restrict_count('T', 1, None);
+        assert_eq!(constraints.count('T'), (1, Some(0)));
+        assert_eq!(
+            constraints.validate(),
+            Err(Contradiction::Count {
+                letter: 'T',
+                min: 1,
+                max: 0
+            })
+        );
+
+        // A maximum of zero marks the letter unused
+        let mut constraints = Constraints::new(&board.0, 5);
+
+        constraints.restrict_count('N', 0, Some(0));
+        assert!(constraints.unused[Dictionary::uchar_to_usize('N')]);
+
+        let words: Vec<String> = find_words_with(
+            SolverArgs {
+                board: &board.0,
+                dictionary: &dictionary,
+                rules: &Standard,
+                debug: false,
+                cancel: None,
+            },
+            &constraints,
+        )
+        .words
+        .into_iter()
+        .map(|elem| dictionary.get_word(elem as usize))
+        .collect();
+
+        // FENCE has an N
+        assert_eq!(words, ["LEVEE", "SPREE"]);
+    }
+
+    #[test]
+    fn candidates() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let find = |guess: &str, answer: &str| {
+            let mut board = empty_board(5);
+            board[0] = feedback(guess, answer);
+
+            find_words(SolverArgs {
+                board: &board,
+                dictionary: &dictionary,
+                rules: &Standard,
+                debug: false,
+                cancel: None,
+            })
+        };
+
+        let all = Candidates::all(&dictionary);
+        assert_eq!(all.count(), 5);
+
+        // CRANK, DRANK, PRANK
+        let rank = find("TRUNK", "CRANK");
+        assert_eq!(rank.candidates.count(), 3);
+        assert_eq!(rank.candidates.to_words(), rank.words);
+
+        // CRANE, CRANK
+        let cran = find("CRANS", "CRANK");
+        assert_eq!(cran.candidates.count(), 2);
+
+        let words = |candidates: Candidates| {
+            candidates
+                .iter()
+                .map(|elem| dictionary.get_word(elem as usize))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            words(rank.candidates.intersection(&cran.candidates)),
+            ["CRANK"]
+        );
+        assert_eq!(
+            words(rank.candidates.union(&cran.candidates)),
+            ["CRANE", "CRANK", "DRANK", "PRANK"]
+        );
+        assert_eq!(
+            words(rank.candidates.difference(&cran.candidates)),
+            ["DRANK", "PRANK"]
+        );
+        assert_eq!(
+            words(all.difference(&rank.candidates.union(&cran.candidates))),
+            ["BRAKE"]
+        );
+        assert!(rank.candidates.difference(&all).is_empty());
+    }
+
+    #[test]
+    fn filter() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank\nslate",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut board1 = empty_board(5);
+        board1[0] = feedback("STAIR", "CRANK");
+
+        let first = find_words(SolverArgs {
+            board: &board1,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        });
+        let first_constraints = Constraints::new(&board1, 5);
+        assert_eq!(first.words.len(), 5);
+
+        // Add a second row, tightening the constraints
+        let mut board2 = board1.clone();
+        board2[1] = feedback("TRUNK", "CRANK");
+
+        let constraints = Constraints::new(&board2, 5);
+        assert!(constraints.implies(&first_constraints));
+        assert!(!first_constraints.implies(&constraints));
+
+        let args = SolverArgs {
+            board: &board2,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        };
+
+        // Filtering gives the same result as a full search
+        let filtered = filter_words(args, &constraints, &first.candidates);
+
+        let full = find_words(SolverArgs {
+            board: &board2,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        });
+
+        assert_eq!(filtered.words, full.words);
+        assert_eq!(filtered.candidates, full.candidates);
+        assert_eq!(filtered.words.len(), 3);
+
+        // Both check the letter counts of the words with allowed letters in every position
+        assert_eq!(filtered.stats.contains_checks, full.stats.contains_checks);
+        assert!(full.stats.contains_checks >= full.words.len());
+        assert_eq!(
+            full.stats.walk_time() + full.stats.contains_time,
+            full.stats.elapsed
+        );
+    }
+
+    #[test]
+    fn reuse_words() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank\nslate",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let mut board = empty_board(5);
+        board[0] = feedback("STAIR", "CRANK");
+
+        let args = || SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            rules: &Standard,
+            debug: false,
+            cancel: None,
+        };
+
+        let constraints = Standard.constraints(&board, 5);
+        let full = find_words(args());
+
+        // Stale words are cleared and the list's allocation is kept
+        let buffer: Vec<LetterNext> = vec![0; 100];
+        let ptr = buffer.as_ptr();
+
+        let found = find_words_into(args(), &constraints, buffer);
+
+        assert_eq!(found.words, full.words);
+        assert_eq!(found.candidates, full.candidates);
+        assert_eq!(found.words.as_ptr(), ptr);
+
+        // Filtering into the same list reuses it again
+        let ptr = found.words.as_ptr();
+        let filtered = filter_words_into(args(), &constraints, &full.candidates, found.words);
+
+        assert_eq!(filtered.words, full.words);
+        assert_eq!(filtered.words.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn implies() {
+        let constraints = |rows: &[(&str, &str)]| {
+            let mut board = empty_board(5);
+
+            for (row, (guess, answer)) in board.iter_mut().zip(rows) {
+                *row = feedback(guess, answer);
+            }
+
+            Constraints::new(&board, 5)
+        };
+
+        let empty = constraints(&[]);
+        let one = constraints(&[("SPEED", "ERASE")]);
+        let two = constraints(&[("SPEED", "ERASE"), ("EERIE", "ERASE")]);
+
+        // Identical constraints imply each other
+        assert!(empty.implies(&empty));
+        assert!(one.implies(&one));
+
+        // Extra rows tighten
+        assert!(one.implies(&empty));
+        assert!(two.implies(&one));
+        assert!(!empty.implies(&one));
+        assert!(!one.implies(&two));
+
+        // A gray letter becoming yellow is not a tightening
+        let gray = constraints(&[("ABBEY", "CRANE")]);
+        let yellow = constraints(&[("ABBEY", "CRANE"), ("BRAKE", "BRAKE")]);
+        assert!(!yellow.implies(&gray));
+    }
+
+    #[test]
+    fn allowed_letters() {
+        let letters = |allowed: [bool; 26]| {
+            allowed
+                .iter()
+                .enumerate()
+                .filter(|(_, allowed)| **allowed)
+                .map(|(l, _)| (l as u8 + b'A') as char)
+                .collect::<String>()
+        };
+
+        let mut board = empty_board(5);
+        board[0] = feedback("SPEED", "ERASE");
+
+        let constraints = Constraints::new(&board, 5);
+
+        // Yellow letters are excluded from their columns, gray letters from every column
+        assert_eq!(
+            letters(constraints.allowed_letters(0)),
+            "ABCEFGHIJKLMNOQRTUVWXYZ"
+        );
+        assert_eq!(
+            letters(constraints.allowed_letters(2)),
+            "ABCFGHIJKLMNOQRSTUVWXYZ"
+        );
+
+        // Green columns only allow their letter
+        board[1] = feedback("CRANE", "ERASE");
+
+        let constraints = Constraints::new(&board, 5);
+
+        assert_eq!(letters(constraints.allowed_letters(1)), "R");
+        assert_eq!(letters(constraints.allowed_letters(4)), "E");
+
+        // Exactly one L, placed in the first column, so no more L's
+        let mut board = empty_board(5);
+        board[0] = feedback("LLAMA", "LEVER");
+
+        let constraints = Constraints::new(&board, 5);
+
+        assert_eq!(letters(constraints.allowed_letters(0)), "L");
+        assert!(!constraints.allowed_letters(1)[11]);
+    }
+
+    #[test]
+    fn yellow_green_contradiction() {
+        let dictionary =
+            Dictionary::new_from_string("slate\nspace\nstale\nshale", WordCase::Lower, false)
+                .unwrap();
+
+        let solve = |board: &[BoardRow]| {
+            find_words(SolverArgs {
+                board,
+                dictionary: &dictionary,
+                rules: &Standard,
+                debug: false,
+                cancel: None,
+            })
+            .words
+        };
+
+        // Consistent boards are valid
+        let mut board = empty_board(5);
+        board[0] = feedback("CRANE", "SLATE");
+        board[1] = feedback("SPACE", "SLATE");
+
+        let constraints = Constraints::new(&board, 5);
+
+        assert_eq!(constraints.validate(), Ok(()));
+        assert_eq!(solve(&board).len(), 3);
+
+        // E yellow in the last column where it is green on the next row
+        board[0][4] = BoardElem::Yellow('E');
+
+        let constraints = Constraints::new(&board, 5);
+        let contradiction = Contradiction::YellowGreen {
+            letter: 'E',
+            col: 4,
+        };
+
+        assert_eq!(constraints.validate(), Err(contradiction));
+        assert_eq!(
+            contradiction.to_string(),
+            "E is yellow in column 5 where it is green"
+        );
+
+        // No word can match, whichever order the rows are in
+        assert!(solve(&board).is_empty());
+        assert!(!constraints
+            .allowed_letters(4)
+            .iter()
+            .any(|allowed| *allowed));
+
+        for elem in dictionary.word_elems() {
+            assert!(!constraints.matches(&dictionary, elem as LetterNext));
+        }
+
+        board.swap(0, 1);
+
+        assert_eq!(Constraints::new(&board, 5).validate(), Err(contradiction));
+        assert!(solve(&board).is_empty());
+    }
+
+    #[test]
+    fn green_contradictions() {
+        let validate = |board: &str| {
+            let board: BoardState = board.parse().unwrap();
+            Constraints::new(&board.0, 5).validate()
+        };
+
+        // Two different letters green in the first column
+        let contradiction = Contradiction::TwoGreens {
+            letters: ['C', 'S'],
+            col: 0,
+        };
+
+        assert_eq!(validate("CRANE:gbbbb/SLATE:gbbbb"), Err(contradiction));
+        assert_eq!(
+            contradiction.to_string(),
+            "C and S are both green in column 1"
+        );
+
+        // The same letter green on both rows is fine
+        assert_eq!(validate("CRANE:gbbbb/CLOUT:gbbbb"), Ok(()));
+
+        // E gray in the last column where it is green, with the yellow E allowing one
+        let contradiction = Contradiction::GrayGreen {
+            letter: 'E',
+            col: 4,
+        };
+
+        assert_eq!(validate("CRANE:bbbbg/EERIE:ybbbb"), Err(contradiction));
+        assert_eq!(
+            contradiction.to_string(),
+            "E is gray in column 5 where it is green"
+        );
+    }
+
+    #[test]
+    fn simulate() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let answers: Vec<WordLetters> = dictionary
+            .word_elems()
+            .map(|elem| word_letters(&dictionary, elem as LetterNext))
+            .collect();
+        let all: Vec<usize> = (0..answers.len()).collect();
+
+        // CRANE solves itself, splits BRAKE and CRANK out, then DRANK is guessed before PRANK
+        let dist = play_guess(&answers, &all, &answers[1], 1);
+
+        assert_eq!(dist.solved, [1, 3, 1, 0, 0, 0]);
+        assert_eq!(dist.failed, 0);
+        assert_eq!(dist.games(), 5);
+        assert!((dist.mean() - 2.0).abs() < 0.0001);
+
+        // Remaining answers are grouped in pattern order
+        let (dist, partitions) = play_partitions(&answers, &all, &answers[1], 1);
+
+        assert_eq!(dist.solved[0], 1);
+        assert_eq!(partitions, [vec![3, 4], vec![2], vec![0]]);
+
+        // Sizes of the groups, including the guess itself
+        let sizes = partition_sizes(&answers, &all, &answers[1]);
+
+        assert_eq!(sizes.values().sum::<usize>(), 5);
+        assert_eq!(sizes.get(&solved_pattern(&answers[1])), Some(&1));
+        assert_eq!(sizes.values().max(), Some(&2));
+
+        let dist = play_set(&answers, &all, BOARD_ROWS + 1);
+
+        assert_eq!(dist.failed, 5);
+        assert!((dist.fail_rate() - 1.0).abs() < 0.0001);
+
+        // Playing each answer on its own gives the same games
+        let mut dist = Distribution::default();
+
+        for answer in all.iter() {
+            dist.merge(&play_answer(&answers, &all, &answers[1], *answer));
+        }
+
+        assert_eq!(dist.solved, [1, 3, 1, 0, 0, 0]);
+        assert_eq!(
+            play_answer(&answers, &all, &answers[1], 4).solved,
+            [0, 0, 1, 0, 0, 0]
+        );
+
+        // Other strategies choose the next guess from the answers left
+        let last = |set: &[usize]| set[set.len() - 1];
+
+        assert_eq!(
+            play_answer_by(&answers, &all, &answers[1], 4, last).solved,
+            [0, 1, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn difficulty() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank\nthree",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let answers: Vec<WordLetters> = dictionary
+            .word_elems()
+            .map(|elem| word_letters(&dictionary, elem as LetterNext))
+            .collect();
+
+        let crane = &answers[1..2];
+
+        // The opener solves itself in one guess
+        let rated = super::difficulty(&answers, 1, crane);
+
+        assert!((rated.guesses - 1.0).abs() < 0.0001);
+        assert_eq!(rated.rating(), "easy");
+
+        // P is only in PRANK, R is in all six answers, A in five and N and K in four
+        let rated = super::difficulty(&answers, 4, &[crane, crane].concat());
+
+        assert!((rated.rarity - (5.0 + 0.0 + 1.0 + 2.0 + 2.0) / 6.0 / 5.0).abs() < 0.0001);
+        assert_eq!(rated.repeats, 0);
+        assert!(rated.guesses >= 2.0);
+
+        // THREE repeats E
+        let rated = super::difficulty(&answers, 5, &[]);
+
+        assert_eq!(rated.repeats, 1);
+        assert_eq!(rated.guesses, 0.0);
+        assert!(rated.to_string().ends_with("1 repeated letter"));
+    }
+
+    #[test]
+    fn strategy() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let words: Vec<LetterNext> = dictionary
+            .word_elems()
+            .map(|elem| elem as LetterNext)
+            .collect();
+        let cancel = CancelToken::new();
+
+        // Entropy scores match rank_words
+        let (kind, scores) =
+            rank_words_strategy(&dictionary, &words, Strategy::Entropy, &cancel).unwrap();
+
+        assert_eq!(kind, ScoreKind::Information);
+        assert_eq!(scores, rank_words(&dictionary, &words));
+
+        // Scoring the letters gives the same scores
+        let letters: Vec<WordLetters> = words
+            .iter()
+            .map(|elem| word_letters(&dictionary, *elem))
+            .collect();
+
+        assert_eq!(
+            rank_letters_strategy(&letters, Strategy::Entropy, &cancel),
+            Some((kind, scores))
+        );
+
+        // Expected guesses are the mean of the games played out, CRANE taking two on average
+        let (kind, scores) =
+            rank_words_strategy(&dictionary, &words, Strategy::Expected, &cancel).unwrap();
+
+        assert_eq!(kind, ScoreKind::Guesses);
+        assert!((scores[1] - 2.0).abs() < 0.0001);
+        assert!(scores.iter().all(|score| *score >= 1.0));
+
+        // Fewer guesses are better
+        assert_eq!(kind.compare(2.0, 3.0), std::cmp::Ordering::Greater);
+        assert_eq!(
+            ScoreKind::Information.compare(2.0, 3.0),
+            std::cmp::Ordering::Less
+        );
+
+        // Hybrid switches to expected guesses when few words remain
+        assert_eq!(
+            Strategy::Hybrid.score_kind(HYBRID_THRESHOLD),
+            ScoreKind::Guesses
+        );
+        assert_eq!(
+            Strategy::Hybrid.score_kind(HYBRID_THRESHOLD + 1),
+            ScoreKind::Information
+        );
+
+        assert_eq!("hybrid".parse(), Ok(Strategy::Hybrid));
+        assert_eq!(Strategy::Expected.to_string(), "expected");
+        assert!("fastest".parse::<Strategy>().is_err());
+
+        // Failed games count as one more guess than the board rows
+        let dist = Distribution {
+            solved: [1, 0, 0, 0, 0, 0],
+            failed: 1,
+        };
+
+        assert!((dist.expected() - 4.0).abs() < 0.0001);
+
+        // Large sets are estimated
+        let answers: Vec<WordLetters> = (0..EXPECTED_SEARCH_LIMIT as u8 + 10)
+            .map(|i| vec![i % 26, i / 26, 23, 24, 25])
+            .collect();
+
+        let estimate = expected_guesses(&answers[0], &answers);
+
+        assert!(estimate > 1.0 && estimate < BOARD_ROWS as f32);
+    }
+
+    #[test]
+    fn random() {
+        // The same seed gives the same numbers
+        let mut a = Random::new(Some(42));
+        let mut b = Random::new(Some(42));
+
+        assert_eq!(a.seed(), 42);
+        assert_eq!(a.sample(100, 10), b.sample(100, 10));
+        assert_eq!(a.index(1000), b.index(1000));
+
+        // Samples are distinct and limited to the length
+        let mut sample = a.sample(10, 20);
+        sample.sort();
+
+        assert_eq!(sample, (0..10).collect::<Vec<_>>());
+        assert_eq!(a.sample(10, 3).len(), 3);
+    }
+
+    #[test]
+    fn adversary() {
+        let dictionary = Dictionary::new_from_string(
+            "brake\ncrane\ncrank\ndrank\nprank",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        let answers: Vec<WordLetters> = dictionary
+            .word_elems()
+            .map(|elem| word_letters(&dictionary, elem as LetterNext))
+            .collect();
+        let all: Vec<usize> = (0..answers.len()).collect();
+
+        // CRANE leaves DRANK and PRANK as the largest group
+        let (pattern, remaining) = super::adversary(&answers, &all, &answers[1]);
+
+        assert_eq!(remaining, [3, 4]);
+        assert_eq!(pattern, 78);
+
+        // DRANK then leaves PRANK rather than admitting it was right
+        let (pattern, remaining) = super::adversary(&answers, &remaining, &answers[3]);
+
+        assert_eq!(remaining, [4]);
+        assert_ne!(pattern, solved_pattern(&answers[3]));
+
+        // Only the answer is left
+        let (pattern, remaining) = super::adversary(&answers, &remaining, &answers[4]);
+
+        assert_eq!(remaining, [4]);
+        assert_eq!(pattern, solved_pattern(&answers[4]));
+    }
+
+    #[test]
+    fn letter_counts() {
+        let dictionary =
+            Dictionary::new_from_string("eerie\nthree\nspeed", WordCase::Lower, false).unwrap();
+
+        let words: Vec<LetterNext> = dictionary
+            .word_elems()
+            .map(|elem| elem as LetterNext)
+            .collect();
+
+        let counts = super::letter_counts(&dictionary, &words);
+
+        // Repeated letters are only counted once for each word
+        assert_eq!(counts[Dictionary::uchar_to_usize('E')], 3);
+        assert_eq!(counts[Dictionary::uchar_to_usize('R')], 2);
+        assert_eq!(counts[Dictionary::uchar_to_usize('S')], 1);
+        assert_eq!(counts[Dictionary::uchar_to_usize('Z')], 0);
+        assert_eq!(counts.iter().sum::<usize>(), 3 + 4 + 4);
+    }
+
+    #[test]
+    fn rules() {
+        assert_eq!(game_rules("standard").unwrap().rows(), Some(BOARD_ROWS));
+        assert_eq!(game_rules("Quordle").unwrap().boards(), 4);
+        assert_eq!(game_rules("absurdle").unwrap().rows(), None);
+        assert!(game_rules("unknown").is_none());
+
+        for rules in GAME_RULES {
+            assert!(std::ptr::addr_eq(game_rules(rules.name()).unwrap(), rules));
+        }
+    }
+
+    #[test]
+    fn hard_mode() {
+        let board = vec![feedback("CRANE", "TRACE")];
+
+        // Green R, A and E must stay in place and the yellow C must be used
+        assert!(HardMode.valid_guess(&board, "TRACE"));
+        assert!(HardMode.valid_guess(&board, "BRACE"));
+        assert!(!HardMode.valid_guess(&board, "TRICE"));
+        assert!(!HardMode.valid_guess(&board, "GRADE"));
+
+        // Gray letters may be played again
+        assert!(HardMode.valid_guess(&board, "NRACE"));
+
+        // Any guess is allowed by the standard rules
+        assert!(Standard.valid_guess(&board, "GRADE"));
+        assert!(HardMode.valid_guess(&[], "GRADE"));
+    }
+
+    #[test]
+    fn board_state() {
+        use BoardElem::*;
+
+        let board = vec![
+            feedback("CRANE", "SCARE"),
+            vec![Unknown('S'), Unknown('T'), Empty, Empty, Empty],
+            vec![Empty; 5],
+        ];
+
+        let state = BoardState(board.clone());
+
+        // Empty rows are left out
+        assert_eq!(state.to_string(), "CRANE:yygbg/ST___:??___");
+
+        let parsed: BoardState = state.to_string().parse().unwrap();
+        assert_eq!(parsed.0, board[..2]);
+
+        // Gray can be '.' and pairs separated by spaces in any case
+        let parsed: BoardState = "crane:..Y.g  slate:y.y.g".parse().unwrap();
+        assert_eq!(parsed.to_string(), "CRANE:bbybg/SLATE:ybybg");
+
+        // Invalid boards
+        assert!("CRANE".parse::<BoardState>().is_err());
+        assert!("CRANE:bbyb".parse::<BoardState>().is_err());
+        assert!("CRANE:bbybx".parse::<BoardState>().is_err());
+        assert!("CR_NE:bbybg".parse::<BoardState>().is_err());
+        assert!("CRANE:bbybg/SLAT:bbyb".parse::<BoardState>().is_err());
+
+        assert_eq!("".parse::<BoardState>(), Ok(BoardState::default()));
+    }
+
+    #[test]
+    fn groups() {
+        let dictionary = Dictionary::new_from_string(
+            "slate\ncrane\nstare\nabbey\ncrate",
+            WordCase::Lower,
+            false,
+        )
+        .unwrap();
+
+        // Scores order the words, groups keep that order
+        let words: Vec<LetterNext> = ["STARE", "CRATE", "SLATE", "CRANE"]
+            .iter()
+            .map(|word| dictionary.find(word).unwrap())
+            .collect();
+
+        assert_eq!(
+            letter_groups(&dictionary, &words),
+            [('C', vec![1, 3]), ('S', vec![0, 2])]
+        );
+
+        assert!(letter_groups(&dictionary, &[]).is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn board_state_serde() {
+        let state: BoardState = "CRANE:bbybg/ST___:??___".parse().unwrap();
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, "\"CRANE:bbybg/ST___:??___\"");
+        assert_eq!(serde_json::from_str::<BoardState>(&json).unwrap(), state);
+
+        assert!(serde_json::from_str::<BoardState>("\"CRANE\"").is_err());
+
+        // Board elements serialize with their colour
+        let json = serde_json::to_string(&state.0[0][4]).unwrap();
+        assert_eq!(json, "{\"Green\":\"E\"}");
+        assert_eq!(
+            serde_json::from_str::<BoardElem>(&json).unwrap(),
+            state.0[0][4]
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! Seeded random number generation
+
+use fastrand::Rng;
+
+/// Random number generator which can be seeded so runs can be repeated
+pub struct Random {
+    rng: Rng,
+    seed: u64,
+}
+
+impl Random {
+    /// Creates a generator from a seed, or from a random seed if none is given
+    pub fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| fastrand::u64(..));
+
+        Self {
+            rng: Rng::with_seed(seed),
+            seed,
+        }
+    }
+
+    /// Returns the seed the generator was created with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns a random index below a length, which must not be zero
+    pub fn index(&mut self, len: usize) -> usize {
+        self.rng.usize(..len)
+    }
+
+    /// Returns a number of distinct random indexes below a length, in random order
+    /// All of the indexes are returned if the number is larger than the length
+    pub fn sample(&mut self, len: usize, count: usize) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..len).collect();
+        let count = count.min(len);
+
+        // Partial Fisher-Yates shuffle
+        for i in 0..count {
+            let j = self.rng.usize(i..len);
+            indexes.swap(i, j);
+        }
+
+        indexes.truncate(count);
+        indexes
+    }
+}
@@ -0,0 +1,227 @@
+//! Candidate word ranking
+
+use std::collections::HashMap;
+
+use dictionary::{Dictionary, LetterNext, MAX_WORD_LEN};
+
+use crate::CancelToken;
+
+/// Word lengths above this count patterns in a map rather than a table
+const MAX_TABLE_COLS: usize = 10;
+
+/// Word as upper case letter numbers
+pub type WordLetters = Vec<u8>;
+
+/// Colour pattern number, wide enough for a base 3 digit for every column of the longest word
+pub type Pattern = u128;
+
+/// Returns the number of possible colour patterns for a guess of the given length
+pub fn patterns(cols: usize) -> usize {
+    3usize.pow(cols as u32)
+}
+
+/// Calculates the colour pattern number for a guess against an answer
+/// Each column is encoded as a base 3 digit (0 = gray, 1 = yellow, 2 = green)
+pub fn pattern(guess: &[u8], answer: &[u8]) -> Pattern {
+    let mut green = 0u64;
+    let mut remaining = [0u8; 26];
+
+    // Mark green letters
+    for (col, (g, a)) in guess.iter().zip(answer).enumerate() {
+        if g == a {
+            green |= 1 << col;
+        } else {
+            remaining[*a as usize] += 1;
+        }
+    }
+
+    // Mark yellow letters and build the pattern number
+    let mut result = 0;
+    let mut mult = 1;
+
+    for (col, g) in guess.iter().enumerate() {
+        let digit = if green & (1 << col) != 0 {
+            2
+        } else {
+            let count = &mut remaining[*g as usize];
+
+            if *count > 0 {
+                *count -= 1;
+                1
+            } else {
+                0
+            }
+        };
+
+        result += digit * mult;
+        mult *= 3;
+    }
+
+    result
+}
+
+/// Returns the letter numbers for a dictionary element
+pub fn word_letters(dictionary: &Dictionary, elem: LetterNext) -> WordLetters {
+    let mut word = [0; MAX_WORD_LEN];
+    let len = dictionary.get_word_into(elem as usize, &mut word);
+
+    word[..len].iter().map(|c| c - b'A').collect()
+}
+
+/// Counts the words containing each letter, indexed by letter number
+pub fn letter_counts(dictionary: &Dictionary, words: &[LetterNext]) -> [usize; 26] {
+    let mut counts = [0; 26];
+
+    let mut word = [0; MAX_WORD_LEN];
+
+    for elem in words {
+        let mut seen = [false; 26];
+        let len = dictionary.get_word_into(*elem as usize, &mut word);
+
+        for c in &word[..len] {
+            seen[(c - b'A') as usize] = true;
+        }
+
+        for (count, seen) in counts.iter_mut().zip(seen) {
+            if seen {
+                *count += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+/// Information given by finding out where a letter is in the candidates
+#[derive(Clone, Debug, PartialEq)]
+pub struct LetterInformation {
+    /// Upper case letter
+    pub letter: char,
+    /// Fraction of the candidates containing the letter
+    pub present: f32,
+    /// Fraction of the candidates with the letter in each column
+    pub columns: Vec<f32>,
+    /// Expected information (in bits) gained by finding out which columns the letter is in, if
+    /// any, when all of the candidates are equally likely to be the answer
+    pub bits: f32,
+}
+
+/// Calculates the information each letter gives when probed, in letter order
+/// The candidates are split by the columns they have the letter in, so letters in about half of
+/// the candidates and spread over the columns give the most information
+pub fn letter_information(dictionary: &Dictionary, words: &[LetterNext]) -> Vec<LetterInformation> {
+    let total = words.len() as f32;
+
+    // Columns each word has each letter in, as bit masks
+    let mut masks: Vec<Vec<u64>> = (0..26).map(|_| Vec::with_capacity(words.len())).collect();
+    let mut cols = 0;
+
+    let mut word = [0; MAX_WORD_LEN];
+
+    for elem in words {
+        let mut word_masks = [0u64; 26];
+        let len = dictionary.get_word_into(*elem as usize, &mut word);
+
+        for (col, c) in word[..len].iter().enumerate() {
+            word_masks[(c - b'A') as usize] |= 1 << col;
+        }
+
+        for (masks, mask) in masks.iter_mut().zip(word_masks) {
+            masks.push(mask);
+        }
+
+        cols = len;
+    }
+
+    masks
+        .into_iter()
+        .enumerate()
+        .map(|(letter, mut masks)| {
+            let columns = (0..cols)
+                .map(|col| {
+                    masks.iter().filter(|mask| *mask & (1 << col) != 0).count() as f32 / total
+                })
+                .collect();
+
+            let present = masks.iter().filter(|mask| **mask != 0).count() as f32 / total;
+
+            // Calculate the entropy of the column mask distribution
+            masks.sort_unstable();
+
+            let bits = masks
+                .chunk_by(|a, b| a == b)
+                .map(|group| {
+                    let p = group.len() as f32 / total;
+                    -p * p.log2()
+                })
+                .sum();
+
+            LetterInformation {
+                letter: (b'A' + letter as u8) as char,
+                present,
+                columns,
+                bits,
+            }
+        })
+        .collect()
+}
+
+/// Scores each word by the expected information (in bits) gained by guessing it when all of the
+/// words are equally likely to be the answer
+pub fn rank_words(dictionary: &Dictionary, words: &[LetterNext]) -> Vec<f32> {
+    rank_words_cancellable(dictionary, words, &CancelToken::new())
+        .expect("Ranking cancelled without a cancel token")
+}
+
+/// Scores each word as rank_words does, checking the token before scoring each word
+/// Returns None if the token is cancelled before all of the words are scored
+pub fn rank_words_cancellable(
+    dictionary: &Dictionary,
+    words: &[LetterNext],
+    cancel: &CancelToken,
+) -> Option<Vec<f32>> {
+    let letters: Vec<WordLetters> = words
+        .iter()
+        .map(|elem| word_letters(dictionary, *elem))
+        .collect();
+
+    letters
+        .iter()
+        .map(|guess| (!cancel.is_cancelled()).then(|| score_guess(guess, &letters)))
+        .collect()
+}
+
+/// Scores a guess by the expected information (in bits) gained by guessing it when all of the
+/// answers are equally likely
+pub fn score_guess(guess: &[u8], answers: &[WordLetters]) -> f32 {
+    let total = answers.len() as f32;
+
+    // Count the answers giving each pattern
+    let counts: Vec<u32> = if guess.len() <= MAX_TABLE_COLS {
+        let mut counts = vec![0u32; patterns(guess.len())];
+
+        for answer in answers {
+            counts[pattern(guess, answer) as usize] += 1;
+        }
+
+        counts
+    } else {
+        let mut counts: HashMap<Pattern, u32> = HashMap::new();
+
+        for answer in answers {
+            *counts.entry(pattern(guess, answer)).or_default() += 1;
+        }
+
+        counts.into_values().collect()
+    };
+
+    // Calculate the entropy of the pattern distribution
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f32 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
@@ -0,0 +1,106 @@
+//! Game rule variants
+
+use crate::{feedback, BoardRow, Constraints, BOARD_ROWS};
+
+/// Rules for a game variant
+pub trait GameRules: Send + Sync {
+    /// Name used to select the variant
+    fn name(&self) -> &'static str;
+
+    /// Number of guesses allowed, or None if there is no limit
+    fn rows(&self) -> Option<usize>;
+
+    /// Number of answers played at the same time, each with its own board
+    fn boards(&self) -> usize {
+        1
+    }
+
+    /// Calculates the board row for a guess played against an answer
+    /// Both words must be upper case and the same length
+    fn feedback(&self, guess: &str, answer: &str) -> BoardRow {
+        feedback(guess, answer)
+    }
+
+    /// Builds the constraints on the answer from a board with rows of the given length
+    fn constraints(&self, board: &[BoardRow], cols: usize) -> Constraints {
+        Constraints::new(board, cols)
+    }
+
+    /// Tests if a guess may be played on a board. The guess must be upper case
+    fn valid_guess(&self, _board: &[BoardRow], _guess: &str) -> bool {
+        true
+    }
+}
+
+/// Standard rules
+pub struct Standard;
+
+impl GameRules for Standard {
+    fn name(&self) -> &'static str {
+        "standard"
+    }
+
+    fn rows(&self) -> Option<usize> {
+        Some(BOARD_ROWS)
+    }
+}
+
+/// Hard mode, where green letters must be played in the same place and yellow letters must be
+/// used in later guesses
+pub struct HardMode;
+
+impl GameRules for HardMode {
+    fn name(&self) -> &'static str {
+        "hard"
+    }
+
+    fn rows(&self) -> Option<usize> {
+        Some(BOARD_ROWS)
+    }
+
+    fn valid_guess(&self, board: &[BoardRow], guess: &str) -> bool {
+        self.constraints(board, guess.len()).uses_hints(guess)
+    }
+}
+
+/// Absurdle, where the answer isn't chosen until the adversary is forced to and there is no
+/// limit on the guesses
+pub struct Absurdle;
+
+impl GameRules for Absurdle {
+    fn name(&self) -> &'static str {
+        "absurdle"
+    }
+
+    fn rows(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Quordle, where four answers are played at the same time with nine guesses
+pub struct Quordle;
+
+impl GameRules for Quordle {
+    fn name(&self) -> &'static str {
+        "quordle"
+    }
+
+    fn rows(&self) -> Option<usize> {
+        Some(9)
+    }
+
+    fn boards(&self) -> usize {
+        4
+    }
+}
+
+/// All of the game variants
+pub const GAME_RULES: [&dyn GameRules; 4] = [&Standard, &HardMode, &Absurdle, &Quordle];
+
+/// Finds a game variant by name
+pub fn game_rules(name: &str) -> Option<&'static dyn GameRules> {
+    GAME_RULES
+        .iter()
+        .find(|rules| rules.name().eq_ignore_ascii_case(name))
+        .copied()
+}
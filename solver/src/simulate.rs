@@ -0,0 +1,239 @@
+//! Game simulation
+
+use std::collections::BTreeMap;
+
+use crate::{pattern, score_guess, Pattern, WordLetters, BOARD_ROWS};
+
+/// Number of guesses taken to solve a set of games
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Distribution {
+    /// Number of games solved with each number of guesses (index 0 = one guess)
+    pub solved: [usize; BOARD_ROWS],
+    /// Number of games not solved within the board rows
+    pub failed: usize,
+}
+
+impl Distribution {
+    /// Adds the games from another distribution
+    pub fn merge(&mut self, other: &Self) {
+        for (solved, other) in self.solved.iter_mut().zip(other.solved.iter()) {
+            *solved += other;
+        }
+
+        self.failed += other.failed;
+    }
+
+    /// Returns the total number of games played
+    pub fn games(&self) -> usize {
+        self.solved.iter().sum::<usize>() + self.failed
+    }
+
+    /// Returns the mean number of guesses for the solved games
+    pub fn mean(&self) -> f32 {
+        let solved: usize = self.solved.iter().sum();
+
+        if solved == 0 {
+            0.0
+        } else {
+            let guesses: usize = self
+                .solved
+                .iter()
+                .enumerate()
+                .map(|(i, count)| (i + 1) * count)
+                .sum();
+
+            guesses as f32 / solved as f32
+        }
+    }
+
+    /// Returns the mean number of guesses for all of the games, counting failed games as one more
+    /// guess than the board rows
+    pub fn expected(&self) -> f32 {
+        match self.games() {
+            0 => 0.0,
+            games => {
+                let guesses: usize = self
+                    .solved
+                    .iter()
+                    .enumerate()
+                    .map(|(i, count)| (i + 1) * count)
+                    .sum::<usize>()
+                    + (BOARD_ROWS + 1) * self.failed;
+
+                guesses as f32 / games as f32
+            }
+        }
+    }
+
+    /// Returns the fraction of games not solved
+    pub fn fail_rate(&self) -> f32 {
+        match self.games() {
+            0 => 0.0,
+            games => self.failed as f32 / games as f32,
+        }
+    }
+}
+
+/// Chooses the next guess from a set of possible answers
+/// The default strategy guesses the answer giving the most expected information, choosing the
+/// first on a tie
+pub fn best_guess(answers: &[WordLetters], set: &[usize]) -> usize {
+    let letters: Vec<WordLetters> = set.iter().map(|i| answers[*i].clone()).collect();
+
+    set.iter()
+        .map(|i| (*i, score_guess(&answers[*i], &letters)))
+        .fold(None, |best: Option<(usize, f32)>, (i, score)| match best {
+            Some((_, best_score)) if best_score >= score => best,
+            _ => Some((i, score)),
+        })
+        .map(|(i, _)| i)
+        .expect("Empty answer set")
+}
+
+/// Plays a guess against each answer in a set on the given turn (1 = first guess)
+/// Returns the distribution of answers solved by the guess and the remaining answers grouped by
+/// the colour pattern they give, in pattern order
+pub fn play_partitions(
+    answers: &[WordLetters],
+    set: &[usize],
+    guess: &[u8],
+    turn: usize,
+) -> (Distribution, Vec<Vec<usize>>) {
+    let mut dist = Distribution::default();
+
+    let partitions = partition(answers, set, guess)
+        .into_iter()
+        .filter_map(|(pattern, partition)| {
+            if pattern == solved_pattern(guess) {
+                dist.solved[turn - 1] += partition.len();
+                None
+            } else {
+                Some(partition)
+            }
+        })
+        .collect();
+
+    (dist, partitions)
+}
+
+/// Groups a set of answers by the colour pattern a guess gives against them
+pub fn partition(
+    answers: &[WordLetters],
+    set: &[usize],
+    guess: &[u8],
+) -> BTreeMap<Pattern, Vec<usize>> {
+    let mut partitions: BTreeMap<Pattern, Vec<usize>> = BTreeMap::new();
+
+    for i in set {
+        partitions
+            .entry(pattern(guess, &answers[*i]))
+            .or_default()
+            .push(*i);
+    }
+
+    partitions
+}
+
+/// Counts the answers in a set left by each colour pattern a guess can give, in pattern order
+pub fn partition_sizes(
+    answers: &[WordLetters],
+    set: &[usize],
+    guess: &[u8],
+) -> BTreeMap<Pattern, usize> {
+    let mut sizes: BTreeMap<Pattern, usize> = BTreeMap::new();
+
+    for i in set {
+        *sizes.entry(pattern(guess, &answers[*i])).or_default() += 1;
+    }
+
+    sizes
+}
+
+/// Returns the colour pattern for a guess which is the answer
+pub fn solved_pattern(guess: &[u8]) -> Pattern {
+    Pattern::pow(3, guess.len() as u32) - 1
+}
+
+/// Chooses the colour pattern for a guess as an adversary (Absurdle rules), keeping as many of
+/// the possible answers as it can. Ties go to the lowest pattern number
+/// Returns the pattern and the answers remaining
+pub fn adversary(answers: &[WordLetters], set: &[usize], guess: &[u8]) -> (Pattern, Vec<usize>) {
+    partition(answers, set, guess)
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, partition)| partition.len())
+        .expect("Empty answer set")
+}
+
+/// Plays the default strategy against each answer in a set starting on the given turn
+pub fn play_set(answers: &[WordLetters], set: &[usize], turn: usize) -> Distribution {
+    if turn > BOARD_ROWS {
+        Distribution {
+            failed: set.len(),
+            ..Distribution::default()
+        }
+    } else {
+        let guess = best_guess(answers, set);
+
+        play_guess(answers, set, &answers[guess], turn)
+    }
+}
+
+/// Plays a guess against each answer in a set on the given turn, then the default strategy until
+/// each game is solved or the board is full
+pub fn play_guess(
+    answers: &[WordLetters],
+    set: &[usize],
+    guess: &[u8],
+    turn: usize,
+) -> Distribution {
+    let (mut dist, partitions) = play_partitions(answers, set, guess, turn);
+
+    for partition in partitions {
+        dist.merge(&play_set(answers, &partition, turn + 1));
+    }
+
+    dist
+}
+
+/// Plays a guess followed by the default strategy against one answer from a set, returning the
+/// distribution for the single game
+pub fn play_answer(
+    answers: &[WordLetters],
+    set: &[usize],
+    guess: &[u8],
+    answer: usize,
+) -> Distribution {
+    play_answer_by(answers, set, guess, answer, |set| best_guess(answers, set))
+}
+
+/// Plays a guess against one answer from a set, then the guesses chosen from the answers left
+/// until the game is solved or the board is full, returning the distribution for the single game
+pub fn play_answer_by(
+    answers: &[WordLetters],
+    set: &[usize],
+    guess: &[u8],
+    answer: usize,
+    mut choose: impl FnMut(&[usize]) -> usize,
+) -> Distribution {
+    let mut dist = Distribution::default();
+    let mut set = set.to_vec();
+    let mut guess = guess.to_vec();
+
+    for turn in 1..=BOARD_ROWS {
+        let colours = pattern(&guess, &answers[answer]);
+
+        if colours == solved_pattern(&guess) {
+            dist.solved[turn - 1] += 1;
+            return dist;
+        }
+
+        if turn < BOARD_ROWS {
+            set.retain(|i| pattern(&guess, &answers[*i]) == colours);
+            guess = answers[choose(&set)].clone();
+        }
+    }
+
+    dist.failed += 1;
+    dist
+}
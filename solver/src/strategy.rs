@@ -0,0 +1,156 @@
+//! Word ranking strategies
+
+use std::cmp::Ordering;
+
+use dictionary::{Dictionary, LetterNext};
+
+use crate::{
+    partition,
+    play_guess,
+    score_guess,
+    solved_pattern,
+    word_letters,
+    CancelToken,
+    WordLetters,
+};
+
+/// The hybrid strategy ranks by expected guesses at or below this number of words
+pub const HYBRID_THRESHOLD: usize = 50;
+
+/// Expected guesses are found by playing out every game at or below this number of words,
+/// otherwise they are estimated from the sizes of the groups each guess splits the words into
+pub const EXPECTED_SEARCH_LIMIT: usize = 100;
+
+/// Bits of information assumed to be gained by each guess when estimating expected guesses
+const ESTIMATE_BITS_PER_GUESS: f32 = 2.0;
+
+/// Word ranking strategy
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Rank by the expected information gained
+    #[default]
+    Entropy,
+    /// Rank by the expected number of guesses to solve
+    Expected,
+    /// Rank by expected information until few words remain, then by expected guesses
+    Hybrid,
+}
+
+impl Strategy {
+    /// Returns how a number of words will be scored by the strategy
+    pub fn score_kind(self, words: usize) -> ScoreKind {
+        match self {
+            Self::Entropy => ScoreKind::Information,
+            Self::Expected => ScoreKind::Guesses,
+            Self::Hybrid if words <= HYBRID_THRESHOLD => ScoreKind::Guesses,
+            Self::Hybrid => ScoreKind::Information,
+        }
+    }
+}
+
+impl std::str::FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "entropy" => Ok(Self::Entropy),
+            "expected" => Ok(Self::Expected),
+            "hybrid" => Ok(Self::Hybrid),
+            _ => Err(format!("Unknown strategy '{s}'")),
+        }
+    }
+}
+
+impl std::fmt::Display for Strategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Entropy => "entropy",
+            Self::Expected => "expected",
+            Self::Hybrid => "hybrid",
+        })
+    }
+}
+
+/// Measure used to score guesses
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScoreKind {
+    /// Expected information gained in bits, higher is better
+    Information,
+    /// Expected number of guesses to solve including the guess, lower is better
+    Guesses,
+}
+
+impl ScoreKind {
+    /// Compares two scores, returning Greater if the first is the better score
+    pub fn compare(self, a: f32, b: f32) -> Ordering {
+        match self {
+            Self::Information => a.total_cmp(&b),
+            Self::Guesses => b.total_cmp(&a),
+        }
+    }
+}
+
+/// Scores each word with a strategy when all of the words are equally likely to be the answer,
+/// checking the token before scoring each word
+/// Returns how the words were scored, or None if the token is cancelled before all of the words
+/// are scored
+pub fn rank_words_strategy(
+    dictionary: &Dictionary,
+    words: &[LetterNext],
+    strategy: Strategy,
+    cancel: &CancelToken,
+) -> Option<(ScoreKind, Vec<f32>)> {
+    let letters: Vec<WordLetters> = words
+        .iter()
+        .map(|elem| word_letters(dictionary, *elem))
+        .collect();
+
+    rank_letters_strategy(&letters, strategy, cancel)
+}
+
+/// Scores the letters of each word with a strategy when all of the words are equally likely to be
+/// the answer, checking the token before scoring each word
+/// The letters can be scored on another thread as the dictionary isn't needed
+pub fn rank_letters_strategy(
+    letters: &[WordLetters],
+    strategy: Strategy,
+    cancel: &CancelToken,
+) -> Option<(ScoreKind, Vec<f32>)> {
+    let kind = strategy.score_kind(letters.len());
+
+    let scores = letters
+        .iter()
+        .map(|guess| {
+            (!cancel.is_cancelled()).then(|| match kind {
+                ScoreKind::Information => score_guess(guess, letters),
+                ScoreKind::Guesses => expected_guesses(guess, letters),
+            })
+        })
+        .collect::<Option<_>>()?;
+
+    Some((kind, scores))
+}
+
+/// Returns the expected number of guesses to solve when all of the answers are equally likely,
+/// including the guess
+/// Small sets of answers are played out with the default strategy, counting failed games as
+/// one more guess than the board rows. Larger sets are estimated
+pub fn expected_guesses(guess: &[u8], answers: &[WordLetters]) -> f32 {
+    let set: Vec<usize> = (0..answers.len()).collect();
+
+    if answers.len() <= EXPECTED_SEARCH_LIMIT {
+        play_guess(answers, &set, guess, 1).expected()
+    } else {
+        // One guess plus the estimated guesses to solve each group of answers left
+        let total = answers.len() as f32;
+
+        1.0 + partition(answers, &set, guess)
+            .into_iter()
+            .filter(|(pattern, _)| *pattern != solved_pattern(guess))
+            .map(|(_, group)| {
+                let len = group.len() as f32;
+                (len / total) * (1.0 + len.log2() / ESTIMATE_BITS_PER_GUESS)
+            })
+            .sum::<f32>()
+    }
+}
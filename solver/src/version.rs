@@ -0,0 +1,44 @@
+//! Build and word list details for bug reports
+
+use std::sync::OnceLock;
+
+use dictionary::{file_sha256, Dictionary, BUNDLED_DICT, BUNDLED_SHA256};
+
+/// Solver version with the commit and date it was built from, eg. "0.1.0 (1a2b3c4 2024-08-10)"
+pub const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("SOLVER_GIT_HASH"),
+    " ",
+    env!("SOLVER_BUILD_DATE"),
+    ")"
+);
+
+/// Returns the version followed by the bundled word list and its SHA-256 hash, for --version
+pub fn long_version() -> &'static str {
+    static LONG_VERSION: OnceLock<String> = OnceLock::new();
+
+    LONG_VERSION.get_or_init(|| {
+        format!("{VERSION}\nBundled word list {BUNDLED_DICT} SHA-256 {BUNDLED_SHA256}")
+    })
+}
+
+/// Returns lines describing the solver build and the word list loaded from a file, with the
+/// file's SHA-256 hash
+pub fn build_info(dictionary_file: &str, dictionary: &Dictionary) -> Vec<String> {
+    let sha256 = match file_sha256(dictionary_file) {
+        Ok(sha256) if sha256 == BUNDLED_SHA256 => format!("{sha256} (bundled)"),
+        Ok(sha256) => sha256,
+        Err(e) => format!("unavailable: {e}"),
+    };
+
+    vec![
+        format!("Version {VERSION}"),
+        format!(
+            "Word list {dictionary_file}: {}, {} words",
+            dictionary.metadata(),
+            dictionary.word_count()
+        ),
+        format!("SHA-256 {sha256}"),
+    ]
+}
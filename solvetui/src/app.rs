@@ -1,14 +1,34 @@
-use std::io;
+use std::time::Duration;
+use std::{cmp, io};
 
-use crossterm::event::{self, Event, KeyCode, MouseEventKind};
+use crossterm::event::{self, Event, KeyCode, MouseButton, MouseEvent, MouseEventKind};
 use dictionary::Dictionary;
+use log::{debug, warn};
+use numformat::{DurationFormat, NumFormat};
 use ratatui::backend::Backend;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style, Stylize};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Borders, Cell, Padding, Paragraph, Row, Table, Wrap};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Padding, Paragraph, Row, Table, Wrap};
 use ratatui::{Frame, Terminal};
-use solveapp::{BoardElem, SolveApp, BOARD_COLS, BOARD_ROWS};
+use solveapp::{
+    BoardCommand,
+    BoardEffect,
+    BoardElem,
+    BoardRow,
+    CountBound,
+    ExtraWords,
+    Preview,
+    ScoreKind,
+    Settings,
+    SolveApp,
+    Strategy,
+    WordSort,
+    BOARD_ROWS,
+};
+
+use crate::demo::Demo;
+use crate::events::Input;
 
 /// App holds the state of the application
 pub struct App {
@@ -18,6 +38,36 @@ pub struct App {
     board_rect: Option<Rect>,
     /// Words rectange
     words_rect: Option<Rect>,
+    /// Status bar rectangle
+    status_rect: Option<Rect>,
+    /// Shortlist pane rectangle
+    shortlist_rect: Option<Rect>,
+    /// Preview pane rectangle
+    preview_rect: Option<Rect>,
+    /// Letter information pane rectangle
+    letters_rect: Option<Rect>,
+    /// Solver timing pane rectangle
+    debug_rect: Option<Rect>,
+    /// Solver timing pane shown
+    debug: bool,
+    /// Letter information pane shown
+    letters: bool,
+    /// Words grouped by their first letter
+    group: bool,
+    /// Search being typed
+    searching: bool,
+    /// Demo games when in demo mode
+    demo: Option<Demo>,
+    /// Word list index of the highlighted word
+    highlight: Option<usize>,
+    /// Words left by each colour pattern for the highlighted word
+    preview: Option<Preview>,
+    /// Solver build and word list details shown below the instructions
+    about: Vec<String>,
+    /// Print the board in colour when exiting
+    export: bool,
+    /// Letter selected in the letter counts popup, if showing
+    counts: Option<char>,
 }
 
 impl App {
@@ -34,36 +84,131 @@ impl App {
     const CELL_YSPACE: u16 = 1;
     /// Total height of a board cell
     const CELL_YTOTAL: u16 = Self::CELL_HEIGHT + Self::CELL_YSPACE;
+    /// Height of the column number header above the board, including the space below it
+    const HEADER_HEIGHT: u16 = 2;
+
+    /// Words scoring at least this fraction of the way from the worst to the best are bold
+    const HOT: f32 = 2.0 / 3.0;
+    /// Words scoring below this fraction of the way from the worst to the best are dimmed
+    const COLD: f32 = 1.0 / 3.0;
+
+    /// Colour patterns shown in each column of the preview pane
+    const PREVIEW_ROWS: u16 = 4;
+    /// Letters shown in the letter information pane
+    const LETTERS_ROWS: u16 = 8;
+    /// How often to check whether the background ranking has finished
+    const RANK_POLL: Duration = Duration::from_millis(50);
 
     /// Usage instructions
-    const INSTRUCTIONS: &'static str = r#"
-Wordle Solver
-    
-Fill the board on the left by pressing letter keys.
+    const INSTRUCTIONS: &'static str = r#"Fill the board on the left by pressing letter keys.
+
+The colour of each letter can be toggled by clicking with the mouse or with the number keys, which act on the row underlined with a bar. Page Up and Page Down select another row, as does clicking a letter. Right click toggles backwards and middle click clears the colour. Letters are shown in black until their colour is set, and turn gray when the next row is started. Columns with a green letter show a lock, and the number key must be pressed twice to change a green letter.
+
+Guesses which aren't in the word list are shown in red. Press + to add the latest one to your word list.
+
+Use the arrow keys to highlight a word and press s to pin it to the shortlist, or s again to unpin it. Pinned words are underlined and are unpinned when they no longer fit the board. The colour patterns the highlighted word could be given are shown below the words with the number of words each would leave, most first. Escape clears the highlight.
+
+When ranking words, press Tab to switch between alphabetical and score order. Words with the best scores are shown in bold and the worst are dimmed.
+
+Press / to search the words, typing letters with ? matching any letter. Only the words containing the search are shown, with the matching letters picked out. Enter finishes the search and Escape clears it.
 
-The colour of each letter can be toggled by clicking with the mouse or with the keys 1-5.
+Press F2 to show the time the solver takes for each row, and F3 to group the words by their first letter. Press F4 to print the board in colour when you exit, to paste into a terminal or an issue.
+
+Press F6 to show how much information each letter not on the board yet would give if you probed it, with the share of the words containing it and the share with it in each column, to help craft your own guesses.
+
+Press F5 to set the fewest and most times letters appear, for knowledge the board can't show. Type a letter to choose it, then + and - change the fewest, ] and [ change the most and Delete clears it.
 
 Press Escape to exit"#;
 
-    /// Creates the application
-    pub fn new(dictionary: Dictionary) -> Self {
+    /// Creates the application, starting with an imported board
+    /// In demo mode games are played automatically using the top ranked word. The ranking
+    /// strategy overrides the one in the settings if given. The build details are shown below the
+    /// instructions
+    pub fn new(
+        dictionary: Dictionary,
+        rank: bool,
+        strategy: Option<Strategy>,
+        board: &[BoardRow],
+        demo: bool,
+        seed: Option<u64>,
+        about: Vec<String>,
+    ) -> Self {
+        let demo = demo.then(|| Demo::new(&dictionary, seed));
+
+        let mut app = SolveApp::new(dictionary);
+
+        app.set_ranking(rank || demo.is_some());
+
+        if let Err(e) = app.add_words(&ExtraWords::load()) {
+            warn!("Unable to load extra words: {e}");
+        }
+
+        let settings = Settings::load();
+        app.set_lock_greens(settings.lock_greens && demo.is_none());
+        app.set_mark_absent(settings.mark_absent);
+        app.set_reject_unknown(settings.reject_unknown);
+        app.set_strict(settings.strict);
+        app.set_show_eliminated(settings.show_eliminated);
+        app.set_strategy(strategy.unwrap_or(settings.strategy));
+
+        if let BoardEffect::Rejected(reason) = app.apply(BoardCommand::Import(board.to_vec())) {
+            warn!("Unable to import the board: {reason}");
+        }
+
         App {
-            app: SolveApp::new(dictionary),
+            app,
             board_rect: None,
             words_rect: None,
+            status_rect: None,
+            shortlist_rect: None,
+            preview_rect: None,
+            letters_rect: None,
+            debug_rect: None,
+            debug: false,
+            letters: false,
+            group: settings.group_words,
+            searching: false,
+            demo,
+            highlight: None,
+            preview: None,
+            about,
+            export: false,
+            counts: None,
         }
     }
 
-    /// Runs the application
-    pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+    /// Sets the extra words accepted as guesses, from a language pack
+    pub fn set_guesses(&mut self, guesses: Dictionary) {
+        self.app.set_guesses(guesses);
+    }
+
+    /// Returns the board in ANSI colours if it should be printed on exit
+    pub fn exported_board(&self) -> Option<String> {
+        self.export.then(|| self.app.board_ansi())
+    }
+
+    /// Runs the application until escape is pressed or the input runs out of events
+    pub fn run<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        input: &mut Input,
+    ) -> io::Result<()> {
         let mut render = true;
         let mut calculate = true;
 
+        // Rank words without blocking key presses, unless replaying or playing a demo where
+        // the rankings are needed straight away
+        self.app
+            .set_background(input.is_terminal() && self.demo.is_none());
+
         loop {
             // Need to recalculate?
             if calculate {
                 self.app.calculate();
 
+                // The word list has changed so the highlighted word has moved
+                self.set_highlight(None);
+
                 calculate = false;
                 render = true;
             }
@@ -74,8 +219,42 @@ Press Escape to exit"#;
                 render = false;
             }
 
+            // Run the next demo step when it's due
+            if let Some(demo) = &mut self.demo {
+                if !event::poll(demo.wait())? {
+                    calculate = demo.step(&mut self.app);
+                    render = true;
+                    continue;
+                }
+            }
+
+            // Collect the word scores when the ranking finishes
+            if self.app.ranking_pending() && !event::poll(Self::RANK_POLL)? {
+                if self.app.poll_ranking() {
+                    // The words have been sorted so the highlighted word has moved
+                    self.set_highlight(None);
+                    render = true;
+                }
+
+                continue;
+            }
+
             // Get the next event
-            let Ok(event) = event::read() else { continue };
+            let event = match input.read() {
+                Some(Ok(event)) => event,
+                Some(Err(_)) => continue,
+                None => break Ok(()),
+            };
+
+            // Log key and mouse button events
+            match event {
+                Event::Key(_)
+                | Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::Down(_),
+                    ..
+                }) => debug!("Event {event:?}"),
+                _ => (),
+            }
 
             // Process the event
             match event {
@@ -85,47 +264,145 @@ Press Escape to exit"#;
                 }
                 Event::Key(event) => match event.code {
                     // Keyboard event
+                    code if self.searching => {
+                        // Typing a search
+                        self.search_key(code);
+                        render = true;
+                    }
+                    code if self.counts.is_some() => {
+                        // Letter counts popup showing
+                        calculate = self.counts_key(code);
+                        render = true;
+                    }
+                    KeyCode::Char('/') => {
+                        // Slash pressed - start a new search of the words
+                        self.searching = true;
+                        self.set_search("");
+                        render = true;
+                    }
+                    KeyCode::Esc if self.highlight.is_some() => {
+                        // Escape pressed with a word highlighted - clear the highlight
+                        self.set_highlight(None);
+                        render = true;
+                    }
+                    KeyCode::Esc if self.app.search().is_some() => {
+                        // Escape pressed with a search - clear the search
+                        self.set_search("");
+                        render = true;
+                    }
                     KeyCode::Esc => {
                         // Escape pressed
                         break Ok(());
                     }
+                    KeyCode::Tab if self.app.ranking() => {
+                        // Tab pressed - switch word sort order
+                        self.app.set_sort(match self.app.sort() {
+                            WordSort::Alpha => WordSort::Score,
+                            WordSort::Score => WordSort::Alpha,
+                        });
+
+                        calculate = true;
+                    }
+                    KeyCode::F(2) => {
+                        // F2 pressed - show or hide the solver timing pane
+                        self.debug = !self.debug;
+                        render = true;
+                    }
+                    KeyCode::F(3) => {
+                        // F3 pressed - group the words by first letter or show them in columns
+                        self.group = !self.group;
+                        render = true;
+                    }
+                    KeyCode::F(4) => {
+                        // F4 pressed - print the board when exiting or cancel
+                        self.export = !self.export;
+                        render = true;
+                    }
+                    KeyCode::F(6) => {
+                        // F6 pressed - show or hide the letter information pane
+                        self.letters = !self.letters;
+                        render = true;
+                    }
+                    _ if self.demo.is_some() => {
+                        // The board can't be changed in demo mode
+                    }
+                    KeyCode::F(5) => {
+                        // F5 pressed - show the letter counts popup
+                        self.counts = Some('A');
+                        render = true;
+                    }
+                    KeyCode::PageUp | KeyCode::PageDown => {
+                        // Page up / down pressed - select the row the number keys act on
+                        if let Some(row) = self.app.active_row() {
+                            let row = if event.code == KeyCode::PageUp {
+                                row.saturating_sub(1)
+                            } else {
+                                row + 1
+                            };
+
+                            render = self.app.apply(BoardCommand::SelectRow(row)).changed();
+                        }
+                    }
+                    KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+                        // Arrow pressed - move the word list highlight
+                        self.move_highlight(event.code);
+                        render = true;
+                    }
+                    KeyCode::Char('s') if self.highlight.is_some() => {
+                        // s pressed with a word highlighted - pin or unpin it
+                        if let Some(elem) = self.highlight {
+                            self.app.toggle_pin(elem);
+                        }
+                        render = true;
+                    }
                     KeyCode::Char(c) if c.is_ascii_uppercase() => {
                         // Upper case character
-                        if self.app.add(c) {
-                            calculate = true;
-                        }
+                        calculate = self.app.apply(BoardCommand::Add(c)).changed();
                     }
                     KeyCode::Char(c) if c.is_ascii_lowercase() => {
                         // Lower case character
-                        if self.app.add(c.to_ascii_uppercase()) {
-                            calculate = true;
-                        }
+                        let c = c.to_ascii_uppercase();
+
+                        calculate = self.app.apply(BoardCommand::Add(c)).changed();
                     }
                     KeyCode::Char(c) if ('1'..='9').contains(&c) => {
                         // Number pressed
                         let col = (c as u8 - b'1') as usize;
 
-                        if self.app.toggle_col(col) {
+                        if self.app.apply(BoardCommand::ToggleCol(col)).changed() {
                             calculate = true;
                         }
                     }
+                    KeyCode::Char('+') => {
+                        // Plus pressed - add unknown word to the extra words
+                        match self.app.learn_unknown_word() {
+                            Ok(word) => calculate = word.is_some(),
+                            Err(e) => warn!("Unable to add word: {e}"),
+                        }
+                    }
                     KeyCode::Backspace | KeyCode::Delete => {
                         // Backspace / delete pressed
-                        if self.app.remove() {
-                            calculate = true;
-                        }
+                        calculate = self.app.apply(BoardCommand::Remove).changed();
                     }
                     _ => (),
                 },
-                Event::Mouse(event) => {
+                Event::Mouse(event) if self.demo.is_none() => {
                     // Mouse event
-                    if let MouseEventKind::Down(event::MouseButton::Left) = event.kind {
-                        // Mouse left click - check for board hit
+                    if let MouseEventKind::Down(button) = event.kind {
+                        // Mouse click - check for board hit
                         if let Some((row, col)) = self.board_hit(event.row, event.column) {
-                            // Try and toggle the board element
-                            if self.app.toggle(row, col) {
-                                calculate = true;
-                            }
+                            // Clicking a letter selects its row for the number keys
+                            self.app.apply(BoardCommand::SelectRow(row));
+
+                            // Left toggles the board element forwards, right backwards and
+                            // middle clears its colour
+                            let command = match button {
+                                MouseButton::Left => BoardCommand::Toggle { row, col },
+                                MouseButton::Right => BoardCommand::ToggleBack { row, col },
+                                MouseButton::Middle => BoardCommand::ClearColour { row, col },
+                            };
+
+                            calculate = self.app.apply(command).changed();
                         }
                     }
                 }
@@ -136,83 +413,426 @@ Press Escape to exit"#;
 
     /// Renders the next frame
     fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        let cols = self.app.cols() as u16;
+
         terminal.draw(|f| {
-            // Split the terminal in to two horizontal sections
+            // Split the terminal in to the main area and the status bar
+            let vchunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+                .split(f.area());
+
+            // Split the main area in to two horizontal sections
             let chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints(
                     [
-                        Constraint::Length(
-                            (BOARD_COLS as u16 * Self::CELL_XTOTAL) - Self::CELL_XSPACE + 2,
-                        ),
-                        Constraint::Min(BOARD_COLS as u16),
+                        Constraint::Length((cols * Self::CELL_XTOTAL) - Self::CELL_XSPACE + 2),
+                        Constraint::Min(cols),
                     ]
                     .as_ref(),
                 )
-                .split(f.area());
+                .split(vchunks[0]);
+
+            // Split the solver timing pane from the bottom of the right hand section
+            let rchunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(0),
+                        Constraint::Length(if self.preview.is_some() {
+                            Self::PREVIEW_ROWS + 2
+                        } else {
+                            0
+                        }),
+                        Constraint::Length(if self.app.shortlist().is_empty() {
+                            0
+                        } else {
+                            3
+                        }),
+                        Constraint::Length(if self.letters {
+                            Self::LETTERS_ROWS + 3
+                        } else {
+                            0
+                        }),
+                        Constraint::Length(if self.debug { BOARD_ROWS as u16 + 3 } else { 0 }),
+                    ]
+                    .as_ref(),
+                )
+                .split(chunks[1]);
 
             // Save rectangles
             self.board_rect = Some(chunks[0]);
-            self.words_rect = Some(chunks[1]);
+            self.words_rect = Some(rchunks[0]);
+            self.preview_rect = self.preview.is_some().then_some(rchunks[1]);
+            self.shortlist_rect = (!self.app.shortlist().is_empty()).then_some(rchunks[2]);
+            self.letters_rect = self.letters.then_some(rchunks[3]);
+            self.debug_rect = self.debug.then_some(rchunks[4]);
+            self.status_rect = Some(vchunks[1]);
+
+            // Draw the status bar
+            self.status_bar(f);
 
             // Draw the board in the left hand section
             self.board_table(f);
 
+            // Draw the preview and shortlist panes
+            self.preview_pane(f);
+            self.shortlist_pane(f);
+
+            // Draw the letter information and solver timing panes
+            self.letters_table(f);
+            self.debug_table(f);
+
             if self.app.words().count().is_some() {
                 // Draw the word list in the right hand section
                 self.words_table(f);
             } else {
-                // Draw the instructions in the right hand section
+                // Draw the instructions in the right hand section, with the build details to
+                // quote in bug reports under the title
+                let mut text =
+                    Text::from(vec![Line::default(), Line::from("Wordle Solver").bold()]);
+
+                text.extend(
+                    self.about
+                        .iter()
+                        .map(|line| Line::from(line.as_str()).dark_gray()),
+                );
+                text.push_line(Line::default());
+                text.extend(Text::styled(
+                    Self::INSTRUCTIONS,
+                    Style::default().add_modifier(Modifier::BOLD),
+                ));
+
                 f.render_widget(
-                    Paragraph::new(Text::styled(
-                        Self::INSTRUCTIONS,
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ))
-                    .wrap(Wrap { trim: false })
-                    .block(Block::default().borders(Borders::ALL).title("Instructions")),
+                    Paragraph::new(text)
+                        .wrap(Wrap { trim: false })
+                        .block(Block::default().borders(Borders::ALL).title("Instructions")),
                     self.words_rect.unwrap(),
                 )
             }
+
+            // Draw the letter counts popup over the words
+            self.counts_popup(f);
         })?;
 
         Ok(())
     }
 
+    /// Draws the status bar
+    /// Shows the demo progress in demo mode, why a colour change was rejected in strict mode,
+    /// offers to add a guess missing from the word list, says the board will be printed on exit or
+    /// shows why the top suggestion is best if ranking, then the solver statistics and word list
+    /// description
+    fn status_bar(&self, f: &mut Frame) {
+        let (text, colour) = match (&self.demo, self.app.rejected(), self.app.unknown_word()) {
+            (Some(demo), _, _) => (demo.status(), Color::Cyan),
+            (None, Some(reason), _) => (reason.to_string(), Color::Red),
+            (None, None, Some(word)) => (
+                format!("{word} is not in the word list, press + to add it"),
+                Color::Red,
+            ),
+            (None, None, None) if self.export => (
+                "The board will be printed in colour on exit, press F4 to cancel".to_string(),
+                Color::Cyan,
+            ),
+            (None, None, None) => match self.app.explain_best() {
+                Some(explanation) => (
+                    format!("{explanation} - {}", self.app.status_text()),
+                    Color::DarkGray,
+                ),
+                None => (self.app.status_text(), Color::DarkGray),
+            },
+        };
+
+        if let Some(rect) = self.status_rect {
+            f.render_widget(
+                Paragraph::new(Text::styled(text, Style::default().fg(colour))),
+                rect,
+            );
+        }
+    }
+
+    /// Draws the colour patterns the highlighted word could be given, each with the board row and
+    /// the number of words it would leave
+    fn preview_pane(&self, f: &mut Frame) {
+        if let (Some(rect), Some(preview)) = (self.preview_rect, &self.preview) {
+            let rows = Self::PREVIEW_ROWS as usize;
+
+            // Each entry is the coloured word followed by the word count
+            let entry_width = self.app.cols() + 7;
+            let cols = ((rect.width as usize).saturating_sub(2) / entry_width).max(1);
+
+            let lines = (0..rows)
+                .map(|row| {
+                    Line::from(
+                        (0..cols)
+                            .filter_map(|col| preview.groups.get((col * rows) + row))
+                            .flat_map(|(board_row, words)| {
+                                let mut spans: Vec<Span> = board_row
+                                    .iter()
+                                    .map(|elem| match elem {
+                                        BoardElem::Gray(c) => (*c, Color::DarkGray),
+                                        BoardElem::Yellow(c) => (*c, Color::Yellow),
+                                        BoardElem::Green(c) => (*c, Color::Green),
+                                        BoardElem::Empty | BoardElem::Unknown(_) => {
+                                            (' ', Color::Black)
+                                        }
+                                    })
+                                    .map(|(c, colour)| {
+                                        Span::styled(
+                                            c.to_string(),
+                                            Style::default().bg(colour).bold(),
+                                        )
+                                    })
+                                    .collect();
+
+                                spans.push(Span::raw(format!(" {words:>5} ")));
+
+                                spans
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let title = format!(
+                "Preview {} ({} patterns, at worst {} words left)",
+                preview.word,
+                preview.groups.len(),
+                preview.worst()
+            );
+
+            f.render_widget(
+                Paragraph::new(Text::from(lines))
+                    .block(Block::default().borders(Borders::ALL).title(title)),
+                rect,
+            );
+        }
+    }
+
+    /// Draws the pinned words
+    fn shortlist_pane(&self, f: &mut Frame) {
+        if let Some(rect) = self.shortlist_rect {
+            let shortlist = self.app.shortlist();
+
+            f.render_widget(
+                Paragraph::new(shortlist.join(" ")).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!("Shortlist ({})", shortlist.len())),
+                ),
+                rect,
+            );
+        }
+    }
+
+    /// Draws the information each letter not on the board yet would give if probed, with the
+    /// share of the words containing it and the share with it in each column
+    fn letters_table(&self, f: &mut Frame) {
+        if let Some(rect) = self.letters_rect {
+            let percent = |share: f32| format!("{:.0}%", share * 100.0);
+
+            let rows = self.app.letter_information().into_iter().map(|info| {
+                let mut cells = vec![
+                    info.letter.to_string(),
+                    format!("{:.2}", info.bits),
+                    percent(info.present),
+                ];
+
+                cells.extend(info.columns.iter().map(|share| percent(*share)));
+
+                Row::new(cells)
+            });
+
+            let cols = self.app.cols();
+
+            let mut header = vec![
+                "Letter".to_string(),
+                "Bits".to_string(),
+                "Words".to_string(),
+            ];
+            header.extend((1..=cols).map(|col| col.to_string()));
+
+            let widths = [6, 5, 6]
+                .into_iter()
+                .chain(std::iter::repeat_n(5, cols))
+                .map(Constraint::Length);
+
+            let table = Table::new(rows, widths)
+                .header(Row::new(header).add_modifier(Modifier::BOLD))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Letter information"),
+                );
+
+            f.render_widget(table, rect);
+        }
+    }
+
+    /// Draws the solver statistics for each number of completed rows, splitting the time spent
+    /// walking the dictionary tree from the time spent checking letter counts
+    fn debug_table(&self, f: &mut Frame) {
+        if let Some(rect) = self.debug_rect {
+            let rows = self.app.row_stats().iter().map(|row| {
+                Row::new([
+                    row.row.to_string(),
+                    if row.filtered { "filtered" } else { "searched" }.to_string(),
+                    row.words.num_format(),
+                    row.stats.nodes.num_format(),
+                    row.stats.walk_time().dur_format(),
+                    row.stats.contains_checks.num_format(),
+                    row.stats.contains_time.dur_format(),
+                ])
+            });
+
+            let header = Row::new([
+                "Row", "Method", "Words", "Nodes", "Walk", "Checks", "Counts",
+            ])
+            .add_modifier(Modifier::BOLD);
+
+            let table = Table::new(rows, [3, 8, 8, 8, 10, 8, 10].map(Constraint::Length))
+                .header(header)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Solver timing"),
+                );
+
+            f.render_widget(table, rect);
+        }
+    }
+
+    /// Draws the fewest and most times each letter with a count appears, marking those set by
+    /// hand, over the word list
+    fn counts_popup(&self, f: &mut Frame) {
+        if let (Some(rect), Some(selected)) = (self.words_rect, self.counts) {
+            let rows: Vec<Row> = ('A'..='Z')
+                .filter_map(|letter| {
+                    let (min, max) = self.app.letter_count(letter);
+                    let set = self.app.count_set(letter).is_some();
+
+                    if (min, max) == (0, None) && !set && letter != selected {
+                        return None;
+                    }
+
+                    let row = Row::new([
+                        letter.to_string(),
+                        min.to_string(),
+                        max.map_or("any".to_string(), |max| max.to_string()),
+                        if set { "by hand" } else { "" }.to_string(),
+                    ]);
+
+                    Some(if letter == selected {
+                        row.reversed()
+                    } else {
+                        row
+                    })
+                })
+                .collect();
+
+            // Centre the popup in the word list area
+            let width = cmp::min(36, rect.width);
+            let height = cmp::min(rows.len() as u16 + 4, rect.height);
+
+            let popup = Rect::new(
+                rect.x + (rect.width - width) / 2,
+                rect.y + (rect.height - height) / 2,
+                width,
+                height,
+            );
+
+            let header = Row::new(["Letter", "Fewest", "Most", ""]).add_modifier(Modifier::BOLD);
+
+            let table = Table::new(rows, [7, 7, 6, 8].map(Constraint::Length))
+                .header(header)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Letter counts")
+                        .title_bottom("+/- fewest [/] most Esc"),
+                );
+
+            f.render_widget(Clear, popup);
+            f.render_widget(table, popup);
+        }
+    }
+
     /// Draws the board table
     fn board_table(&self, f: &mut Frame) {
+        let active = self.app.active_row();
+
         // Build board table contents
         let content = self.app.board().iter().enumerate().map(|(rn, row)| {
             // Build board table row
-            Row::new(row.iter().map(|col| match col {
-                BoardElem::Empty => Self::board_cell(' ', Color::DarkGray),
-                BoardElem::Gray(c) => Self::board_cell(*c, Color::DarkGray),
-                BoardElem::Yellow(c) => Self::board_cell(*c, Color::Yellow),
-                BoardElem::Green(c) => Self::board_cell(*c, Color::Green),
+            // Words not in the dictionary are drawn in red
+            let invalid = self.app.row_valid(rn) == Some(false);
+            let active = active == Some(rn);
+
+            Row::new(row.iter().enumerate().map(move |(cn, col)| {
+                let auto = self.app.is_auto(rn, cn);
+
+                let cell = match col {
+                    BoardElem::Empty => Self::board_cell(' ', Color::DarkGray, auto, active),
+                    BoardElem::Unknown(c) => Self::board_cell(*c, Color::Black, auto, active),
+                    BoardElem::Gray(c) => Self::board_cell(*c, Color::DarkGray, auto, active),
+                    BoardElem::Yellow(c) => Self::board_cell(*c, Color::Yellow, auto, active),
+                    BoardElem::Green(c) => Self::board_cell(*c, Color::Green, auto, active),
+                };
+
+                if invalid {
+                    cell.fg(Color::Red)
+                } else {
+                    cell
+                }
             }))
             .height(Self::CELL_HEIGHT)
             .top_margin(if rn == 0 { 0 } else { 1 })
         });
 
+        // Number the columns, with a lock on columns with a green letter
+        let header = Row::new((0..self.app.cols()).map(|cn| {
+            let (label, colour) = if self.app.col_locked(cn) {
+                (format!("{} 🔒", cn + 1), Color::Green)
+            } else {
+                (format!("{}", cn + 1), Color::DarkGray)
+            };
+
+            Cell::from(Text::from(label).centered()).style(Style::default().fg(colour))
+        }))
+        .bottom_margin(Self::HEADER_HEIGHT - 1);
+
         // Create the board table
-        let table = Table::new(content, [Constraint::Length(Self::CELL_WIDTH); BOARD_COLS])
-            .column_spacing(Self::CELL_XSPACE)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Board")
-                    .padding(Padding::bottom(1)), // Padding for overflow bug in rataui 0.27
-            );
+        let table = Table::new(
+            content,
+            vec![Constraint::Length(Self::CELL_WIDTH); self.app.cols()],
+        )
+        .header(header)
+        .column_spacing(Self::CELL_XSPACE)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Board")
+                .padding(Padding::bottom(1)), // Padding for overflow bug in rataui 0.27
+        );
 
         // Render the table
         f.render_widget(table, self.board_rect.unwrap());
     }
 
     /// Draws a single board cell
-    fn board_cell<'b>(c: char, colour: Color) -> Cell<'b> {
+    /// Cells filled from known green letters are drawn in italics, and cells on the active row
+    /// are underlined with a bar
+    fn board_cell<'b>(c: char, colour: Color, auto: bool, active: bool) -> Cell<'b> {
         Cell::from(
-            Text::from(format!("\n{}", c))
+            Text::from(format!("\n{}\n{}", c, if active { "━━━" } else { "" }))
                 .centered()
-                .add_modifier(Modifier::BOLD),
+                .add_modifier(if auto {
+                    Modifier::ITALIC
+                } else {
+                    Modifier::BOLD
+                }),
         )
         .style(Style::default().bg(colour))
     }
@@ -224,15 +844,17 @@ Press Escape to exit"#;
         // Make sure we have a Rect
         if let Some(board_rect) = self.board_rect {
             // Make sure the position is inside the rectangle
-            if row > board_rect.top() && col > board_rect.left() {
+            let top = board_rect.top() + 1 + Self::HEADER_HEIGHT;
+
+            if row >= top && col > board_rect.left() {
                 // Work out the hit element and offset within the element
                 let col_elem = (col - (board_rect.left() + 1)) / Self::CELL_XTOTAL;
                 let col_pos = (col - (board_rect.left() + 1)) % Self::CELL_XTOTAL;
-                let row_elem = (row - (board_rect.top() + 1)) / Self::CELL_YTOTAL;
-                let row_pos = (row - (board_rect.top() + 1)) % Self::CELL_YTOTAL;
+                let row_elem = (row - top) / Self::CELL_YTOTAL;
+                let row_pos = (row - top) % Self::CELL_YTOTAL;
 
                 // Make sure the click is inside the drawn element
-                if col_elem < BOARD_COLS as u16
+                if col_elem < self.app.cols() as u16
                     && row_elem < BOARD_ROWS as u16
                     && col_pos < Self::CELL_WIDTH
                     && row_pos < Self::CELL_HEIGHT
@@ -246,46 +868,298 @@ Press Escape to exit"#;
         result
     }
 
+    /// Moves the word list highlight with an arrow key, starting at the first word
+    /// Up and down move through the words in order and left and right move between the columns
+    fn move_highlight(&mut self, key: KeyCode) {
+        // Move through the words shown
+        let shown = self.app.shown_words();
+
+        if shown.is_empty() {
+            return;
+        }
+
+        let words = shown.len();
+        let pos = self
+            .highlight
+            .and_then(|elem| shown.iter().position(|shown| *shown == elem));
+
+        // Words in each column of the words table. Grouped words aren't in columns, so left and
+        // right move by one word
+        let rows = match self.words_rect {
+            Some(rect) if !self.group => (rect.height as usize).saturating_sub(2).max(1),
+            _ => 1,
+        };
+
+        let pos = match (pos, key) {
+            (None, _) => 0,
+            (Some(pos), KeyCode::Up) => pos.saturating_sub(1),
+            (Some(pos), KeyCode::Down) => (pos + 1).min(words - 1),
+            (Some(pos), KeyCode::Left) => pos.checked_sub(rows).unwrap_or(pos),
+            (Some(pos), KeyCode::Right) if pos + rows < words => pos + rows,
+            (Some(pos), _) => pos,
+        };
+
+        self.set_highlight(Some(shown[pos]));
+    }
+
+    /// Handles a key press while typing a search
+    /// Enter finishes the search, leaving the words filtered, and escape clears it
+    fn search_key(&mut self, key: KeyCode) {
+        let mut search = self.app.search().unwrap_or_default().to_string();
+
+        match key {
+            KeyCode::Char(c) if c.is_ascii_alphabetic() || matches!(c, '?' | '_' | '.') => {
+                search.push(c);
+            }
+            KeyCode::Backspace | KeyCode::Delete => {
+                search.pop();
+            }
+            KeyCode::Enter => {
+                self.searching = false;
+            }
+            KeyCode::Esc => {
+                self.searching = false;
+                search.clear();
+            }
+            _ => (),
+        }
+
+        self.set_search(&search);
+    }
+
+    /// Handles a key press in the letter counts popup, returning true if a count was changed
+    /// Letters select the letter to change, as do the up and down arrows
+    fn counts_key(&mut self, key: KeyCode) -> bool {
+        let Some(letter) = self.counts else {
+            return false;
+        };
+
+        let step = |bound, up| BoardCommand::StepCount { letter, bound, up };
+
+        let command = match key {
+            KeyCode::Char(c) if c.is_ascii_alphabetic() => {
+                self.counts = Some(c.to_ascii_uppercase());
+                None
+            }
+            KeyCode::Up if letter > 'A' => {
+                self.counts = Some((letter as u8 - 1) as char);
+                None
+            }
+            KeyCode::Down if letter < 'Z' => {
+                self.counts = Some((letter as u8 + 1) as char);
+                None
+            }
+            KeyCode::Char('+' | '=') => Some(step(CountBound::Min, true)),
+            KeyCode::Char('-') => Some(step(CountBound::Min, false)),
+            KeyCode::Char(']') => Some(step(CountBound::Max, true)),
+            KeyCode::Char('[') => Some(step(CountBound::Max, false)),
+            KeyCode::Backspace | KeyCode::Delete => Some(BoardCommand::SetCount {
+                letter,
+                min: 0,
+                max: None,
+            }),
+            KeyCode::Esc | KeyCode::F(5) => {
+                self.counts = None;
+                None
+            }
+            _ => None,
+        };
+
+        command.is_some_and(|command| self.app.apply(command).changed())
+    }
+
+    /// Changes the search, clearing the highlight if the highlighted word is no longer shown
+    fn set_search(&mut self, search: &str) {
+        if self.app.search().unwrap_or_default() != search.to_ascii_uppercase() {
+            self.app.set_search(search);
+
+            if self
+                .highlight
+                .is_some_and(|elem| !self.app.shown_words().contains(&elem))
+            {
+                self.set_highlight(None);
+            }
+        }
+    }
+
+    /// Highlights a word list word, or clears the highlight, and previews the highlighted word
+    fn set_highlight(&mut self, highlight: Option<usize>) {
+        if highlight != self.highlight {
+            self.highlight = highlight;
+            self.preview = highlight.and_then(|elem| self.app.preview(elem));
+        }
+    }
+
     /// Draw the words table
     fn words_table(&self, f: &mut Frame) {
         if let Some(rect) = self.words_rect {
             let words = self.app.words().count().unwrap();
 
-            // Calculate the number of rows and columns
-            let rows = rect.height as usize - 2;
-            let cols = (rect.width as usize - 1) / (BOARD_COLS + 1);
+            // Words matching the search
+            let shown = self.app.shown_words();
 
-            // Create spans
-            let spans = (0..rows)
-                .map(|row| {
-                    Line::from(Span::styled(
-                        (0..cols).fold(String::new(), |mut line, col| {
-                            let elem = (col * rows) + row;
-
-                            if elem < words {
-                                if col > 0 {
-                                    line.push(' ');
-                                }
-                                line.push_str(&self.app.get_word(elem).unwrap());
-                            }
-
-                            line
-                        }),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ))
-                })
-                .collect::<Vec<_>>();
+            // Words eliminated by the last edit, shown crossed out after the words
+            let eliminated = self.app.eliminated();
+            let crossed = Style::default().add_modifier(Modifier::CROSSED_OUT | Modifier::DIM);
+
+            // Get the top suggestion
+            let best = self.app.best_word();
+
+            let spans = if self.group {
+                // Create a line for each first letter, wrapped to the width of the pane
+                self.app
+                    .word_groups()
+                    .into_iter()
+                    .map(|(c, group)| {
+                        let header = Span::styled(
+                            format!("{c} ({}):", group.len()),
+                            Style::default().fg(Color::Cyan),
+                        );
+
+                        let words = group
+                            .iter()
+                            .flat_map(|elem| self.word_spans(*elem, best, true));
+
+                        Line::from(std::iter::once(header).chain(words).collect::<Vec<_>>())
+                    })
+                    .chain((!eliminated.is_empty()).then(|| {
+                        Line::from(vec![
+                            Span::styled(
+                                format!("Eliminated ({}): ", eliminated.len()),
+                                Style::default().fg(Color::Cyan),
+                            ),
+                            Span::styled(eliminated.join(" "), crossed),
+                        ])
+                    }))
+                    .collect::<Vec<_>>()
+            } else {
+                // Width of each word including score suffix
+                let word_width = if self.app.ranking() {
+                    self.app.cols() + 4
+                } else {
+                    self.app.cols()
+                };
+
+                // Calculate the number of rows and columns
+                let rows = rect.height as usize - 2;
+                let cols = (rect.width as usize - 1) / (word_width + 1);
+
+                // Create spans
+                (0..rows)
+                    .map(|row| {
+                        Line::from(
+                            (0..cols)
+                                .flat_map(|col| {
+                                    let pos = (col * rows) + row;
+
+                                    match shown.get(pos) {
+                                        Some(elem) => self.word_spans(*elem, best, col > 0),
+                                        None => match eliminated.get(pos - shown.len()) {
+                                            // Pad to the width of a scored word
+                                            Some(word) => vec![
+                                                Span::raw(if col > 0 { " " } else { "" }),
+                                                Span::styled(word.clone(), crossed),
+                                                Span::raw(" ".repeat(word_width - word.len())),
+                                            ],
+                                            None => Vec::new(),
+                                        },
+                                    }
+                                })
+                                .collect::<Vec<_>>(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            };
 
             // Create text content
             let content = Text::from(spans);
 
-            let para = Paragraph::new(content).block(
+            // Title with the word count, order and search
+            let mut title = vec![format!("{words} found")];
+
+            match (self.app.ranking(), self.app.sort(), self.app.score_kind()) {
+                (true, _, _) if self.app.ranking_pending() => title.push("ranking".to_string()),
+                (true, WordSort::Score, Some(ScoreKind::Guesses)) => {
+                    title.push("by expected guesses".to_string())
+                }
+                (true, WordSort::Score, _) => title.push("by information".to_string()),
+                (true, WordSort::Alpha, _) => title.push("alphabetical".to_string()),
+                (false, _, _) => (),
+            }
+
+            if self.searching || self.app.search().is_some() {
+                title.push(format!(
+                    "{} matching /{}{}",
+                    shown.len(),
+                    self.app.search().unwrap_or_default(),
+                    if self.searching { "_" } else { "" }
+                ));
+            }
+
+            if !eliminated.is_empty() {
+                title.push(format!("{} eliminated", eliminated.len()));
+            }
+
+            let mut para = Paragraph::new(content).block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(format!("Words ({} found)", words)),
+                    .title(format!("Words ({})", title.join(", "))),
             );
 
+            if self.group {
+                para = para.wrap(Wrap { trim: false });
+            }
+
             f.render_widget(para, rect);
         }
     }
+
+    /// Creates the spans for a word list word, with its score if ranking and the letters
+    /// matching the search picked out
+    fn word_spans(&self, elem: usize, best: Option<usize>, space: bool) -> Vec<Span<'static>> {
+        let mut word = self.app.get_word(elem).unwrap();
+
+        if let Some(score) = self.app.get_score(elem) {
+            word.push_str(&format!(" {score:.1}"));
+        }
+
+        if space {
+            word.insert(0, ' ');
+        }
+
+        // Better scoring words are drawn brighter
+        let mut style = match self.app.get_heat(elem) {
+            Some(heat) if heat >= Self::HOT => Style::default().add_modifier(Modifier::BOLD),
+            Some(heat) if heat < Self::COLD => Style::default().add_modifier(Modifier::DIM),
+            Some(_) => Style::default(),
+            None => Style::default().add_modifier(Modifier::BOLD),
+        };
+
+        if Some(elem) == best {
+            style = style.fg(Color::Green);
+        }
+
+        if self.app.is_pinned(elem) {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+
+        if Some(elem) == self.highlight {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+
+        match self.app.search_match(elem) {
+            Some(range) => {
+                // Offset the match by the leading space
+                let start = range.start + space as usize;
+                let end = range.end + space as usize;
+
+                vec![
+                    Span::styled(word[..start].to_string(), style),
+                    Span::styled(word[start..end].to_string(), style.fg(Color::Magenta)),
+                    Span::styled(word[end..].to_string(), style),
+                ]
+            }
+            None => vec![Span::styled(word, style)],
+        }
+    }
 }
@@ -0,0 +1,169 @@
+//! Demo mode playing random games automatically
+
+use std::time::{Duration, Instant};
+
+use dictionary::Dictionary;
+use log::info;
+use solveapp::{feedback, BoardCommand, BoardElem, BoardRow, Random, SolveApp, BOARD_ROWS};
+
+/// Delay between typed letters
+const LETTER_DELAY: Duration = Duration::from_millis(200);
+/// Delay before the colours of a typed guess are shown
+const REVEAL_DELAY: Duration = Duration::from_millis(700);
+/// Delay after the colours are shown before the next guess is typed
+const ROW_DELAY: Duration = Duration::from_millis(1500);
+/// Delay after a game finishes before the next one starts
+const GAME_DELAY: Duration = Duration::from_millis(3000);
+
+/// Next demo action
+enum Step {
+    /// Choose an answer and clear the board
+    NewGame,
+    /// Type the next letter of the guess
+    Type,
+    /// Show the colours of the guess
+    Reveal,
+}
+
+/// Demo game state
+pub struct Demo {
+    /// Random number generator
+    random: Random,
+    /// Words to choose answers and first guesses from
+    words: Vec<String>,
+    /// Next action
+    step: Step,
+    /// Time the next action is due
+    next: Instant,
+    /// Answer for the current game
+    answer: String,
+    /// Guess being typed
+    guess: String,
+    /// Number of letters of the guess typed
+    typed: usize,
+    /// Completed board rows
+    rows: Vec<BoardRow>,
+    /// Number of games finished
+    games: usize,
+    /// Number of games solved
+    solved: usize,
+}
+
+impl Demo {
+    /// Creates the demo, taking answers from the dictionary
+    /// Games are repeated when the same random seed is given
+    pub fn new(dictionary: &Dictionary, seed: Option<u64>) -> Self {
+        let words = dictionary
+            .find_pattern(&"?".repeat(dictionary.word_len()), None)
+            .into_iter()
+            .map(|elem| dictionary.get_word(elem as usize))
+            .collect();
+
+        let random = Random::new(seed);
+        info!("Demo seed {}", random.seed());
+
+        Self {
+            random,
+            words,
+            step: Step::NewGame,
+            next: Instant::now(),
+            answer: String::new(),
+            guess: String::new(),
+            typed: 0,
+            rows: Vec::new(),
+            games: 0,
+            solved: 0,
+        }
+    }
+
+    /// Returns the time left until the next action is due
+    pub fn wait(&self) -> Duration {
+        self.next.saturating_duration_since(Instant::now())
+    }
+
+    /// Performs the next action on the board. Returns true if the words need recalculating
+    /// Typed letters have no colour so don't change the words
+    pub fn step(&mut self, app: &mut SolveApp) -> bool {
+        if self.words.is_empty() {
+            self.next = Instant::now() + GAME_DELAY;
+            return false;
+        }
+
+        let (delay, calculate) = match self.step {
+            Step::NewGame => {
+                self.answer = self.random_word();
+                self.rows.clear();
+                app.apply(BoardCommand::Import(Vec::new()));
+
+                self.step = Step::Type;
+                (LETTER_DELAY, true)
+            }
+            Step::Type => {
+                if self.typed == 0 {
+                    // Play the top suggestion, or a random word on the first row
+                    self.guess = app
+                        .best_word()
+                        .and_then(|elem| app.get_word(elem))
+                        .unwrap_or_else(|| self.random_word());
+                }
+
+                let c = self.guess.as_bytes()[self.typed] as char;
+                app.apply(BoardCommand::Add(c));
+                self.typed += 1;
+
+                if self.typed == self.guess.len() {
+                    self.step = Step::Reveal;
+                    (REVEAL_DELAY, false)
+                } else {
+                    (LETTER_DELAY, false)
+                }
+            }
+            Step::Reveal => {
+                let row = feedback(&self.guess, &self.answer);
+                let solved = row.iter().all(|elem| matches!(elem, BoardElem::Green(_)));
+
+                self.rows.push(row);
+                app.apply(BoardCommand::Import(self.rows.clone()));
+                self.typed = 0;
+
+                if solved || self.rows.len() == BOARD_ROWS {
+                    self.games += 1;
+
+                    if solved {
+                        self.solved += 1;
+                    }
+
+                    self.step = Step::NewGame;
+                    (GAME_DELAY, true)
+                } else {
+                    self.step = Step::Type;
+                    (ROW_DELAY, true)
+                }
+            }
+        };
+
+        self.next = Instant::now() + delay;
+
+        calculate
+    }
+
+    /// Returns the demo status for the status bar
+    pub fn status(&self) -> String {
+        let game = match self.step {
+            Step::NewGame if self.games > 0 => {
+                format!("Demo game {} answer {}", self.games, self.answer)
+            }
+            _ => format!("Demo game {}", self.games + 1),
+        };
+
+        format!(
+            "{game}, {} of {} games solved - press Escape to exit",
+            self.solved, self.games
+        )
+    }
+
+    /// Chooses a random word
+    fn random_word(&mut self) -> String {
+        self.words[self.random.index(self.words.len())].clone()
+    }
+}
@@ -0,0 +1,154 @@
+//! Input event recording and replay
+
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use std::{io, vec};
+
+use crossterm::event::{self, Event, MouseEvent, MouseEventKind};
+use log::{info, warn};
+use ratatui::buffer::Buffer;
+use serde::{Deserialize, Serialize};
+
+/// Recorded input events with the terminal size they were recorded at
+#[derive(Serialize, Deserialize)]
+pub struct Recording {
+    /// Terminal width
+    pub width: u16,
+    /// Terminal height
+    pub height: u16,
+    /// Events in the order they were read
+    events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    /// Loads a recording from a JSON file
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Ok(serde_json::from_str(&read_to_string(path)?)?)
+    }
+}
+
+/// Input event with the time it was read
+#[derive(Serialize, Deserialize)]
+struct RecordedEvent {
+    /// Milliseconds since recording started
+    time: u64,
+    /// Event read
+    event: Event,
+}
+
+/// Records events to a JSON file as they are read
+pub struct Recorder {
+    /// File to write
+    path: PathBuf,
+    /// Time recording started
+    start: Instant,
+    /// Events recorded so far
+    recording: Recording,
+}
+
+impl Recorder {
+    /// Creates a recorder for a terminal size, writing an empty recording to the file
+    pub fn new(path: &Path, (width, height): (u16, u16)) -> io::Result<Self> {
+        let recorder = Self {
+            path: path.to_path_buf(),
+            start: Instant::now(),
+            recording: Recording {
+                width,
+                height,
+                events: Vec::new(),
+            },
+        };
+
+        recorder.save()?;
+
+        info!("Recording events to {}", path.display());
+
+        Ok(recorder)
+    }
+
+    /// Adds an event to the recording and saves it, so the events up to a crash are kept
+    /// Mouse movements are left out as they don't change anything
+    fn record(&mut self, event: &Event) {
+        if let Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Moved,
+            ..
+        }) = event
+        {
+            return;
+        }
+
+        self.recording.events.push(RecordedEvent {
+            time: self.start.elapsed().as_millis() as u64,
+            event: event.clone(),
+        });
+
+        if let Err(e) = self.save() {
+            warn!("Unable to save recorded events: {e}");
+        }
+    }
+
+    /// Writes the recording to the file
+    fn save(&self) -> io::Result<()> {
+        write(&self.path, serde_json::to_string_pretty(&self.recording)?)
+    }
+}
+
+/// Source of input events
+pub enum Input {
+    /// Read events from the terminal, recording them if requested
+    Terminal(Option<Recorder>),
+    /// Replay recorded events
+    Replay(vec::IntoIter<Event>),
+}
+
+impl Input {
+    /// Creates an input replaying a recording
+    pub fn replay(recording: Recording) -> Self {
+        info!("Replaying {} events", recording.events.len());
+
+        Self::Replay(
+            recording
+                .events
+                .into_iter()
+                .map(|recorded| recorded.event)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )
+    }
+
+    /// Returns true if events are read from the terminal
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Terminal(_))
+    }
+
+    /// Returns the next event, or None when all of the recorded events have been replayed
+    pub fn read(&mut self) -> Option<io::Result<Event>> {
+        match self {
+            Self::Terminal(recorder) => {
+                let event = event::read();
+
+                if let (Some(recorder), Ok(event)) = (recorder, &event) {
+                    recorder.record(event);
+                }
+
+                Some(event)
+            }
+            Self::Replay(events) => events.next().map(Ok),
+        }
+    }
+}
+
+/// Returns the text of a screen buffer with trailing spaces removed from each line
+pub fn screen_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+
+    buffer
+        .content
+        .chunks(width.max(1))
+        .map(|line| {
+            let text: String = line.iter().map(|cell| cell.symbol()).collect();
+            format!("{}\n", text.trim_end())
+        })
+        .collect()
+}
@@ -0,0 +1,149 @@
+//! Terminal image support, detected from the environment
+
+use std::{env, io};
+
+use png::{BitDepth, ColorType, Encoder};
+
+/// Base64 characters that are encoded from each 6 bits
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Largest base64 payload in each kitty escape sequence
+const KITTY_CHUNK: usize = 4096;
+
+/// Protocol for drawing images in the terminal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// Kitty graphics protocol, also supported by Ghostty and Konsole
+    Kitty,
+    /// iTerm2 inline images, also supported by WezTerm
+    Iterm,
+}
+
+impl ImageProtocol {
+    /// Detects the image protocol supported by the terminal from its environment variables,
+    /// returning None if images can't be drawn. Setting WORDLE_GRAPHICS to kitty, iterm or none
+    /// overrides the detection
+    pub fn detect() -> Option<Self> {
+        Self::detect_from(|name| env::var(name).ok())
+    }
+
+    /// Detects the image protocol from environment variables looked up with a function
+    fn detect_from(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        match var("WORDLE_GRAPHICS").as_deref() {
+            Some("kitty") => return Some(Self::Kitty),
+            Some("iterm") => return Some(Self::Iterm),
+            Some("none") => return None,
+            _ => (),
+        }
+
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+
+        // Multiplexers don't pass the escape sequences through reliably
+        if var("TMUX").is_some() || term.starts_with("screen") {
+            return None;
+        }
+
+        if term == "xterm-kitty"
+            || var("KITTY_WINDOW_ID").is_some()
+            || var("KONSOLE_VERSION").is_some()
+            || program == "ghostty"
+        {
+            Some(Self::Kitty)
+        } else if program == "iTerm.app"
+            || program == "WezTerm"
+            || var("LC_TERMINAL").as_deref() == Some("iTerm2")
+        {
+            Some(Self::Iterm)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the escape sequences drawing an RGBA image at the cursor position, scaled to
+    /// fill a number of terminal columns and rows
+    pub fn image(
+        self,
+        (width, height): (u32, u32),
+        rgba: &[u8],
+        (cols, rows): (u16, u16),
+    ) -> io::Result<String> {
+        match self {
+            Self::Kitty => {
+                let data = base64(rgba);
+                let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+                let mut escapes = String::new();
+
+                // Responses are suppressed so they aren't read as key presses
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = u8::from(i + 1 < chunks.len());
+                    let chunk = std::str::from_utf8(chunk).unwrap();
+
+                    if i == 0 {
+                        escapes.push_str(&format!(
+                            "\x1b_Ga=T,f=32,s={width},v={height},c={cols},r={rows},q=2,m={more};\
+                             {chunk}\x1b\\"
+                        ));
+                    } else {
+                        escapes.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+                    }
+                }
+
+                Ok(escapes)
+            }
+            Self::Iterm => {
+                let png = png_encode(width, height, rgba)?;
+
+                Ok(format!(
+                    "\x1b]1337;File=inline=1;size={};width={cols};height={rows};\
+                     preserveAspectRatio=1:{}\x07",
+                    png.len(),
+                    base64(&png)
+                ))
+            }
+        }
+    }
+
+    /// Returns the escape sequence removing the images drawn, which stay on the screen with the
+    /// kitty protocol until deleted
+    pub fn clear(self) -> &'static str {
+        match self {
+            Self::Kitty => "\x1b_Ga=d,q=2\x1b\\",
+            Self::Iterm => "",
+        }
+    }
+}
+
+/// Encodes RGBA pixels as a PNG image
+fn png_encode(width: u32, height: u32, rgba: &[u8]) -> io::Result<Vec<u8>> {
+    let mut png = Vec::new();
+
+    let mut encoder = Encoder::new(&mut png, width, height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+
+    encoder.write_header()?.write_image_data(rgba)?;
+
+    Ok(png)
+}
+
+/// Encodes bytes as padded base64
+fn base64(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &byte)| {
+            bits | u32::from(byte) << (16 - i * 8)
+        });
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64[(bits >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
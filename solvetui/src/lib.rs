@@ -0,0 +1,346 @@
+//! Terminal Wordle solver
+
+use std::error::Error;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use clap_mangen::Man;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode,
+    enable_raw_mode,
+    EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use dictionary::{bundled_sha256, verify_sha256, Dictionary, WordCase};
+use ratatui::backend::{CrosstermBackend, TestBackend};
+use ratatui::Terminal;
+use solveapp::board_image::board_from_image;
+use solveapp::packs::LanguagePack;
+use solveapp::{build_info, long_version, paths, BoardRow, Strategy, BOARD_ROWS, VERSION};
+
+mod app;
+mod demo;
+mod events;
+mod graphics;
+mod splash;
+
+use app::App;
+use events::{screen_text, Input, Recorder, Recording};
+
+/// Wordle solver
+#[derive(Parser, Default)]
+#[clap(author, version = VERSION, long_version = long_version(), about)]
+struct Args {
+    /// Word list file
+    #[clap(
+        short = 'd',
+        long = "dictionary",
+        default_value_t = default_dict().into(),
+    )]
+    dictionary_file: String,
+
+    /// Expected SHA-256 hash of the word list file, checked before loading
+    /// The bundled word list is always checked
+    #[clap(long = "dict-sha256")]
+    dict_sha256: Option<String>,
+
+    /// Log file
+    #[clap(short = 'l', long = "log-file")]
+    log_file: Option<String>,
+
+    /// Accept upper and mixed case words in the word list
+    #[clap(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Import the board colours from a PNG screenshot of a game
+    #[clap(long = "import-image", requires = "words")]
+    import_image: Option<PathBuf>,
+
+    /// Words guessed in the screenshot, comma separated
+    #[clap(long = "words", value_delimiter = ',', requires = "import_image")]
+    words: Vec<String>,
+
+    /// Rank words by expected information
+    #[clap(short = 'r', long = "rank")]
+    rank: bool,
+
+    /// Word ranking strategy (entropy, expected or hybrid), overriding the settings file
+    #[clap(long = "strategy")]
+    strategy: Option<Strategy>,
+
+    /// Play random games automatically
+    #[clap(long = "demo", conflicts_with = "import_image")]
+    demo: bool,
+
+    /// Seed for the demo's random choices, so games can be repeated
+    #[clap(long = "seed", requires = "demo")]
+    seed: Option<u64>,
+
+    /// Record keyboard and mouse events to a JSON file
+    #[clap(long = "record", conflicts_with = "demo")]
+    record: Option<PathBuf>,
+
+    /// Replay events recorded with --record without a terminal, printing the final screen
+    #[clap(long = "replay", conflicts_with_all = ["demo", "record"])]
+    replay: Option<PathBuf>,
+
+    /// Show the Wordle logo before starting, as an image if the terminal supports it
+    #[clap(long = "splash")]
+    splash: bool,
+
+    /// Keep settings and word list additions in a separate profile
+    #[clap(long = "profile")]
+    profile: Option<String>,
+
+    /// Use a language pack installed in the packs directory instead of the word list file
+    #[clap(long = "pack")]
+    pack: Option<String>,
+
+    /// List the language packs installed and exit
+    #[clap(long = "list-packs")]
+    list_packs: bool,
+
+    /// Verbose output
+    #[clap(short = 'v', long = "verbose")]
+    verbose: bool,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print shell completions for the solver
+    Completions {
+        /// Shell to complete for
+        shell: Shell,
+    },
+
+    /// Print the man page for the solver in roff format
+    Manpage,
+}
+
+/// Runs with command line arguments, the first being the program name
+pub fn run<I, T>(args: I) -> Result<(), Box<dyn Error>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    // Parse command line arguments
+    let args = Args::parse_from(args);
+
+    // Print completions or the man page instead of running
+    match &args.command {
+        Some(Command::Completions { shell }) => {
+            let mut command = Args::command();
+            let name = command.get_name().to_string();
+
+            clap_complete::generate(*shell, &mut command, name, &mut io::stdout());
+            return Ok(());
+        }
+        Some(Command::Manpage) => {
+            Man::new(Args::command()).render(&mut io::stdout())?;
+            return Ok(());
+        }
+        None => (),
+    }
+
+    // Set up logging
+    solveapp::init_logging(args.log_file.as_deref())?;
+
+    // Select the profile before any settings are loaded
+    if let Some(profile) = &args.profile {
+        paths::set_profile(profile)?;
+    }
+
+    let case = if args.ignore_case {
+        WordCase::Normalise
+    } else {
+        WordCase::Lower
+    };
+
+    // List the language packs
+    if args.list_packs {
+        list_packs(case);
+        return Ok(());
+    }
+
+    // Load words from the language pack or the word list file
+    let (dictionary, guesses, file) = match &args.pack {
+        Some(name) => {
+            let pack = LanguagePack::find(name)?;
+            let (answers, guesses) = pack.load(case, args.verbose)?.into_parts();
+
+            (answers, Some(guesses), pack.file())
+        }
+        None => {
+            // Check we have a dictionary
+            if args.dictionary_file.is_empty() {
+                eprintln!(
+                    "No dictionary file given and none of the default dictionaries could be found."
+                );
+                eprintln!("Default dictionaries are:");
+
+                for d in DICTS {
+                    eprintln!("  {d}");
+                }
+
+                std::process::exit(1);
+            }
+
+            // Check the word list hasn't been corrupted or changed
+            let sha256 = args
+                .dict_sha256
+                .as_deref()
+                .or_else(|| bundled_sha256(&args.dictionary_file));
+
+            if let Some(sha256) = sha256 {
+                verify_sha256(&args.dictionary_file, sha256, args.verbose)?;
+            }
+
+            let dictionary = Dictionary::new_from_file(&args.dictionary_file, case, args.verbose)?;
+
+            (dictionary, None, args.dictionary_file.clone())
+        }
+    };
+
+    // Import the board from a screenshot
+    let board = match &args.import_image {
+        Some(path) => import_board(path, &args.words, &dictionary)?,
+        None => Vec::new(),
+    };
+
+    // Describe the build and word list for the instructions
+    let about = build_info(&file, &dictionary);
+
+    // create app
+    let mut app = App::new(
+        dictionary,
+        args.rank,
+        args.strategy,
+        &board,
+        args.demo,
+        args.seed,
+        about,
+    );
+
+    if let Some(guesses) = guesses {
+        app.set_guesses(guesses);
+    }
+
+    // Replay recorded events on a terminal of the recorded size
+    if let Some(path) = &args.replay {
+        let recording = Recording::load(path)?;
+        let backend = TestBackend::new(recording.width, recording.height);
+        let mut terminal = Terminal::new(backend)?;
+
+        app.run(&mut terminal, &mut Input::replay(recording))?;
+        print!("{}", screen_text(terminal.backend().buffer()));
+
+        if let Some(board) = app.exported_board() {
+            print!("{board}");
+        }
+
+        return Ok(());
+    }
+
+    // Start recording events
+    let recorder = match &args.record {
+        Some(path) => Some(Recorder::new(path, crossterm::terminal::size()?)?),
+        None => None,
+    };
+
+    // setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    if args.splash {
+        splash::show(&mut stdout)?;
+    }
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // run the app
+    let res = app.run(&mut terminal, &mut Input::Terminal(recorder));
+
+    // restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        println!("{:?}", err)
+    }
+
+    // Print the board for pasting into a terminal or an issue
+    if let Some(board) = app.exported_board() {
+        print!("{board}");
+    }
+
+    Ok(())
+}
+
+/// Prints the language packs installed with their descriptions
+fn list_packs(case: WordCase) {
+    let dir = paths::packs_dir().map_or("unknown".to_string(), |dir| dir.display().to_string());
+    let packs = LanguagePack::scan();
+
+    if packs.is_empty() {
+        println!("No language packs installed in {dir}");
+    } else {
+        println!("Language packs in {dir}:");
+
+        for pack in packs {
+            println!("  {}", pack.describe(case));
+        }
+    }
+}
+
+/// Reads the board from a screenshot, checking the words fit the board
+fn import_board(
+    path: &Path,
+    words: &[String],
+    dictionary: &Dictionary,
+) -> Result<Vec<BoardRow>, Box<dyn Error>> {
+    if words.len() > BOARD_ROWS {
+        return Err(format!("At most {BOARD_ROWS} words can be imported").into());
+    }
+
+    if words.iter().any(|word| word.len() != dictionary.word_len()) {
+        return Err(format!("Words must have {} letters", dictionary.word_len()).into());
+    }
+
+    Ok(board_from_image(path, words)?)
+}
+
+const DICTS: [&str; 3] = [
+    "words.txt",
+    "words.txt.gz",
+    "/etc/dictionaries-common/words",
+];
+
+fn default_dict() -> &'static str {
+    DICTS
+        .iter()
+        .find(|d| dict_valid(d).is_some())
+        .unwrap_or(&"")
+}
+
+fn dict_valid(dict: &str) -> Option<String> {
+    if Path::new(dict).is_file() {
+        Some(dict.into())
+    } else {
+        None
+    }
+}
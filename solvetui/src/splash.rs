@@ -0,0 +1,114 @@
+//! Logo splash shown before the solver starts
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::cursor::{Hide, MoveTo};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::{event, queue};
+use solveapp::VERSION;
+
+use crate::graphics::ImageProtocol;
+
+/// Wordle logo shared with the gui, as RGBA pixels
+const LOGO: &[u8] = include_bytes!("../../solvegui/assets/wordle_logo_192x192.rgba");
+
+/// Width and height of the logo in pixels
+const LOGO_SIZE: (u32, u32) = (192, 192);
+
+/// Terminal columns and rows the logo image is scaled to, cells being about twice as high as
+/// they are wide
+const LOGO_CELLS: (u16, u16) = (24, 12);
+
+/// Letters and tile colours drawn when the terminal can't show images
+const ASCII_LOGO: [(char, Color); 6] = [
+    ('W', Color::Green),
+    ('O', Color::Yellow),
+    ('R', Color::DarkGrey),
+    ('D', Color::Green),
+    ('L', Color::Yellow),
+    ('E', Color::Green),
+];
+
+/// How long the splash is shown for if no key is pressed
+const SPLASH_TIME: Duration = Duration::from_millis(1500);
+
+/// Shows the logo until a key is pressed or the splash times out, then clears the screen
+pub fn show(out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let protocol = ImageProtocol::detect();
+
+    // The ASCII logo is three rows of tiles
+    let logo_rows = match protocol {
+        Some(_) => LOGO_CELLS.1,
+        None => 3,
+    };
+
+    let top = height.saturating_sub(logo_rows + 3) / 2;
+
+    queue!(out, Hide, Clear(ClearType::All))?;
+
+    match protocol {
+        Some(protocol) => queue!(
+            out,
+            MoveTo(width.saturating_sub(LOGO_CELLS.0) / 2, top),
+            Print(protocol.image(LOGO_SIZE, LOGO, LOGO_CELLS)?)
+        )?,
+        None => ascii_logo(out, width, top)?,
+    }
+
+    let title = format!("Wordle solver {VERSION}");
+
+    centred(out, width, top + logo_rows + 1, &title)?;
+    centred(out, width, top + logo_rows + 2, "Press any key")?;
+
+    out.flush()?;
+
+    if event::poll(SPLASH_TIME)? {
+        event::read()?;
+    }
+
+    if let Some(protocol) = protocol {
+        queue!(out, Print(protocol.clear()))?;
+    }
+
+    queue!(out, Clear(ClearType::All))?;
+
+    out.flush()
+}
+
+/// Draws the logo as a row of coloured letter tiles
+fn ascii_logo(out: &mut impl Write, width: u16, top: u16) -> io::Result<()> {
+    let left = width.saturating_sub(ASCII_LOGO.len() as u16 * 4) / 2;
+
+    for row in 0..3 {
+        queue!(out, MoveTo(left, top + row))?;
+
+        for (letter, colour) in ASCII_LOGO {
+            let tile = if row == 1 {
+                format!(" {letter} ")
+            } else {
+                "   ".to_string()
+            };
+
+            queue!(
+                out,
+                SetBackgroundColor(colour),
+                SetForegroundColor(Color::White),
+                Print(tile),
+                ResetColor,
+                Print(' ')
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Draws a line of text centred on a row
+fn centred(out: &mut impl Write, width: u16, row: u16, text: &str) -> io::Result<()> {
+    let left = width.saturating_sub(text.chars().count() as u16) / 2;
+
+    queue!(out, MoveTo(left, row), Print(text))
+}
@@ -0,0 +1,15 @@
+#![warn(missing_docs)]
+
+//! Wordle solver library
+//!
+//! Re-exports the word list, solver and application state crates so they can be used together
+//! without depending on any of the terminal or graphical front ends. The application state is
+//! behind the `app` feature (on by default), screenshot import behind the `image` feature,
+//! board scripting behind the `script` feature, zstd and xz compressed word lists behind the
+//! `zstd` and `xz` features, language pack archives behind the `archive` feature and the
+//! installed language packs behind the `packs` feature
+
+pub use dictionary;
+#[cfg(feature = "app")]
+pub use solveapp;
+pub use solver;
@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::ffi::OsString;
+
+use clap::{Parser, Subcommand};
+
+/// Wordle solver
+#[derive(Parser)]
+#[clap(
+    author,
+    version,
+    about,
+    after_help = "Other commands are passed to the command line tools (eg. wordle openers)"
+)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the terminal solver
+    #[clap(disable_help_flag = true)]
+    Tui {
+        /// Terminal solver arguments (see wordle tui --help)
+        #[clap(allow_hyphen_values = true, trailing_var_arg = true)]
+        args: Vec<OsString>,
+    },
+
+    /// Run the graphical solver
+    #[cfg(feature = "gui")]
+    #[clap(disable_help_flag = true)]
+    Gui {
+        /// Graphical solver arguments (see wordle gui --help)
+        #[clap(allow_hyphen_values = true, trailing_var_arg = true)]
+        args: Vec<OsString>,
+    },
+
+    /// Run a command line tool (see wordle solve --help)
+    #[clap(disable_help_flag = true)]
+    Solve {
+        /// Command line tool and arguments
+        #[clap(allow_hyphen_values = true, trailing_var_arg = true)]
+        args: Vec<OsString>,
+    },
+
+    /// Any other command is passed to the command line tools (eg. wordle openers)
+    #[clap(external_subcommand)]
+    Other(Vec<OsString>),
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Parse command line arguments
+    let args = Args::parse();
+
+    // Run the front end with its own arguments, named after the command for its help and errors
+    match args.command {
+        Command::Tui { args } => solvetui::run(front_end_args("wordle tui", args)),
+        #[cfg(feature = "gui")]
+        Command::Gui { args } => solvegui::run(front_end_args("wordle gui", args)),
+        Command::Solve { args } | Command::Other(args) => {
+            solve::run(front_end_args("wordle", args))
+        }
+    }
+}
+
+/// Returns the arguments for a front end after its program name
+fn front_end_args(name: &str, args: Vec<OsString>) -> Vec<OsString> {
+    std::iter::once(OsString::from(name)).chain(args).collect()
+}